@@ -10,16 +10,20 @@
 //!
 //! Brackets can be generated by sweeping a window over a region of interest and
 //! looking for sign changes at the window boundary.
+//! When there is only a single guess rather than a region, `expand_bracket`
+//! searches outward from it instead.
 //!
 //! There are several pitfalls:
 //!
 //! * Roots which touch but don't cross the x-axis can't be detected using
-//!   a sliding window.
+//!   a sliding window.  `first_bracket_with_derivative` also watches df for
+//!   sign changes to catch them.
 //! * Windows which are "too large" may miss the root.  (e.g. function dips
 //!   under x axis and back up again all inside the same window bounds).
 //! * Windows which are "too large" may capture multiple roots.  Root finding
 //!   algorithms operating on the bracket will only converge on one of the
-//!   contained roots.
+//!   contained roots.  `has_interior_sign_changes` detects this, and
+//!   `BracketGenerator::split_wide` subdivides such brackets automatically.
 //!
 //! # Examples
 //! ```
@@ -41,28 +45,57 @@
 //! The resulting brackets can be passed to a root finding method like
 //! bisection() to locate the actual roots.
 //!
-use std::f64;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::f64;
 
-use wrap::RealFnEval;
+use num_traits::Float;
+
+use solver::{to_f64, RootError};
+use wrap::{RealDfEval, RealFnEval};
 
 /// Bounds represents the closed finite interval [a,b].
+///
+/// The endpoint type defaults to f64, but any `num_traits::Float` works.
 #[derive(Clone, Copy, Debug, PartialEq)]
-pub struct Bounds {
+pub struct Bounds<T = f64> {
     /// Left side of interval.
-    pub a: f64,
+    pub a: T,
 
     /// Right side of interval.
-    pub b: f64,
+    pub b: T,
 }
 
-impl Bounds {
+impl<T: Float> Bounds<T> {
     /// Create new closed interval [a, b].
     ///
-    /// This will panic if the bounds are invalid or not finite.
-    pub fn new(a: f64, b: f64) -> Bounds {
-        assert!(a <= b);
-        assert!(a.is_finite() && b.is_finite());
-        Bounds { a, b }
+    /// This will panic if the bounds are invalid or not finite.  Use `try_new`
+    /// for endpoints which come from user input.
+    pub fn new(a: T, b: T) -> Bounds<T> {
+        match Bounds::try_new(a, b) {
+            Ok(bounds) => bounds,
+            Err(e) => panic!("invalid bounds: {:?}", e),
+        }
+    }
+
+    /// Create new closed interval [a, b], or report why the endpoints are
+    /// invalid.
+    ///
+    /// A NaN endpoint counts as `BoundsError::Flipped`, since it fails a <= b.
+    pub fn try_new(a: T, b: T) -> Result<Bounds<T>, BoundsError> {
+        if a.is_nan() || b.is_nan() || a > b {
+            return Err(BoundsError::Flipped {
+                a: to_f64(a),
+                b: to_f64(b),
+            });
+        }
+        if !a.is_finite() || !b.is_finite() {
+            return Err(BoundsError::NonFinite {
+                a: to_f64(a),
+                b: to_f64(b),
+            });
+        }
+        Ok(Bounds { a, b })
     }
 
     /// Computes the midpoint of the interval.
@@ -79,44 +112,293 @@ impl Bounds {
     /// expose the IEEE 754 rounding methods so we use what is supplied by the
     /// environment rather than 'round to nearest-even'.
     ///
-    pub fn middle(&self) -> f64 {
+    pub fn middle(&self) -> T {
+        let two = T::one() + T::one();
         if self.a == -self.b {
-            0.0
+            T::zero()
         } else {
-            (self.a - self.a / 2.0) + self.b / 2.0
+            (self.a - self.a / two) + self.b / two
         }
     }
 
+    /// Computes the geometric midpoint sqrt(a*b) of a strictly positive interval.
+    ///
+    /// For intervals spanning many decades the arithmetic midpoint is dominated
+    /// by the large endpoint, while the geometric midpoint bisects in log space.
+    /// Returns None unless both endpoints are strictly positive.
+    ///
+    /// The square roots are taken separately so that a*b cannot overflow or
+    /// underflow.
+    pub fn geometric_middle(&self) -> Option<T> {
+        if self.a > T::zero() && self.b > T::zero() {
+            Some((self.a.sqrt() * self.b.sqrt()).max(self.a).min(self.b))
+        } else {
+            None
+        }
+    }
+
+    /// Split into the halves [a, mid] and [mid, b] at `middle()`.
+    pub fn bisect(&self) -> (Bounds<T>, Bounds<T>) {
+        let mid = self.middle();
+        (Bounds { a: self.a, b: mid }, Bounds { a: mid, b: self.b })
+    }
+
     /// Check if x is in interval.
-    pub fn contains(&self, x: f64) -> bool {
+    pub fn contains(&self, x: T) -> bool {
         x >= self.a && x <= self.b
     }
 
     /// Diameter of interval.
-    pub fn size(&self) -> f64 {
+    pub fn size(&self) -> T {
         self.b - self.a
     }
+
+    /// Alias for `size`, the usual name for a bracket's diameter.
+    pub fn width(&self) -> T {
+        self.size()
+    }
+}
+
+/// Reasons a pair of endpoints do not form valid Bounds.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BoundsError {
+    /// Left endpoint is greater than right endpoint (or either is NaN).
+    Flipped { a: f64, b: f64 },
+
+    /// An endpoint is infinite.
+    NonFinite { a: f64, b: f64 },
 }
 
 /// BracketGenerator is an iterator that emits root-holding brackets.
 ///
 /// Internally it is making repeated calls to first_bracket until the entire
-/// bounds are explored.
+/// bounds are explored.  Alternatively, `with_points` scans a user supplied
+/// sequence of sample points instead of fixed-size windows,
+/// `new_logarithmic` grows the windows geometrically, and `with_strategy`
+/// lets a closure pick the size of each window.
+///
+/// Calling `split_wide(samples)` additionally checks each bracket found with
+/// has_interior_sign_changes and splits any holding multiple sign changes.
+///
+/// Calling `flat_limit(k)` gives up early on functions which look identically
+/// flat, see below.
 pub struct BracketGenerator<'a, F: 'a> {
     f: &'a F,
-    remaining: Option<Bounds>,
-    window_size: f64,
+    sweep: Sweep<'a>,
+    split_samples: Option<usize>,
+    pending: Vec<Bounds>,
+    flat_limit: Option<usize>,
+    found_any: bool,
+}
+
+/// Picks the next window size given the last window and f at its endpoints.
+type WindowStrategy<'a> = dyn FnMut(&Bounds, f64, f64) -> f64 + 'a;
+
+/// How BracketGenerator chooses where to sample.
+enum Sweep<'a> {
+    /// Windows over the remaining bounds, sized by the strategy.
+    Window {
+        remaining: Option<Bounds>,
+        strategy: Box<WindowStrategy<'a>>,
+    },
+
+    /// Caller supplied sorted sample points, along with the last point seen.
+    Points {
+        points: Box<dyn Iterator<Item = f64> + 'a>,
+        last: Option<(f64, f64)>,
+    },
 }
 
 impl<'a, F> BracketGenerator<'a, F>
 where
     F: RealFnEval,
 {
-    pub fn new(f: &F, bounds: Bounds, window_size: f64) -> BracketGenerator<F> {
+    pub fn new(f: &'a F, bounds: Bounds, window_size: f64) -> BracketGenerator<'a, F> {
+        BracketGenerator::with_strategy(f, bounds, move |_: &Bounds, _: f64, _: f64| window_size)
+    }
+
+    /// Search using windows that grow geometrically by `factor`.
+    ///
+    /// Each window spans [x, factor * x], so the sweep covers a range of
+    /// magnitudes in logarithmically many steps while still resolving roots
+    /// close to the lower end.  Requires `bounds.a > 0` and `factor > 1`.
+    pub fn new_logarithmic(f: &'a F, bounds: Bounds, factor: f64) -> BracketGenerator<'a, F> {
+        assert!(
+            bounds.a > 0.0,
+            "logarithmic sweep needs a positive lower bound"
+        );
+        assert!(factor > 1.0 && factor.is_finite());
+        BracketGenerator::with_strategy(f, bounds, move |w: &Bounds, _: f64, _: f64| {
+            w.b * (factor - 1.0)
+        })
+    }
+
+    /// Search using windows sized by a user supplied strategy.
+    ///
+    /// The strategy is called as `strategy(window, f_a, f_b)` after examining
+    /// each window without a sign change, and returns the (positive) size of
+    /// the next window.  At the start of the sweep, and after each bracket is
+    /// emitted, it gets the zero-width window at the resume point instead.  This
+    /// allows adaptive logic, e.g. shrinking the window where f varies rapidly.
+    pub fn with_strategy<S>(f: &'a F, bounds: Bounds, strategy: S) -> BracketGenerator<'a, F>
+    where
+        S: 'a + FnMut(&Bounds, f64, f64) -> f64,
+    {
         BracketGenerator {
             f,
-            remaining: Some(bounds),
-            window_size,
+            sweep: Sweep::Window {
+                remaining: Some(bounds),
+                strategy: Box::new(strategy),
+            },
+            split_samples: None,
+            pending: Vec::new(),
+            flat_limit: None,
+            found_any: false,
+        }
+    }
+
+    /// Search using a custom sequence of sample points.
+    ///
+    /// The points must be finite and strictly increasing.  A bracket is emitted
+    /// wherever consecutive samples show a sign change.  This allows Chebyshev
+    /// nodes, log-spaced sequences, or anything else that concentrates samples
+    /// where roots are expected.
+    pub fn with_points<P>(f: &'a F, points: P) -> BracketGenerator<'a, F>
+    where
+        P: 'a + IntoIterator<Item = f64>,
+    {
+        BracketGenerator {
+            f,
+            sweep: Sweep::Points {
+                points: Box::new(points.into_iter()),
+                last: None,
+            },
+            split_samples: None,
+            pending: Vec::new(),
+            flat_limit: None,
+            found_any: false,
+        }
+    }
+
+    /// Split brackets holding more than one sign change across `samples`
+    /// interior points into tighter brackets with one sign change each.
+    pub fn split_wide(self, samples: usize) -> BracketGenerator<'a, F> {
+        assert!(samples > 0);
+        BracketGenerator {
+            split_samples: Some(samples),
+            ..self
+        }
+    }
+
+    /// Stop sweeping once the first `k` windows all show exactly the same f(x).
+    ///
+    /// A constant function, e.g. one that is clamped or saturated, otherwise
+    /// gets scanned window by window across the whole range.  The check only
+    /// applies before the first bracket is found, and only to window sweeps.
+    /// It is a heuristic: a function flat over the first k windows but varying
+    /// later would be missed, so pick k to cover a meaningful part of the range.
+    ///
+    /// When the early-out triggers, `next()` returns None and `try_next()`
+    /// returns `RootError::FlatFunction`.
+    pub fn flat_limit(self, k: usize) -> BracketGenerator<'a, F> {
+        assert!(k > 0);
+        BracketGenerator {
+            flat_limit: Some(k),
+            ..self
+        }
+    }
+
+    /// Pair each bracket with the orientation of its sign change.
+    ///
+    /// The orientation is `Crossing::PosToNeg` or `Crossing::NegToPos`, taken
+    /// from the sign of f at the left endpoint.  This costs one extra function
+    /// evaluation per bracket.
+    pub fn into_oriented(self) -> OrientedBrackets<'a, F> {
+        OrientedBrackets { inner: self }
+    }
+
+    /// Fallible version of `next()`.
+    ///
+    /// Reports NaN function values as `RootError::NonFiniteEvaluation` and the
+    /// `flat_limit` early-out as `RootError::FlatFunction` rather than ending
+    /// the iteration.
+    pub fn try_next(&mut self) -> Result<Option<Bounds>, RootError> {
+        if let Some(bracket) = self.pending.pop() {
+            return Ok(Some(bracket));
+        }
+
+        let bracket = match self.try_next_unsplit()? {
+            Some(bracket) => bracket,
+            None => return Ok(None),
+        };
+        self.found_any = true;
+        let samples = match self.split_samples {
+            Some(samples) if has_interior_sign_changes(self.f, &bracket, samples) > 1 => samples,
+            _ => return Ok(Some(bracket)),
+        };
+
+        // queue tight sub-brackets, reversed so they pop in order
+        let mut last: Option<(f64, f64)> = None;
+        for x_cur in sample_points(&bracket, samples) {
+            let f_cur = self.f.eval_f(x_cur);
+            if let Some((x_pre, f_pre)) = last {
+                if is_sign_change(f_pre, f_cur) {
+                    self.pending.push(Bounds::new(x_pre, x_cur));
+                }
+            }
+            last = Some((x_cur, f_cur));
+        }
+        self.pending.reverse();
+        Ok(self.pending.pop())
+    }
+
+    /// Next bracket from the underlying sweep, before any splitting.
+    fn try_next_unsplit(&mut self) -> Result<Option<Bounds>, RootError> {
+        let flat_limit = if self.found_any {
+            None
+        } else {
+            self.flat_limit
+        };
+        match self.sweep {
+            Sweep::Window {
+                ref mut remaining,
+                ref mut strategy,
+            } => {
+                let mut search_bounds = match *remaining {
+                    Some(search_bounds) => search_bounds,
+                    None => return Ok(None),
+                };
+                let result = try_first_bracket_with(self.f, &search_bounds, strategy, flat_limit);
+
+                match result {
+                    Ok(Some(ref found_bracket)) => {
+                        search_bounds.a = found_bracket.b;
+                        *remaining = Some(search_bounds);
+                    }
+                    _ => {
+                        *remaining = None;
+                    }
+                }
+                result
+            }
+            Sweep::Points {
+                ref mut points,
+                ref mut last,
+            } => loop {
+                let x_cur = match points.next() {
+                    Some(x_cur) => x_cur,
+                    None => return Ok(None),
+                };
+                assert!(x_cur.is_finite());
+                let f_cur = self.f.eval_f(x_cur);
+
+                if let Some((x_pre, f_pre)) = last.replace((x_cur, f_cur)) {
+                    assert!(x_pre < x_cur);
+                    if is_sign_change(f_pre, f_cur) {
+                        return Ok(Some(Bounds::new(x_pre, x_cur)));
+                    }
+                }
+            },
         }
     }
 }
@@ -128,19 +410,36 @@ where
     type Item = Bounds;
 
     fn next(&mut self) -> Option<Bounds> {
-        let mut search_bounds = self.remaining?;
-        let result = first_bracket(self.f, &search_bounds, self.window_size);
-
-        match result {
-            None => {
-                self.remaining = None;
-            }
-            Some(ref found_bracket) => {
-                search_bounds.a = found_bracket.b;
-                self.remaining = Some(search_bounds);
-            }
+        match self.try_next() {
+            Ok(bracket) => bracket,
+            Err(RootError::FlatFunction { .. }) => None,
+            Err(_) => panic!("f(x) evaluated to NaN"),
         }
-        result
+    }
+}
+
+/// Iterator emitting brackets along with their sign change orientation.
+///
+/// Created by `BracketGenerator::into_oriented`.
+pub struct OrientedBrackets<'a, F: 'a> {
+    inner: BracketGenerator<'a, F>,
+}
+
+impl<'a, F> Iterator for OrientedBrackets<'a, F>
+where
+    F: RealFnEval,
+{
+    type Item = (Bounds, Crossing);
+
+    fn next(&mut self) -> Option<(Bounds, Crossing)> {
+        let bracket = self.inner.next()?;
+        let f_a = self.inner.f.eval_f(bracket.a);
+        let crossing = if f_a.is_sign_positive() {
+            Crossing::PosToNeg
+        } else {
+            Crossing::NegToPos
+        };
+        Some((bracket, crossing))
     }
 }
 
@@ -149,12 +448,56 @@ where
 /// The common alternative `a * b < 0` fails if the signs differ but enough
 /// precision is lost that result is zero--i.e. multiplying two subnormal floats
 /// together.  This is illustrated in test_is_sign_change_underflow().
-pub fn is_sign_change(lhs: f64, rhs: f64) -> bool {
+pub fn is_sign_change<T: Float>(lhs: T, rhs: T) -> bool {
     assert!(!lhs.is_nan());
     assert!(!rhs.is_nan());
     lhs.signum() != rhs.signum()
 }
 
+/// Check if signs differ, treating magnitudes below `zero_eps` as zero.
+///
+/// Right at a root, f often evaluates to tiny values of either sign, e.g.
+/// 1e-300 and -1e-300, which `is_sign_change` dutifully reports as a sign
+/// change.  Here any value with |x| < zero_eps counts as zero, and zero never
+/// changes sign against anything.  With `zero_eps` of zero this matches
+/// `is_sign_change` except for the handling of exact (signed) zeros.
+pub fn is_sign_change_eps(lhs: f64, rhs: f64, zero_eps: f64) -> bool {
+    assert!(zero_eps >= 0.0);
+    if lhs.abs() < zero_eps || rhs.abs() < zero_eps || lhs == 0.0 || rhs == 0.0 {
+        return false;
+    }
+    is_sign_change(lhs, rhs)
+}
+
+/// Required orientation of the sign change across a bracket [a, b].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Crossing {
+    /// f(a) positive and f(b) negative, i.e. f decreasing through the root.
+    PosToNeg,
+
+    /// f(a) negative and f(b) positive, i.e. f increasing through the root.
+    NegToPos,
+
+    /// Any sign change.
+    Either,
+}
+
+impl Crossing {
+    /// Check if f(a) and f(b) change sign with the required orientation.
+    ///
+    /// This will panic if either value is NaN.
+    pub fn matches(&self, f_a: f64, f_b: f64) -> bool {
+        if !is_sign_change(f_a, f_b) {
+            return false;
+        }
+        match *self {
+            Crossing::PosToNeg => f_a.is_sign_positive(),
+            Crossing::NegToPos => f_a.is_sign_negative(),
+            Crossing::Either => true,
+        }
+    }
+}
+
 /// Scans interval [a, b] and emits the first bracket containing a sign change.
 ///
 /// For a continuous function the Intermediate Value Theorem guarantees that the
@@ -165,32 +508,376 @@ where
     F: RealFnEval,
 {
     assert!(window_size > 0.0);
+    try_first_bracket(f, bounds, window_size, None).expect("f(x) evaluated to NaN")
+}
+
+/// Scans interval [a, b] like `first_bracket`, but also emits windows holding
+/// a touching root.
+///
+/// Roots of even multiplicity, like x^2 at zero, touch the x-axis without f
+/// changing sign.  A window is flagged anyway when df changes sign across it
+/// and f at the critical point is near zero, meaning within sqrt(ε) of the
+/// larger |f| at the window's ends.  The critical point is located by
+/// bisecting on df, costing up to 64 extra evaluations of df per candidate.
+///
+/// The returned window need not show a sign change in f, so it is not a valid
+/// bracket for bisection.  It is meant for a minimizer or a multiplicity-aware
+/// solver, e.g. Newton-Raphson from the middle, checked with
+/// `estimate_multiplicity`.  Minima which come near zero without touching are
+/// flagged too.
+pub fn first_bracket_with_derivative<F, D>(
+    f: &F,
+    df: &D,
+    bounds: &Bounds,
+    window_size: f64,
+) -> Option<Bounds>
+where
+    F: RealFnEval,
+    D: RealDfEval,
+{
+    assert!(window_size > 0.0);
 
     let mut win = Bounds {
         a: bounds.a,
         b: (bounds.a + window_size).min(bounds.b),
     };
-
     let mut f_a = f.eval_f(win.a);
+    let mut df_a = df.eval_df(win.a);
     while win.a < bounds.b {
         let f_b = f.eval_f(win.b);
+        let df_b = df.eval_df(win.b);
 
-        // found root or singularity
+        // ordinary crossing root or singularity
         if is_sign_change(f_a, f_b) {
             return Some(win);
         }
 
+        // extremum which may kiss the axis
+        if is_sign_change(df_a, df_b) {
+            let x_crit = critical_point(df, &win, df_a);
+            let scale = f_a.abs().max(f_b.abs());
+            if f.eval_f(x_crit).abs() <= f64::EPSILON.sqrt() * scale {
+                return Some(win);
+            }
+        }
+
         f_a = f_b;
+        df_a = df_b;
         win.a = win.b;
         win.b = (win.b + window_size).min(bounds.b);
     }
     None
 }
 
+/// Locate where df changes sign inside the window by bisection.
+fn critical_point<D>(df: &D, win: &Bounds, df_a: f64) -> f64
+where
+    D: RealDfEval,
+{
+    let mut win = *win;
+    let mut df_a = df_a;
+    for _ in 0..64 {
+        let mid = win.middle();
+        if mid == win.a || mid == win.b {
+            break;
+        }
+        let df_mid = df.eval_df(mid);
+        if is_sign_change(df_a, df_mid) {
+            win.b = mid;
+        } else {
+            win.a = mid;
+            df_a = df_mid;
+        }
+    }
+    win.middle()
+}
+
+/// Scan for first bracket, reporting NaN function values rather than panicking.
+pub(crate) fn try_first_bracket<F>(
+    f: &F,
+    bounds: &Bounds,
+    window_size: f64,
+    flat_limit: Option<usize>,
+) -> Result<Option<Bounds>, RootError>
+where
+    F: RealFnEval,
+{
+    let mut fixed = |_: &Bounds, _: f64, _: f64| window_size;
+    try_first_bracket_with(f, bounds, &mut fixed, flat_limit)
+}
+
+/// Scan for first bracket with each window size chosen by `strategy`.
+fn try_first_bracket_with<F, S>(
+    f: &F,
+    bounds: &Bounds,
+    strategy: &mut S,
+    mut flat_limit: Option<usize>,
+) -> Result<Option<Bounds>, RootError>
+where
+    F: RealFnEval,
+    S: ?Sized + FnMut(&Bounds, f64, f64) -> f64,
+{
+    let mut f_a = f.eval_f(bounds.a);
+    if f_a.is_nan() {
+        return Err(RootError::NonFiniteEvaluation { x: bounds.a });
+    }
+
+    let start = Bounds {
+        a: bounds.a,
+        b: bounds.a,
+    };
+    let window_size = strategy(&start, f_a, f_a);
+    assert!(window_size > 0.0);
+    let mut win = Bounds {
+        a: bounds.a,
+        b: (bounds.a + window_size).min(bounds.b),
+    };
+
+    let f_first = f_a;
+    let mut flat_windows = 0;
+    while win.a < bounds.b {
+        let f_b = f.eval_f(win.b);
+        if f_b.is_nan() {
+            return Err(RootError::NonFiniteEvaluation { x: win.b });
+        }
+
+        // found root or singularity
+        if is_sign_change(f_a, f_b) {
+            return Ok(Some(win));
+        }
+
+        // give up on functions that look identically flat
+        if let Some(k) = flat_limit {
+            if f_b != f_first {
+                flat_limit = None;
+            } else {
+                flat_windows += 1;
+                if flat_windows >= k {
+                    return Err(RootError::FlatFunction {
+                        a: bounds.a,
+                        b: win.b,
+                    });
+                }
+            }
+        }
+
+        let window_size = strategy(&win, f_a, f_b);
+        assert!(window_size > 0.0);
+        f_a = f_b;
+        win.a = win.b;
+        win.b = (win.b + window_size).min(bounds.b);
+    }
+    Ok(None)
+}
+
+/// Search outward from a single guess `x0` until a sign change is straddled.
+///
+/// Probes x0 ± step, growing step geometrically by `factor` on each of at most
+/// `max_iter` iterations.  Each iteration compares f at the new probes against
+/// the previous ones on the same side, so the returned bracket is just the
+/// newly explored stretch rather than the whole symmetric interval.  If both
+/// sides change sign in the same iteration, the right side is returned.
+///
+/// Functions without a sign change, e.g. x^2 + 1 or the tangent root of x^2,
+/// exhaust `max_iter` and return None after 2 * `max_iter` + 1 evaluations.
+/// Keep `max_iter` modest since the probes grow exponentially.  None is also
+/// returned if f evaluates to NaN or the probes overflow.
+pub fn expand_bracket<F>(
+    f: &F,
+    x0: f64,
+    initial_step: f64,
+    factor: f64,
+    max_iter: usize,
+) -> Option<Bounds>
+where
+    F: RealFnEval,
+{
+    assert!(x0.is_finite());
+    assert!(initial_step > 0.0 && initial_step.is_finite());
+    assert!(factor > 1.0 && factor.is_finite());
+
+    let f0 = f.eval_f(x0);
+    if f0.is_nan() {
+        return None;
+    }
+    let (mut lo, mut f_lo) = (x0, f0);
+    let (mut hi, mut f_hi) = (x0, f0);
+
+    let mut step = initial_step;
+    for _ in 0..max_iter {
+        let a = x0 - step;
+        let b = x0 + step;
+        if !a.is_finite() || !b.is_finite() {
+            return None;
+        }
+
+        let f_a = f.eval_f(a);
+        let f_b = f.eval_f(b);
+        if f_a.is_nan() || f_b.is_nan() {
+            return None;
+        }
+
+        if is_sign_change(f_hi, f_b) {
+            return Some(Bounds::new(hi, b));
+        }
+        if is_sign_change(f_a, f_lo) {
+            return Some(Bounds::new(a, lo));
+        }
+
+        lo = a;
+        f_lo = f_a;
+        hi = b;
+        f_hi = f_b;
+        step *= factor;
+    }
+    None
+}
+
+/// Counts sign changes of f across the bracket sampled at `samples` evenly
+/// spaced interior points.
+///
+/// A tight bracket gives one.  Anything larger means the bracket holds several
+/// roots (or singularities) and the user may want to subdivide it.  A zero can
+/// still hide an even number of roots between samples.
+pub fn has_interior_sign_changes<F>(f: &F, b: &Bounds, samples: usize) -> usize
+where
+    F: RealFnEval,
+{
+    let mut count = 0;
+    let mut f_pre: Option<f64> = None;
+    for x in sample_points(b, samples) {
+        let f_cur = f.eval_f(x);
+        if let Some(f_pre) = f_pre {
+            if is_sign_change(f_pre, f_cur) {
+                count += 1;
+            }
+        }
+        f_pre = Some(f_cur);
+    }
+    count
+}
+
+/// Counts sign changes of f sampled at `samples` evenly spaced points across
+/// the bounds, endpoints included.
+///
+/// This is a cheap check that a root search found everything: with fine
+/// enough sampling the count matches the number of simple roots.  Roots of
+/// even multiplicity, e.g. the tangent root of x^2, contribute zero since f
+/// does not change sign there.  Two roots falling between adjacent samples
+/// also cancel out.
+pub fn sign_changes<F>(f: &F, bounds: &Bounds, samples: usize) -> usize
+where
+    F: RealFnEval,
+{
+    assert!(samples >= 2);
+    has_interior_sign_changes(f, bounds, samples - 2)
+}
+
+/// Parallel counterpart of `BracketGenerator::new(f, bounds, window_size)`.
+///
+/// The window endpoints are laid out exactly as the sequential sweep would,
+/// f is evaluated at all of them in parallel, and the sign-change windows are
+/// collected in order.  Each pair of neighbouring endpoints is examined once,
+/// including pairs straddling the chunks handed to different threads, so a
+/// sign change at a seam is neither missed nor reported twice.  The result
+/// matches collecting the sequential generator.
+///
+/// A NaN at any window endpoint returns `RootError::NonFiniteEvaluation` for
+/// the first such endpoint, the error `try_next` reports on reaching it.  Since
+/// f is evaluated everywhere up front, the brackets before it are not returned.
+///
+/// Worthwhile only when f is expensive or the sweep very fine, since all
+/// window endpoints are held in memory.  Requires the `rayon` feature.
+#[cfg(feature = "rayon")]
+pub fn find_brackets_parallel<F>(
+    f: &F,
+    bounds: &Bounds,
+    window_size: f64,
+) -> Result<Vec<Bounds>, RootError>
+where
+    F: Fn(f64) -> f64 + Sync,
+{
+    use rayon::prelude::*;
+
+    assert!(window_size > 0.0);
+
+    // same accumulation as the sequential sweep, so the endpoints match bit for bit
+    let mut points = vec![bounds.a];
+    let mut x = bounds.a;
+    while x < bounds.b {
+        x = (x + window_size).min(bounds.b);
+        points.push(x);
+    }
+
+    let values: Vec<f64> = points.par_iter().map(|&x| f(x)).collect();
+    if let Some(i) = values.iter().position(|v| v.is_nan()) {
+        return Err(RootError::NonFiniteEvaluation { x: points[i] });
+    }
+
+    Ok((1..values.len())
+        .into_par_iter()
+        .filter(|&i| is_sign_change(values[i - 1], values[i]))
+        .map(|i| Bounds::new(points[i - 1], points[i]))
+        .collect())
+}
+
+/// Subdivide a bracket until each piece holds a single sign change.
+///
+/// Each interval is sampled at a handful of interior points.  Intervals without
+/// sign changes are dropped, those with exactly one are kept, and the rest are
+/// bisected and examined again, at most `max_depth` times.  Pieces still
+/// holding several sign changes at the depth limit are kept only if the count
+/// is odd, i.e. if f changes sign across the endpoints.
+///
+/// The brackets are returned in increasing order.  Like any sampling scheme,
+/// roots closer together than the sample spacing may be missed.
+pub fn isolate_roots<F>(f: &F, b: &Bounds, max_depth: usize) -> Vec<Bounds>
+where
+    F: RealFnEval,
+{
+    let mut isolated = Vec::new();
+    isolate_into(f, *b, max_depth, &mut isolated);
+    isolated
+}
+
+/// Recursive worker for `isolate_roots`.
+fn isolate_into<F>(f: &F, b: Bounds, depth: usize, isolated: &mut Vec<Bounds>)
+where
+    F: RealFnEval,
+{
+    let changes = has_interior_sign_changes(f, &b, 8);
+    if changes == 0 {
+        return;
+    }
+    if changes == 1 || depth == 0 {
+        if changes % 2 == 1 {
+            isolated.push(b);
+        }
+        return;
+    }
+
+    let (left, right) = b.bisect();
+    isolate_into(f, left, depth - 1, isolated);
+    isolate_into(f, right, depth - 1, isolated);
+}
+
+/// Endpoints of the bracket along with `samples` evenly spaced interior points.
+fn sample_points(b: &Bounds, samples: usize) -> impl Iterator<Item = f64> {
+    let (a, b) = (b.a, b.b);
+    let n = samples + 1;
+    (0..(n + 1)).map(move |i| {
+        if i == n {
+            b
+        } else {
+            a + (b - a) * (i as f64) / (n as f64)
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use wrap::RealFn;
+    use wrap::{CountingFn, RealFn, RealFnAndFirst};
 
     #[test]
     fn test_bounds_new_valid() {
@@ -203,6 +890,99 @@ mod tests {
         assert_eq!(b.b, 2.0);
     }
 
+    #[test]
+    fn test_crossing_matches() {
+        assert!(Crossing::PosToNeg.matches(1.0, -1.0));
+        assert!(!Crossing::PosToNeg.matches(-1.0, 1.0));
+        assert!(Crossing::NegToPos.matches(-1.0, 1.0));
+        assert!(!Crossing::NegToPos.matches(1.0, -1.0));
+        assert!(Crossing::Either.matches(1.0, -1.0));
+        assert!(Crossing::Either.matches(-1.0, 1.0));
+
+        // no sign change never matches
+        assert!(!Crossing::Either.matches(1.0, 2.0));
+        assert!(!Crossing::NegToPos.matches(-1.0, -2.0));
+    }
+
+    #[test]
+    fn test_has_interior_sign_changes() {
+        let fin = |x: f64| x.sin();
+        let f = RealFn::new(&fin);
+
+        // roots at 0 and pi, yet no sign change across the endpoints
+        let wide = Bounds::new(-0.5, 3.5);
+        assert_eq!(has_interior_sign_changes(&f, &wide, 20), 2);
+        assert_eq!(
+            has_interior_sign_changes(&f, &Bounds::new(-0.5, 0.5), 20),
+            1
+        );
+        assert_eq!(has_interior_sign_changes(&f, &Bounds::new(0.5, 2.5), 20), 0);
+    }
+
+    #[test]
+    fn test_sign_changes() {
+        // roots at 0, pi, and 2pi
+        let fin = |x: f64| x.sin();
+        let f = RealFn::new(&fin);
+        assert_eq!(sign_changes(&f, &Bounds::new(-0.1, 6.3), 100), 3);
+
+        // endpoints only
+        assert_eq!(sign_changes(&f, &Bounds::new(-0.1, 6.3), 2), 1);
+
+        // tangent root at 1 contributes nothing
+        let fin = |x: f64| (x - 1.0) * (x - 1.0) * (x + 1.0);
+        let f = RealFn::new(&fin);
+        assert_eq!(sign_changes(&f, &Bounds::new(-2.0, 2.0), 101), 1);
+    }
+
+    #[test]
+    fn test_isolate_roots() {
+        // roots near -0.408, 0.715, and 4.307
+        let fin = |x: f64| x.exp() - 4.0 * x * x;
+        let f = RealFn::new(&fin);
+
+        let b = Bounds::new(-1.0, 5.0);
+        let isolated = isolate_roots(&f, &b, 10);
+        assert_eq!(isolated.len(), 3);
+        for (b, root) in isolated.iter().zip([-0.408, 0.715, 4.307].iter()) {
+            assert!(b.contains(*root), "{:?} missing {}", b, root);
+            assert_eq!(has_interior_sign_changes(&f, b, 100), 1);
+        }
+    }
+
+    #[test]
+    fn test_isolate_roots_depth_limit() {
+        let fin = |x: f64| x.exp() - 4.0 * x * x;
+        let f = RealFn::new(&fin);
+        let b = Bounds::new(-1.0, 5.0);
+
+        // odd total number of sign changes, so kept whole
+        assert_eq!(isolate_roots(&f, &b, 0), vec![b]);
+    }
+
+    #[test]
+    fn test_bracket_generator_split_wide() {
+        // roots at 0, pi, 2pi inside a single wide window
+        let fin = |x: f64| x.sin();
+        let f = RealFn::new(&fin);
+        let bounds = Bounds::new(-0.5, 7.0);
+
+        let unsplit: Vec<Bounds> = BracketGenerator::new(&f, bounds, 10.0).collect();
+        assert_eq!(unsplit, vec![bounds]);
+
+        let split: Vec<Bounds> = BracketGenerator::new(&f, bounds, 10.0)
+            .split_wide(100)
+            .collect();
+        assert_eq!(split.len(), 3);
+        for (b, root) in split
+            .iter()
+            .zip([0.0, f64::consts::PI, 2.0 * f64::consts::PI].iter())
+        {
+            assert!(b.contains(*root));
+            assert!(b.size() < 0.1);
+        }
+    }
+
     #[test]
     #[should_panic]
     fn test_bounds_new_flipped_extents() {
@@ -215,6 +995,51 @@ mod tests {
         Bounds::new(f64::NAN, -2.0);
     }
 
+    #[test]
+    fn test_bounds_try_new() {
+        assert_eq!(Bounds::try_new(-2.0, 2.0), Ok(Bounds::new(-2.0, 2.0)));
+        assert_eq!(Bounds::try_new(2.0, 2.0), Ok(Bounds::new(2.0, 2.0)));
+
+        // flipped extents
+        assert_eq!(
+            Bounds::try_new(2.0, -2.0),
+            Err(BoundsError::Flipped { a: 2.0, b: -2.0 })
+        );
+
+        // nan on either side
+        match Bounds::try_new(f64::NAN, -2.0) {
+            Err(BoundsError::Flipped { a, b }) => {
+                assert!(a.is_nan());
+                assert_eq!(b, -2.0);
+            }
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+        match Bounds::try_new(-2.0, f64::NAN) {
+            Err(BoundsError::Flipped { .. }) => {}
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+
+        // infinite endpoints
+        assert_eq!(
+            Bounds::try_new(f64::NEG_INFINITY, f64::INFINITY),
+            Err(BoundsError::NonFinite {
+                a: f64::NEG_INFINITY,
+                b: f64::INFINITY,
+            })
+        );
+        assert_eq!(
+            Bounds::try_new(0.0, f64::INFINITY),
+            Err(BoundsError::NonFinite {
+                a: 0.0,
+                b: f64::INFINITY,
+            })
+        );
+    }
+
     #[test]
     fn test_bounds_new_signed_zeros() {
         let a = 0.0;
@@ -246,6 +1071,22 @@ mod tests {
         assert!(b.contains(b.middle()));
     }
 
+    #[test]
+    fn test_bounds_geometric_middle() {
+        assert_eq!(Bounds::new(1.0, 100.0).geometric_middle(), Some(10.0));
+        assert_eq!(Bounds::new(4.0, 4.0).geometric_middle(), Some(4.0));
+
+        // no overflow or underflow
+        let b = Bounds::new(1e300, f64::MAX);
+        assert!(b.contains(b.geometric_middle().unwrap()));
+        let b = Bounds::new(f64::MIN_POSITIVE / 20.0, 1e-300);
+        assert!(b.contains(b.geometric_middle().unwrap()));
+
+        // needs strictly positive endpoints
+        assert_eq!(Bounds::new(0.0, 100.0).geometric_middle(), None);
+        assert_eq!(Bounds::new(-100.0, -1.0).geometric_middle(), None);
+    }
+
     #[test]
     fn test_bounds_middle_symmetric() {
         // easy case
@@ -280,21 +1121,34 @@ mod tests {
         assert_eq!(b.middle(), v);
     }
 
+    #[test]
+    fn test_bounds_bisect() {
+        for &(a, b) in [(-2.0, 2.0), (0.1, 0.7), (-1e308, 1.7e308), (3.0, 3.0)].iter() {
+            let bounds = Bounds::new(a, b);
+            let (left, right) = bounds.bisect();
+            assert_eq!(left.a, a);
+            assert_eq!(right.b, b);
+            assert_eq!(left.b, right.a);
+            assert_eq!(left.b, bounds.middle());
+            assert!(left.size() >= 0.0 && right.size() >= 0.0);
+        }
+    }
+
     #[test]
     fn test_bounds_contains() {
         let b = Bounds::new(28.0, 31.2);
 
         // outside
-        assert_eq!(b.contains(-29.0), false);
-        assert_eq!(b.contains(31.21), false);
-        assert_eq!(b.contains(f64::NAN), false);
+        assert!(!b.contains(-29.0));
+        assert!(!b.contains(31.21));
+        assert!(!b.contains(f64::NAN));
 
         // on end points
-        assert_eq!(b.contains(28.0), true);
-        assert_eq!(b.contains(31.2), true);
+        assert!(b.contains(28.0));
+        assert!(b.contains(31.2));
 
         // inside
-        assert_eq!(b.contains(29.631), true);
+        assert!(b.contains(29.631));
     }
 
     #[test]
@@ -305,6 +1159,16 @@ mod tests {
         assert_eq!(6.4, Bounds::new(-3.2, 3.2).size());
     }
 
+    #[test]
+    fn test_bounds_width() {
+        assert_eq!(3.0, Bounds::new(2.0, 5.0).width());
+        assert_eq!(0.0, Bounds::new(2.0, 2.0).width());
+        assert_eq!(
+            Bounds::new(-7.2, -3.2).size(),
+            Bounds::new(-7.2, -3.2).width()
+        );
+    }
+
     #[test]
     fn test_bracket_generator_hits() {
         let fin = |x: f64| x.sin();
@@ -324,6 +1188,28 @@ mod tests {
         assert_eq!(Bounds::new(11.9, 4.0 * pi + 0.1), results[4]); // 4pi
     }
 
+    #[test]
+    fn test_bracket_generator_oriented() {
+        let fin = |x: f64| x.sin();
+        let f = RealFn::new(&fin);
+        let pi = f64::consts::PI;
+        let b = Bounds::new(-0.1, 4.0 * pi + 0.1);
+
+        let results: Vec<(Bounds, Crossing)> =
+            BracketGenerator::new(&f, b, 1.0).into_oriented().collect();
+        assert_eq!(results.len(), 5);
+
+        // sin rises through 0, 2pi, 4pi and falls through pi, 3pi
+        assert_eq!((Bounds::new(-0.1, 0.9), Crossing::NegToPos), results[0]);
+        assert_eq!(Crossing::PosToNeg, results[1].1);
+        assert_eq!(Crossing::NegToPos, results[2].1);
+        assert_eq!(Crossing::PosToNeg, results[3].1);
+        assert_eq!(Crossing::NegToPos, results[4].1);
+        for &(bracket, crossing) in &results {
+            assert!(crossing.matches(fin(bracket.a), fin(bracket.b)));
+        }
+    }
+
     #[test]
     fn test_bracket_generator_empty() {
         let fin = |x: f64| x.sin();
@@ -334,6 +1220,42 @@ mod tests {
         assert!(gen.next().is_none());
     }
 
+    #[test]
+    fn test_bracket_generator_flat_limit() {
+        let fin = |_: f64| 33.0;
+        let inner = RealFn::new(&fin);
+        let f = CountingFn::new(&inner);
+        let b = Bounds::new(-1e6, 1e6);
+
+        assert!(BracketGenerator::new(&f, b, 1.0).next().is_none());
+        let full_evals = f.f_evals();
+
+        let f = CountingFn::new(&inner);
+        let mut gen = BracketGenerator::new(&f, b, 1.0).flat_limit(10);
+        match gen.try_next() {
+            Err(RootError::FlatFunction { .. }) => {}
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+        assert_eq!(f.f_evals(), 11);
+        assert!(f.f_evals() < full_evals);
+    }
+
+    #[test]
+    fn test_bracket_generator_flat_limit_partly_flat() {
+        // flat for x < 5, then a root at 8.5
+        let fin = |x: f64| if x < 5.0 { -3.0 } else { x - 8.5 };
+        let f = RealFn::new(&fin);
+        let b = Bounds::new(0.0, 10.0);
+
+        let brackets: Vec<Bounds> = BracketGenerator::new(&f, b, 1.0).flat_limit(3).collect();
+        assert!(brackets.is_empty());
+
+        let brackets: Vec<Bounds> = BracketGenerator::new(&f, b, 1.0).flat_limit(5).collect();
+        assert_eq!(brackets, vec![Bounds::new(8.0, 9.0)]);
+    }
+
     #[test]
     #[should_panic]
     fn test_bracket_generator_window_negative() {
@@ -344,39 +1266,218 @@ mod tests {
         let _brackets: Vec<Bounds> = BracketGenerator::new(&f, b, -0.1).collect();
     }
 
+    #[test]
+    fn test_bracket_generator_with_strategy() {
+        // oscillation speeds up along with the amplitude, roots at sqrt(k pi)
+        let fin = |x: f64| x * x * (x * x).sin();
+        let f = RealFn::new(&fin);
+        let b = Bounds::new(0.5, 6.0);
+        let expected = sign_changes(&f, &b, 100000);
+
+        // fixed windows miss the closely spaced roots
+        let fixed: Vec<Bounds> = BracketGenerator::new(&f, b, 0.5).collect();
+        assert!(fixed.len() < expected);
+
+        // halve the window wherever |f| is large, back to 0.5 elsewhere
+        let strategy = |w: &Bounds, _f_a: f64, f_b: f64| {
+            if f_b.abs() > 1.0 {
+                (w.size() / 2.0).max(0.05)
+            } else {
+                0.5
+            }
+        };
+        let adaptive: Vec<Bounds> = BracketGenerator::with_strategy(&f, b, strategy).collect();
+        assert_eq!(adaptive.len(), expected);
+        for w in adaptive.windows(2) {
+            assert!(w[0].b <= w[1].a);
+        }
+    }
+
+    #[test]
+    fn test_bracket_generator_logarithmic() {
+        // Ford95 Example Nine, root near 0.0095 in a range spanning 1e5
+        let fin = |x: f64| x.recip() + x.ln() - 100.0;
+        let inner = RealFn::new(&fin);
+        let f = CountingFn::new(&inner);
+        let b = Bounds::new(0.001, 100.0);
+
+        let results: Vec<Bounds> = BracketGenerator::new_logarithmic(&f, b, 1.1).collect();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].a <= 0.0095556044375379 && 0.0095556044375379 <= results[0].b);
+        assert!((results[0].b / results[0].a - 1.1).abs() < 1e-12);
+
+        // log(1e5) / log(1.1) is about 121 windows
+        assert!(f.f_evals() < 250, "evals={}", f.f_evals());
+
+        // the last window is clipped to the upper bound
+        let gin = |x: f64| x - 99.0;
+        let g = RealFn::new(&gin);
+        let results: Vec<Bounds> = BracketGenerator::new_logarithmic(&g, b, 2.0).collect();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].b, 100.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bracket_generator_logarithmic_nonpositive() {
+        let fin = |x: f64| x;
+        let f = RealFn::new(&fin);
+        let _ = BracketGenerator::new_logarithmic(&f, Bounds::new(0.0, 1.0), 2.0);
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn test_find_brackets_parallel() {
+        let fin = |x: f64| x.sin();
+        let f = RealFn::new(&fin);
+
+        for &(a, b, window) in &[(-0.1, 6.3, 0.1), (-0.1, 100.0, 1e-3), (0.0, 31.5, 0.01)] {
+            let bounds = Bounds::new(a, b);
+            let sequential: Vec<Bounds> = BracketGenerator::new(&f, bounds, window).collect();
+            let parallel = find_brackets_parallel(&fin, &bounds, window).expect("brackets");
+            assert_eq!(sequential, parallel);
+        }
+
+        // root exactly on a window endpoint
+        let gin = |x: f64| x - 0.5;
+        let g = RealFn::new(&gin);
+        let bounds = Bounds::new(0.0, 1.0);
+        let sequential: Vec<Bounds> = BracketGenerator::new(&g, bounds, 0.25).collect();
+        assert_eq!(
+            sequential,
+            find_brackets_parallel(&gin, &bounds, 0.25).expect("brackets")
+        );
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_find_brackets_parallel_seams() {
+        // sign flips at every integer, so every pair of endpoints is a bracket
+        // and each seam between thread chunks must show up exactly once
+        let fin = |x: f64| {
+            if (x.round() as i64) % 2 == 0 {
+                1.0
+            } else {
+                -1.0
+            }
+        };
+        let bounds = Bounds::new(0.0, 20000.0);
+        let parallel = find_brackets_parallel(&fin, &bounds, 1.0).expect("brackets");
+        assert_eq!(parallel.len(), 20000);
+        for (i, b) in parallel.iter().enumerate() {
+            assert_eq!(*b, Bounds::new(i as f64, (i + 1) as f64));
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_find_brackets_parallel_nan() {
+        let fin = |x: f64| if x > 2.05 { f64::NAN } else { x.sin() - 0.5 };
+        let f = RealFn::new(&fin);
+        let bounds = Bounds::new(0.0, 5.0);
+
+        let mut gen = BracketGenerator::new(&f, bounds, 0.1);
+        let nan_at = loop {
+            match gen.try_next() {
+                Ok(Some(_)) => {}
+                Err(RootError::NonFiniteEvaluation { x }) => break x,
+                _ => panic!("incorrect error type"),
+            }
+        };
+        match find_brackets_parallel(&fin, &bounds, 0.1) {
+            Err(RootError::NonFiniteEvaluation { x }) => assert_eq!(x, nan_at),
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+    }
+
+    #[test]
+    fn test_bracket_generator_with_points() {
+        // roots at 0.01, 1, and 100
+        let fin = |x: f64| (x - 0.01) * (x - 1.0) * (x - 100.0);
+        let f = RealFn::new(&fin);
+
+        let points = vec![0.001, 0.005, 0.02, 0.5, 2.0, 50.0, 200.0];
+        let results: Vec<Bounds> = BracketGenerator::with_points(&f, points).collect();
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(Bounds::new(0.005, 0.02), results[0]);
+        assert_eq!(Bounds::new(0.5, 2.0), results[1]);
+        assert_eq!(Bounds::new(50.0, 200.0), results[2]);
+    }
+
+    #[test]
+    fn test_bracket_generator_with_points_log_spaced() {
+        let fin = |x: f64| x.ln() - 3.0;
+        let f = RealFn::new(&fin);
+
+        let points = (-3..4).map(|e| 10f64.powi(e));
+        let results: Vec<Bounds> = BracketGenerator::with_points(&f, points).collect();
+
+        // root at e^3 ~ 20.09
+        assert_eq!(results, vec![Bounds::new(10.0, 100.0)]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bracket_generator_with_points_unsorted() {
+        let fin = |x: f64| x;
+        let f = RealFn::new(&fin);
+
+        let _brackets: Vec<Bounds> =
+            BracketGenerator::with_points(&f, vec![-1.0, 2.0, 1.0]).collect();
+    }
+
     #[test]
     fn test_is_sign_change() {
         // easy peasy
-        assert_eq!(is_sign_change(-1.0, -1.0), false);
-        assert_eq!(is_sign_change(1.0, 1.0), false);
-        assert_eq!(is_sign_change(-1.0, 1.0), true);
+        assert!(!is_sign_change(-1.0, -1.0));
+        assert!(!is_sign_change(1.0, 1.0));
+        assert!(is_sign_change(-1.0, 1.0));
 
         // zero tests
-        assert_eq!(is_sign_change(0.0, 0.0), false);
-        assert_eq!(is_sign_change(0.0, 1.0), false);
-        assert_eq!(is_sign_change(0.0, -1.0), true);
+        assert!(!is_sign_change(0.0, 0.0));
+        assert!(!is_sign_change(0.0, 1.0));
+        assert!(is_sign_change(0.0, -1.0));
 
         // naughty signed zeroes
-        assert_eq!(is_sign_change(-0.0, -1.0), false);
-        assert_eq!(is_sign_change(-0.0, 0.0), true);
+        assert!(!is_sign_change(-0.0, -1.0));
+        assert!(is_sign_change(-0.0, 0.0));
     }
 
     #[test]
     fn test_is_sign_change_underflow() {
         // floating point underflow breaks naive a*b<0 check
-        assert_eq!(
+        assert!(
             is_sign_change(1e-120, -2e-300),
-            true,
             "sign change with float underflow"
         );
     }
 
+    #[test]
+    fn test_is_sign_change_eps() {
+        // both effectively zero
+        assert!(is_sign_change(1e-300, -1e-300));
+        assert!(!is_sign_change_eps(1e-300, -1e-300, 1e-200));
+        assert!(!is_sign_change_eps(1.0, -1e-300, 1e-200));
+
+        // genuine sign changes still count
+        assert!(is_sign_change_eps(1e-100, -1e-100, 1e-200));
+        assert!(is_sign_change_eps(-1.0, 1.0, 1e-12));
+        assert!(!is_sign_change_eps(1.0, 1.0, 1e-12));
+
+        // exact zeros never change sign
+        assert!(!is_sign_change_eps(0.0, -1.0, 0.0));
+        assert!(!is_sign_change_eps(-0.0, 0.0, 0.0));
+    }
+
     #[test]
     fn test_is_sign_change_overflow() {
         // this passes the naive a*b<0 check because its -inf
         let a = f64::MAX / 2.;
         let b = f64::MIN / 2.;
-        assert_eq!(is_sign_change(a, b), true);
+        assert!(is_sign_change(a, b));
     }
 
     #[test]
@@ -440,6 +1541,52 @@ mod tests {
         assert!(win.is_none());
     }
 
+    #[test]
+    fn test_expand_bracket_sin() {
+        let fin = |x: f64| x.sin();
+        let f = RealFn::new(&fin);
+
+        // probes 1 ± 0.1, 0.2, 0.4, 0.8, then -0.6 crosses zero
+        let win = expand_bracket(&f, 1.0, 0.1, 2.0, 20).expect("window found");
+        assert!((win.a - -0.6).abs() < 1e-12);
+        assert!((win.b - 0.2).abs() < 1e-12);
+        assert!(win.contains(0.0));
+
+        // starting on the far side of pi expands right instead
+        let win = expand_bracket(&f, 2.0, 0.5, 2.0, 20).expect("window found");
+        assert!(win.contains(f64::consts::PI));
+    }
+
+    #[test]
+    fn test_expand_bracket_monotone() {
+        // root at ln(1000), far to the right of the guess
+        let fin = |x: f64| x.exp() - 1000.0;
+        let f = RealFn::new(&fin);
+
+        let win = expand_bracket(&f, 0.0, 0.01, 1.6, 50).expect("window found");
+        assert!(win.contains(1000f64.ln()));
+        assert!(is_sign_change(fin(win.a), fin(win.b)));
+    }
+
+    #[test]
+    fn test_expand_bracket_miss() {
+        // no sign change anywhere
+        let fin = |x: f64| x * x + 1.0;
+        let f = RealFn::new(&fin);
+        assert!(expand_bracket(&f, 3.0, 0.1, 2.0, 30).is_none());
+
+        // probes overflow before anything is found
+        assert!(expand_bracket(&f, 3.0, 1e300, 1e10, 30).is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_expand_bracket_factor_one() {
+        let fin = |x: f64| x;
+        let f = RealFn::new(&fin);
+        let _ = expand_bracket(&f, 3.0, 0.1, 1.0, 30);
+    }
+
     #[test]
     fn test_first_bracket_even_degree() {
         // root at zero is of even degree (touches but does not cross x-axis).
@@ -450,4 +1597,45 @@ mod tests {
         let win = first_bracket(&f, &Bounds::new(-4.5, 4.5), 1.0);
         assert!(win.is_none());
     }
+
+    #[test]
+    fn test_first_bracket_with_derivative_even_degree() {
+        let in_f = |x: f64| x * x;
+        let in_df = |x: f64| 2.0 * x;
+        let f = RealFnAndFirst::new(&in_f, &in_df);
+
+        let win = first_bracket_with_derivative(&f, &f, &Bounds::new(-4.5, 4.5), 1.0);
+        assert_eq!(win, Some(Bounds::new(-0.5, 0.5)));
+
+        // touching root landing on a window boundary
+        let win = first_bracket_with_derivative(&f, &f, &Bounds::new(-4.0, 4.0), 1.0);
+        assert_eq!(win, Some(Bounds::new(-1.0, 0.0)));
+
+        // quartic with the window off-center
+        let in_f = |x: f64| (x - 0.2).powi(4);
+        let in_df = |x: f64| 4.0 * (x - 0.2).powi(3);
+        let f = RealFnAndFirst::new(&in_f, &in_df);
+        let win = first_bracket_with_derivative(&f, &f, &Bounds::new(-4.5, 4.5), 1.0);
+        assert_eq!(win, Some(Bounds::new(-0.5, 0.5)));
+    }
+
+    #[test]
+    fn test_first_bracket_with_derivative_crossing() {
+        // crossings are still reported
+        let in_f = |x: f64| x + 9.0;
+        let in_df = |_: f64| 1.0;
+        let f = RealFnAndFirst::new(&in_f, &in_df);
+        let win = first_bracket_with_derivative(&f, &f, &Bounds::new(-100.0, 100.0), 10.0);
+        assert_eq!(win, Some(Bounds::new(-10.0, 0.0)));
+    }
+
+    #[test]
+    fn test_first_bracket_with_derivative_miss() {
+        // minimum well above the axis
+        let in_f = |x: f64| x * x + 0.1;
+        let in_df = |x: f64| 2.0 * x;
+        let f = RealFnAndFirst::new(&in_f, &in_df);
+        let win = first_bracket_with_derivative(&f, &f, &Bounds::new(-4.5, 4.5), 1.0);
+        assert!(win.is_none());
+    }
 }
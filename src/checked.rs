@@ -0,0 +1,1561 @@
+//! Non-panicking versions of the public entry points.
+//!
+//! The rest of the crate uses assertions to reject invalid input, such as
+//! flipped bounds, a NaN at a bracket endpoint, or a non-finite starting
+//! guess.  That is convenient for scripts but unacceptable for long-running
+//! services.  (Brackets without a sign change are already reported as
+//! `RootError::NoSignChange` everywhere.)
+//!
+//! Each function here performs the same checks as its panicking counterpart up
+//! front and reports violations as a `RootError` (or `BoundsError`).  Function
+//! values which turn out to be NaN mid-solve are also reported as errors rather
+//! than tripping an assertion.  Coverage spans:
+//!
+//! * bounds, criteria and function wrapper construction
+//! * the solvers in `solver`, along with `Polynomial` and the system solvers
+//! * `is_sign_change`, `is_sign_change_eps`, `first_bracket`, `expand_bracket`
+//!   and the fixed window `BracketGenerator` sweep, stepped with `try_next`
+//!
+//! Not repeated here are convenience variants of a solver, e.g. those differing
+//! only in how results are reported or endpoints supplied (`_verbose`,
+//! `_trace`, `_deadline`, `_with_endpoints`, `_crossing`), `custom_iterative`
+//! and the basin maps, nor diagnostics like `estimate_multiplicity` and
+//! `armijo_backtrack`.  Neither
+//! are the sampling helpers in `bracket`, such as `sign_changes` and
+//! `isolate_roots`, which still assert that f is not NaN.  The system solvers
+//! still assert that f and the Jacobian return results matching the dimension
+//! of the start.
+//!
+//! Bracketing wrappers evaluate f at both endpoints to validate the bracket.
+//! Unless the solver can take those values directly, this costs two extra
+//! evaluations.
+//!
+//! # Examples
+//! ```
+//! use rootfind::checked;
+//! use rootfind::convergence::DeltaX;
+//! use rootfind::solver::RootError;
+//! use rootfind::wrap::RealFn;
+//!
+//! let in_f = |x: f64| x * x;
+//! let f = RealFn::new(&in_f);
+//!
+//! // bracket has no sign change
+//! let bounds = checked::bounds(-10.0, -5.0).expect("valid bounds");
+//! match checked::bisection(&f, &bounds, &DeltaX::new(1e-9), 100) {
+//!     Err(RootError::NoSignChange { .. }) => {}
+//!     _ => panic!("expected error"),
+//! }
+//! ```
+use alloc::vec::Vec;
+use bracket::{self, Bounds, BoundsError, BracketGenerator};
+use convergence::{
+    AdaptiveResidual, BracketAndResidual, DeltaX, FnResidual, IsConverged, NewtonStep,
+    RecordingDeltaX, SignificantDigits, Stagnation, TargetCrossed,
+};
+use num_complex::Complex;
+use solver::{self, system, system2, BracketMethod, Method, RootError};
+#[cfg(feature = "std")]
+use wrap::CachingFn;
+use wrap::{
+    ComplexDfEval, ComplexFnEval, Polynomial, RealD2fEval, RealD3fEval, RealDfEval,
+    RealFnAndFirstNumericalSecond, RealFnEval, RealFnNumericalDiff,
+};
+
+/// Create closed interval [a, b].
+///
+/// Same as `Bounds::try_new`.
+pub fn bounds(a: f64, b: f64) -> Result<Bounds, BoundsError> {
+    Bounds::try_new(a, b)
+}
+
+/// Check if signs differ, rejecting NaN.
+pub fn is_sign_change(lhs: f64, rhs: f64) -> Result<bool, RootError> {
+    check_not_nan("lhs", lhs)?;
+    check_not_nan("rhs", rhs)?;
+    Ok(bracket::is_sign_change(lhs, rhs))
+}
+
+/// Scan interval for the first bracket containing a sign change.
+pub fn first_bracket<F>(
+    f: &F,
+    bounds: &Bounds,
+    window_size: f64,
+) -> Result<Option<Bounds>, RootError>
+where
+    F: RealFnEval,
+{
+    check_positive("window_size", window_size)?;
+    bracket::try_first_bracket(f, bounds, window_size, None)
+}
+
+/// Check if signs differ with a zero tolerance, rejecting NaN.
+pub fn is_sign_change_eps(lhs: f64, rhs: f64, zero_eps: f64) -> Result<bool, RootError> {
+    check_not_nan("lhs", lhs)?;
+    check_not_nan("rhs", rhs)?;
+    check_non_negative("zero_eps", zero_eps)?;
+    Ok(bracket::is_sign_change_eps(lhs, rhs, zero_eps))
+}
+
+/// Fixed window bracket sweep.
+///
+/// Step through the brackets with `try_next`, which reports NaN function
+/// values as errors.  The plain `Iterator` interface still panics on them.
+pub fn bracket_generator<'a, F>(
+    f: &'a F,
+    bounds: Bounds,
+    window_size: f64,
+) -> Result<BracketGenerator<'a, F>, RootError>
+where
+    F: RealFnEval,
+{
+    check_positive("window_size", window_size)?;
+    Ok(BracketGenerator::new(f, bounds, window_size))
+}
+
+/// Search outward from a single guess until a sign change is straddled.
+pub fn expand_bracket<F>(
+    f: &F,
+    x0: f64,
+    initial_step: f64,
+    factor: f64,
+    max_iter: usize,
+) -> Result<Option<Bounds>, RootError>
+where
+    F: RealFnEval,
+{
+    check_finite("x0", x0)?;
+    check_positive("initial_step", initial_step)?;
+    if !factor.is_finite() || factor <= 1.0 {
+        return Err(RootError::InvalidParameter {
+            name: "factor",
+            value: factor,
+        });
+    }
+    Ok(bracket::expand_bracket(
+        f,
+        x0,
+        initial_step,
+        factor,
+        max_iter,
+    ))
+}
+
+/// Parallel fixed window bracket sweep.
+#[cfg(feature = "rayon")]
+pub fn find_brackets_parallel<F>(
+    f: &F,
+    bounds: &Bounds,
+    window_size: f64,
+) -> Result<Vec<Bounds>, RootError>
+where
+    F: Fn(f64) -> f64 + Sync,
+{
+    check_positive("window_size", window_size)?;
+    bracket::find_brackets_parallel(f, bounds, window_size)
+}
+
+/// DeltaX convergence criteria.
+pub fn delta_x(epsilon_abs: f64) -> Result<DeltaX, RootError> {
+    check_positive("epsilon_abs", epsilon_abs)?;
+    Ok(DeltaX::new(epsilon_abs))
+}
+
+/// RecordingDeltaX convergence criteria.
+pub fn recording_delta_x(epsilon_abs: f64) -> Result<RecordingDeltaX, RootError> {
+    check_positive("epsilon_abs", epsilon_abs)?;
+    Ok(RecordingDeltaX::new(epsilon_abs))
+}
+
+/// FnResidual convergence criteria.
+pub fn fn_residual(epsilon_abs: f64) -> Result<FnResidual, RootError> {
+    check_non_negative("epsilon_abs", epsilon_abs)?;
+    Ok(FnResidual::new(epsilon_abs))
+}
+
+/// AdaptiveResidual convergence criteria.
+pub fn adaptive_residual(epsilon_rel: f64, warmup: usize) -> Result<AdaptiveResidual, RootError> {
+    check_positive("epsilon_rel", epsilon_rel)?;
+    if warmup == 0 {
+        return Err(RootError::InvalidParameter {
+            name: "warmup",
+            value: 0.0,
+        });
+    }
+    Ok(AdaptiveResidual::new(epsilon_rel, warmup))
+}
+
+/// SignificantDigits convergence criteria.
+pub fn significant_digits(digits: u32) -> Result<SignificantDigits, RootError> {
+    if !(1..=15).contains(&digits) {
+        return Err(RootError::InvalidParameter {
+            name: "digits",
+            value: f64::from(digits),
+        });
+    }
+    Ok(SignificantDigits::new(digits))
+}
+
+/// NewtonStep convergence criteria.
+pub fn newton_step<'a, F>(f: &'a F, epsilon_abs: f64) -> Result<NewtonStep<'a, F>, RootError>
+where
+    F: RealDfEval,
+{
+    check_positive("epsilon_abs", epsilon_abs)?;
+    Ok(NewtonStep::new(f, epsilon_abs))
+}
+
+/// BracketAndResidual convergence criteria.
+pub fn bracket_and_residual(
+    width_eps: f64,
+    residual_eps: f64,
+) -> Result<BracketAndResidual, RootError> {
+    check_positive("width_eps", width_eps)?;
+    check_non_negative("residual_eps", residual_eps)?;
+    Ok(BracketAndResidual::new(width_eps, residual_eps))
+}
+
+/// TargetCrossed convergence criteria.
+pub fn target_crossed(target: f64) -> Result<TargetCrossed, RootError> {
+    check_finite("target", target)?;
+    Ok(TargetCrossed::new(target))
+}
+
+/// Stagnation convergence criteria.
+pub fn stagnation(window: usize, epsilon: f64) -> Result<Stagnation, RootError> {
+    if window < 2 {
+        return Err(RootError::InvalidParameter {
+            name: "window",
+            value: window as f64,
+        });
+    }
+    check_positive("epsilon", epsilon)?;
+    Ok(Stagnation::new(window, epsilon))
+}
+
+/// Central difference derivatives with step `h`.
+pub fn numerical_diff<'a, F>(f: &'a F, h: f64) -> Result<RealFnNumericalDiff<'a, F>, RootError>
+where
+    F: Fn(f64) -> f64,
+{
+    check_positive("h", h)?;
+    Ok(RealFnNumericalDiff::new(f).with_step(h))
+}
+
+/// Analytic first derivative with a numerical second derivative of step `h`.
+pub fn numerical_second<'a, F1, F2>(
+    f: &'a F1,
+    df: &'a F2,
+    h: f64,
+) -> Result<RealFnAndFirstNumericalSecond<'a, F1, F2>, RootError>
+where
+    F1: Fn(f64) -> f64,
+    F2: Fn(f64) -> f64,
+{
+    check_positive("h", h)?;
+    Ok(RealFnAndFirstNumericalSecond::new(f, df).with_step(h))
+}
+
+/// Memoizing wrapper holding at most `capacity` evaluations.
+#[cfg(feature = "std")]
+pub fn caching_fn<'a, F>(f: &'a F, capacity: usize) -> Result<CachingFn<'a, F>, RootError>
+where
+    F: RealFnEval,
+{
+    if capacity == 0 {
+        return Err(RootError::InvalidParameter {
+            name: "capacity",
+            value: 0.0,
+        });
+    }
+    Ok(CachingFn::with_capacity(f, capacity))
+}
+
+/// Find all roots of f in [a, b] to within `tol`.
+pub fn find_roots<F>(f: F, a: f64, b: f64, tol: f64) -> Result<Vec<f64>, RootError>
+where
+    F: Fn(f64) -> f64,
+{
+    check_finite("a", a)?;
+    check_finite("b", b)?;
+    if a > b {
+        return Err(RootError::InvalidParameter {
+            name: "b",
+            value: b,
+        });
+    }
+    check_positive("tol", tol)?;
+    solver::try_find_roots(&f, &Bounds::new(a, b), tol)
+}
+
+/// Find all roots of f within `bounds` using the chosen bracketing method.
+pub fn find_all_roots<F>(
+    f: &F,
+    bounds: &Bounds,
+    window_size: f64,
+    method: BracketMethod,
+    max_iter: usize,
+) -> Result<Vec<f64>, RootError>
+where
+    F: RealFnEval,
+{
+    check_positive("window_size", window_size)?;
+    solver::try_find_all_roots(f, bounds, window_size, method, max_iter)
+}
+
+/// Root finding using Newton-Raphson.
+pub fn newton_raphson<F, C>(
+    f: &F,
+    start: f64,
+    finish: &C,
+    max_iter: usize,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval + RealDfEval,
+    C: IsConverged,
+{
+    check_finite("start", start)?;
+    solver::newton_raphson(f, start, finish, max_iter)
+}
+
+/// Newton-Raphson with perturbed restarts.
+pub fn newton_raphson_robust<F, C>(
+    f: &F,
+    start: f64,
+    finish: &C,
+    max_iter: usize,
+    max_restarts: usize,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval + RealDfEval,
+    C: IsConverged,
+{
+    check_finite("start", start)?;
+    solver::newton_raphson_robust(f, start, finish, max_iter, max_restarts)
+}
+
+/// Newton-Raphson with each step limited to at most `max_step` in magnitude.
+pub fn newton_raphson_clamped<F, C>(
+    f: &F,
+    start: f64,
+    max_step: f64,
+    finish: &C,
+    max_iter: usize,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval + RealDfEval,
+    C: IsConverged,
+{
+    check_finite("start", start)?;
+    check_positive("max_step", max_step)?;
+    solver::newton_raphson_clamped(f, start, max_step, finish, max_iter)
+}
+
+/// Newton-Raphson globalized by a backtracking line search.
+pub fn newton_raphson_damped<F, C>(
+    f: &F,
+    start: f64,
+    finish: &C,
+    max_iter: usize,
+    max_backtracks: usize,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval + RealDfEval,
+    C: IsConverged,
+{
+    check_finite("start", start)?;
+    solver::newton_raphson_damped(f, start, finish, max_iter, max_backtracks)
+}
+
+/// Bracket-guarded Newton-Raphson.
+pub fn safe_newton<F, C>(
+    f: &F,
+    bounds: &Bounds,
+    finish: &C,
+    max_iter: usize,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval + RealDfEval,
+    C: IsConverged,
+{
+    safe_newton_with_bisections(f, bounds, 1, finish, max_iter)
+}
+
+/// Bracket-guarded Newton-Raphson with bisections up front.
+pub fn safe_newton_with_bisections<F, C>(
+    f: &F,
+    bounds: &Bounds,
+    initial_bisections: usize,
+    finish: &C,
+    max_iter: usize,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval + RealDfEval,
+    C: IsConverged,
+{
+    check_bracket(f, bounds)?;
+    solver::safe_newton_with_bisections(f, bounds, initial_bisections, finish, max_iter)
+}
+
+/// Bracket-guarded Newton-Raphson stopping once the root is known to within
+/// `x_tol` in x.
+pub fn solve_to_x_tol<F>(
+    f: &F,
+    bounds: &Bounds,
+    x_tol: f64,
+    max_iter: usize,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval + RealDfEval,
+{
+    check_positive("x_tol", x_tol)?;
+    check_bracket(f, bounds)?;
+    solver::solve_to_x_tol(f, bounds, x_tol, max_iter)
+}
+
+/// Race Newton-Raphson against bisection.
+pub fn race<F, C>(
+    f: &F,
+    bounds: &Bounds,
+    guess: f64,
+    finish: &C,
+    max_iter: usize,
+) -> Result<(f64, Method), RootError>
+where
+    F: RealFnEval + RealDfEval,
+    C: IsConverged,
+{
+    check_finite("guess", guess)?;
+    check_bracket(f, bounds)?;
+    solver::race(f, bounds, guess, finish, max_iter)
+}
+
+/// Newton-Raphson with a residual check on the result.
+pub fn solve_verified<F>(
+    f: &F,
+    start: f64,
+    x_tol: f64,
+    max_residual: f64,
+    max_iter: usize,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval + RealDfEval,
+{
+    check_finite("start", start)?;
+    check_positive("x_tol", x_tol)?;
+    check_non_negative("max_residual", max_residual)?;
+    solver::solve_verified(f, start, x_tol, max_residual, max_iter)
+}
+
+/// Root finding using Halley's method.
+pub fn halley_method<F, C>(f: &F, start: f64, finish: &C, max_iter: usize) -> Result<f64, RootError>
+where
+    F: RealFnEval + RealDfEval + RealD2fEval,
+    C: IsConverged,
+{
+    check_finite("start", start)?;
+    solver::halley_method(f, start, finish, max_iter)
+}
+
+/// Halley's method with a residual-reducing safeguard.
+pub fn halley_method_adaptive<F, C>(
+    f: &F,
+    start: f64,
+    finish: &C,
+    max_iter: usize,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval + RealDfEval + RealD2fEval,
+    C: IsConverged,
+{
+    check_finite("start", start)?;
+    solver::halley_method_adaptive(f, start, finish, max_iter)
+}
+
+/// Root finding using Householder's third order method.
+pub fn householder3<F, C>(f: &F, start: f64, finish: &C, max_iter: usize) -> Result<f64, RootError>
+where
+    F: RealFnEval + RealDfEval + RealD2fEval + RealD3fEval,
+    C: IsConverged,
+{
+    check_finite("start", start)?;
+    solver::householder3(f, start, finish, max_iter)
+}
+
+/// Root finding using Schröder's method for multiple roots.
+pub fn schroder<F, C>(f: &F, start: f64, finish: &C, max_iter: usize) -> Result<f64, RootError>
+where
+    F: RealFnEval + RealDfEval + RealD2fEval,
+    C: IsConverged,
+{
+    check_finite("start", start)?;
+    solver::schroder(f, start, finish, max_iter)
+}
+
+/// Polynomial root finding using Laguerre's method.
+pub fn laguerre(
+    poly: &Polynomial,
+    start: f64,
+    tolerance: f64,
+    max_iter: usize,
+) -> Result<f64, RootError> {
+    if poly.degree() == 0 {
+        return Err(RootError::InvalidParameter {
+            name: "degree",
+            value: 0.0,
+        });
+    }
+    check_finite("start", start)?;
+    check_positive("tolerance", tolerance)?;
+    solver::laguerre(poly, start, tolerance, max_iter)
+}
+
+/// Root finding using Muller's method.
+pub fn muller<F>(
+    f: &F,
+    x0: f64,
+    x1: f64,
+    x2: f64,
+    tolerance: f64,
+    max_iter: usize,
+) -> Result<Complex<f64>, RootError>
+where
+    F: ComplexFnEval,
+{
+    check_finite("x0", x0)?;
+    check_finite("x1", x1)?;
+    check_finite("x2", x2)?;
+    if x1 == x0 {
+        return Err(RootError::InvalidParameter {
+            name: "x1",
+            value: x1,
+        });
+    }
+    if x2 == x0 || x2 == x1 {
+        return Err(RootError::InvalidParameter {
+            name: "x2",
+            value: x2,
+        });
+    }
+    check_positive("tolerance", tolerance)?;
+    solver::muller(f, x0, x1, x2, tolerance, max_iter)
+}
+
+/// Newton-Raphson in the complex plane.
+pub fn complex_newton<F, C>(
+    f: &F,
+    start: Complex<f64>,
+    finish: &C,
+    max_iter: usize,
+) -> Result<Complex<f64>, RootError>
+where
+    F: ComplexFnEval + ComplexDfEval,
+    C: IsConverged,
+{
+    check_finite("start", start.re)?;
+    check_finite("start", start.im)?;
+    solver::complex_newton(f, start, finish, max_iter)
+}
+
+/// Fixed-point iteration accelerated with Wegstein's method.
+pub fn wegstein<F, C>(g: &F, x0: f64, finish: &C, max_iter: usize) -> Result<f64, RootError>
+where
+    F: RealFnEval,
+    C: IsConverged,
+{
+    check_finite("x0", x0)?;
+    solver::wegstein(g, x0, finish, max_iter)
+}
+
+/// Fixed-point iteration x = g(x).
+pub fn fixed_point<G, C>(g: &G, start: f64, finish: &C, max_iter: usize) -> Result<f64, RootError>
+where
+    G: Fn(f64) -> f64,
+    C: IsConverged,
+{
+    check_finite("start", start)?;
+    solver::fixed_point(g, start, finish, max_iter)
+}
+
+/// Fixed-point iteration with Aitken's delta-squared acceleration.
+pub fn aitken_accelerate<G, C>(
+    g: &G,
+    start: f64,
+    finish: &C,
+    max_iter: usize,
+) -> Result<f64, RootError>
+where
+    G: Fn(f64) -> f64,
+    C: IsConverged,
+{
+    check_finite("start", start)?;
+    solver::aitken_accelerate(g, start, finish, max_iter)
+}
+
+/// Root finding using inverse quadratic interpolation.
+pub fn inverse_quadratic<F, C>(
+    f: &F,
+    x0: f64,
+    x1: f64,
+    x2: f64,
+    finish: &C,
+    max_iter: usize,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval,
+    C: IsConverged,
+{
+    check_finite("x0", x0)?;
+    check_finite("x1", x1)?;
+    check_finite("x2", x2)?;
+    solver::inverse_quadratic(f, x0, x1, x2, finish, max_iter)
+}
+
+/// Root finding via Bisection Method.
+pub fn bisection<F, C>(
+    f: &F,
+    bounds: &Bounds,
+    finish: &C,
+    max_iter: usize,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval,
+    C: IsConverged,
+{
+    let (f_a, f_b) = check_bracket(f, bounds)?;
+    solver::bisection_from(f, bounds, f_a, f_b, finish, max_iter, None)
+}
+
+/// Bisection returning the final bracket rather than a point estimate.
+pub fn bisection_bracket<F, C>(
+    f: &F,
+    bounds: &Bounds,
+    finish: &C,
+    max_iter: usize,
+) -> Result<Bounds, RootError>
+where
+    F: RealFnEval,
+    C: IsConverged,
+{
+    check_bracket(f, bounds)?;
+    solver::bisection_bracket(f, bounds, finish, max_iter)
+}
+
+/// Number of bisection iterations needed to shrink a bracket to width `eps`.
+pub fn bisection_iterations_for(bounds: &Bounds, eps: f64) -> Result<usize, RootError> {
+    check_positive("eps", eps)?;
+    Ok(solver::bisection_iterations_for(bounds, eps))
+}
+
+/// Bisect until the bracket is no wider than `target_width`.
+pub fn tighten_bracket<F>(
+    f: &F,
+    bounds: &Bounds,
+    target_width: f64,
+    max_iter: usize,
+) -> Result<Bounds, RootError>
+where
+    F: RealFnEval,
+{
+    check_non_negative("target_width", target_width)?;
+    check_bracket(f, bounds)?;
+    solver::tighten_bracket(f, bounds, target_width, max_iter)
+}
+
+/// Bisection treating |f| below `zero_eps` as an exact root.
+pub fn bisection_eps<F>(
+    f: &F,
+    bounds: &Bounds,
+    zero_eps: f64,
+    max_iter: usize,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval,
+{
+    check_non_negative("zero_eps", zero_eps)?;
+
+    // endpoints within zero_eps are roots, so need no sign change
+    check_endpoints(f, bounds)?;
+    solver::bisection_eps(f, bounds, zero_eps, max_iter)
+}
+
+/// Bisection splitting at the geometric midpoint.
+pub fn bisection_log<F>(f: &F, bounds: &Bounds, max_iter: usize) -> Result<f64, RootError>
+where
+    F: RealFnEval,
+{
+    check_bracket(f, bounds)?;
+    solver::bisection_log(f, bounds, max_iter)
+}
+
+/// Bisection splitting in the middle of the representable floats.
+pub fn bisection_ulps<F>(
+    f: &F,
+    bounds: &Bounds,
+    max_ulps: u64,
+    max_iter: usize,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval,
+{
+    if max_ulps == 0 {
+        return Err(RootError::InvalidParameter {
+            name: "max_ulps",
+            value: 0.0,
+        });
+    }
+    check_bracket(f, bounds)?;
+    solver::bisection_ulps(f, bounds, max_ulps, max_iter)
+}
+
+/// Steffensen's method safeguarded by a bracket.
+pub fn steffensen_bracketed<F, C>(
+    f: &F,
+    bounds: &Bounds,
+    finish: &C,
+    max_iter: usize,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval,
+    C: IsConverged,
+{
+    check_bracket(f, bounds)?;
+    solver::steffensen_bracketed(f, bounds, finish, max_iter)
+}
+
+/// Illinois variant of Regula Falsi.
+pub fn false_position_illinios<F>(f: &F, bounds: &Bounds, max_iter: usize) -> Result<f64, RootError>
+where
+    F: RealFnEval,
+{
+    false_position_illinios_eps(f, bounds, 1e-12, max_iter)
+}
+
+/// Illinois variant of Regula Falsi with explicit flat-interpolant threshold.
+pub fn false_position_illinios_eps<F>(
+    f: &F,
+    bounds: &Bounds,
+    flat_eps: f64,
+    max_iter: usize,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval,
+{
+    check_non_negative("flat_eps", flat_eps)?;
+    let (f_a, f_b) = check_finite_bracket(f, bounds)?;
+    solver::false_position_illinios_from(f, bounds, f_a, f_b, 0.0, flat_eps, 1.0, max_iter, None)
+}
+
+/// Illinois variant of Regula Falsi with a relative flat-interpolant threshold.
+pub fn false_position_illinios_relative_eps<F>(
+    f: &F,
+    bounds: &Bounds,
+    flat_eps: f64,
+    max_iter: usize,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval,
+{
+    check_non_negative("flat_eps", flat_eps)?;
+    let (f_a, f_b) = check_finite_bracket(f, bounds)?;
+    let scale = f_a.abs().max(f_b.abs());
+    solver::false_position_illinios_from(f, bounds, f_a, f_b, 0.0, flat_eps, scale, max_iter, None)
+}
+
+/// Pegasus variant of Regula Falsi.
+pub fn false_position_pegasus<F>(f: &F, bounds: &Bounds, max_iter: usize) -> Result<f64, RootError>
+where
+    F: RealFnEval,
+{
+    check_finite_bracket(f, bounds)?;
+    solver::false_position_pegasus(f, bounds, max_iter)
+}
+
+/// Illinois false position seeded with an initial endpoint bias.
+pub fn false_position_biased<F>(
+    f: &F,
+    bounds: &Bounds,
+    initial_bias: f64,
+    max_iter: usize,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval,
+{
+    check_finite("initial_bias", initial_bias)?;
+    let (f_a, f_b) = check_finite_bracket(f, bounds)?;
+    solver::false_position_illinios_from(
+        f,
+        bounds,
+        f_a,
+        f_b,
+        initial_bias,
+        1e-12,
+        1.0,
+        max_iter,
+        None,
+    )
+}
+
+/// Root finding via Brent-Dekker method.
+pub fn brent_dekker<F>(f: &F, bounds: &Bounds, max_iter: usize) -> Result<f64, RootError>
+where
+    F: RealFnEval,
+{
+    check_bracket(f, bounds)?;
+    solver::brent_dekker(f, bounds, max_iter)
+}
+
+/// Root finding via Ridders' method.
+pub fn ridders<F>(f: &F, bounds: &Bounds, max_iter: usize) -> Result<f64, RootError>
+where
+    F: RealFnEval,
+{
+    check_bracket(f, bounds)?;
+    solver::ridders(f, bounds, max_iter)
+}
+
+/// Root finding via the ITP method.
+pub fn itp<F>(
+    f: &F,
+    bounds: &Bounds,
+    k1: f64,
+    k2: f64,
+    n0: usize,
+    max_iter: usize,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval,
+{
+    check_positive("k1", k1)?;
+    if !(1.0..2.618).contains(&k2) {
+        return Err(RootError::InvalidParameter {
+            name: "k2",
+            value: k2,
+        });
+    }
+    check_bracket(f, bounds)?;
+    solver::itp(f, bounds, k1, k2, n0, max_iter)
+}
+
+/// Polynomial with coefficients in increasing order of power.
+pub fn polynomial(coeffs: Vec<f64>) -> Result<Polynomial, RootError> {
+    match coeffs.iter().find(|c| !c.is_finite()) {
+        Some(&value) => Err(RootError::InvalidParameter {
+            name: "coeffs",
+            value,
+        }),
+        // an empty list is reported as a length of zero
+        None if coeffs.is_empty() => Err(RootError::InvalidParameter {
+            name: "coeffs",
+            value: 0.0,
+        }),
+        None => Ok(Polynomial::new(coeffs)),
+    }
+}
+
+/// Quadratic factor x^2 + u*x + v of a polynomial via Bairstow's method.
+pub fn bairstow(
+    poly: &Polynomial,
+    u0: f64,
+    v0: f64,
+    max_iter: usize,
+    tol: f64,
+) -> Result<(f64, f64), RootError> {
+    if poly.degree() < 2 {
+        return Err(RootError::InvalidParameter {
+            name: "degree",
+            value: poly.degree() as f64,
+        });
+    }
+    check_finite("u0", u0)?;
+    check_finite("v0", v0)?;
+    check_positive("tol", tol)?;
+    poly.bairstow(u0, v0, max_iter, tol)
+}
+
+/// Factor a polynomial into real monic linear and quadratic polynomials.
+pub fn bairstow_factors(
+    poly: &Polynomial,
+    max_iter: usize,
+    tol: f64,
+) -> Result<Vec<Polynomial>, RootError> {
+    check_positive("tol", tol)?;
+    poly.bairstow_factors(max_iter, tol)
+}
+
+/// All roots of a polynomial via the Aberth-Ehrlich method.
+pub fn aberth(
+    poly: &Polynomial,
+    max_iter: usize,
+    tol: f64,
+) -> Result<Vec<Complex<f64>>, RootError> {
+    check_positive("tol", tol)?;
+    poly.aberth(max_iter, tol)
+}
+
+/// Newton's method for a system of two equations.
+pub fn newton2<F, J>(
+    f: F,
+    jac: J,
+    start: [f64; 2],
+    tol: f64,
+    max_iter: usize,
+) -> Result<[f64; 2], RootError>
+where
+    F: Fn([f64; 2]) -> [f64; 2],
+    J: Fn([f64; 2]) -> [[f64; 2]; 2],
+{
+    check_finite("start", start[0])?;
+    check_finite("start", start[1])?;
+    check_positive("tol", tol)?;
+    system2::newton2(f, jac, start, tol, max_iter)
+}
+
+/// Newton's method for a system of N equations.
+pub fn newton_nd<F, J>(
+    f: F,
+    jac: J,
+    start: &[f64],
+    tol: f64,
+    max_iter: usize,
+) -> Result<Vec<f64>, RootError>
+where
+    F: Fn(&[f64]) -> Vec<f64>,
+    J: Fn(&[f64]) -> Vec<Vec<f64>>,
+{
+    for &x in start {
+        check_finite("start", x)?;
+    }
+    check_positive("tol", tol)?;
+    system::newton_nd(f, jac, start, tol, max_iter)
+}
+
+/// Evaluate bracket endpoints, ensuring they are not NaN.
+fn check_endpoints<F>(f: &F, bounds: &Bounds) -> Result<(f64, f64), RootError>
+where
+    F: RealFnEval,
+{
+    solver::check_degenerate(bounds)?;
+    let f_a = f.eval_f(bounds.a);
+    if f_a.is_nan() {
+        return Err(RootError::NonFiniteEvaluation { x: bounds.a });
+    }
+    let f_b = f.eval_f(bounds.b);
+    if f_b.is_nan() {
+        return Err(RootError::NonFiniteEvaluation { x: bounds.b });
+    }
+    Ok((f_a, f_b))
+}
+
+/// Evaluate bracket endpoints, ensuring they are not NaN and change sign.
+fn check_bracket<F>(f: &F, bounds: &Bounds) -> Result<(f64, f64), RootError>
+where
+    F: RealFnEval,
+{
+    let (f_a, f_b) = check_endpoints(f, bounds)?;
+    if !bracket::is_sign_change(f_a, f_b) {
+        return Err(RootError::NoSignChange {
+            a: bounds.a,
+            b: bounds.b,
+        });
+    }
+    Ok((f_a, f_b))
+}
+
+/// Like `check_bracket`, also rejecting infinite endpoint values.
+fn check_finite_bracket<F>(f: &F, bounds: &Bounds) -> Result<(f64, f64), RootError>
+where
+    F: RealFnEval,
+{
+    let (f_a, f_b) = check_bracket(f, bounds)?;
+    if f_a.is_infinite() {
+        return Err(RootError::NonFiniteEvaluation { x: bounds.a });
+    }
+    if f_b.is_infinite() {
+        return Err(RootError::NonFiniteEvaluation { x: bounds.b });
+    }
+    Ok((f_a, f_b))
+}
+
+fn check_not_nan(name: &'static str, value: f64) -> Result<(), RootError> {
+    if value.is_nan() {
+        return Err(RootError::InvalidParameter { name, value });
+    }
+    Ok(())
+}
+
+fn check_finite(name: &'static str, value: f64) -> Result<(), RootError> {
+    if !value.is_finite() {
+        return Err(RootError::InvalidParameter { name, value });
+    }
+    Ok(())
+}
+
+fn check_positive(name: &'static str, value: f64) -> Result<(), RootError> {
+    if !value.is_finite() || value <= 0.0 {
+        return Err(RootError::InvalidParameter { name, value });
+    }
+    Ok(())
+}
+
+fn check_non_negative(name: &'static str, value: f64) -> Result<(), RootError> {
+    if !value.is_finite() || value < 0.0 {
+        return Err(RootError::InvalidParameter { name, value });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64;
+    use wrap::{
+        ComplexFn, ComplexFnAndFirst, RealFn, RealFnAndFirst, RealFnAndFirstSecond,
+        RealFnThroughThird,
+    };
+
+    fn assert_invalid_parameter<T>(result: Result<T, RootError>, expected: &str) {
+        match result {
+            Err(RootError::InvalidParameter { name, .. }) => assert_eq!(name, expected),
+            _ => panic!("expected invalid parameter {}", expected),
+        }
+    }
+
+    #[test]
+    fn test_bounds() {
+        assert_eq!(bounds(-2.0, 2.0), Ok(Bounds::new(-2.0, 2.0)));
+        assert_eq!(
+            bounds(2.0, -2.0),
+            Err(BoundsError::Flipped { a: 2.0, b: -2.0 })
+        );
+        assert!(matches!(
+            bounds(f64::NAN, -2.0),
+            Err(BoundsError::Flipped { .. })
+        ));
+        assert_eq!(
+            bounds(f64::NEG_INFINITY, f64::INFINITY),
+            Err(BoundsError::NonFinite {
+                a: f64::NEG_INFINITY,
+                b: f64::INFINITY,
+            })
+        );
+    }
+
+    #[test]
+    fn test_is_sign_change() {
+        assert!(is_sign_change(-1.0, 1.0).expect("valid"));
+        assert_invalid_parameter(is_sign_change(f64::NAN, 1.0), "lhs");
+        assert_invalid_parameter(is_sign_change(1.0, f64::NAN), "rhs");
+    }
+
+    #[test]
+    fn test_first_bracket() {
+        let fin = |x: f64| x + 9.0;
+        let f = RealFn::new(&fin);
+        let b = Bounds::new(-100.0, 100.0);
+
+        let win = first_bracket(&f, &b, 10.0).expect("valid").expect("found");
+        assert_eq!(win, Bounds::new(-10.0, 0.0));
+
+        assert_invalid_parameter(first_bracket(&f, &b, 0.0), "window_size");
+        assert_invalid_parameter(first_bracket(&f, &b, -1.0), "window_size");
+
+        // NaN inside search region
+        let fin = |x: f64| if x > 5.0 { f64::NAN } else { 1.0 };
+        let f = RealFn::new(&fin);
+        match first_bracket(&f, &b, 10.0) {
+            Err(RootError::NonFiniteEvaluation { x }) => assert_eq!(x, 10.0),
+            _ => panic!("expected NaN evaluation"),
+        }
+    }
+
+    #[test]
+    fn test_is_sign_change_eps() {
+        assert!(!is_sign_change_eps(-1e-12, 1.0, 1e-9).expect("valid"));
+        assert!(is_sign_change_eps(-1.0, 1.0, 1e-9).expect("valid"));
+        assert_invalid_parameter(is_sign_change_eps(f64::NAN, 1.0, 0.0), "lhs");
+        assert_invalid_parameter(is_sign_change_eps(-1.0, 1.0, -1.0), "zero_eps");
+    }
+
+    #[test]
+    fn test_bracket_generator() {
+        let fin = |x: f64| if x > 2.5 { f64::NAN } else { x.sin() };
+        let f = RealFn::new(&fin);
+        let b = Bounds::new(-0.5, 4.0);
+
+        assert_invalid_parameter(bracket_generator(&f, b, 0.0), "window_size");
+        assert_invalid_parameter(bracket_generator(&f, b, f64::NAN), "window_size");
+
+        let mut brackets = bracket_generator(&f, b, 1.0).expect("valid");
+        let first = brackets.try_next().expect("valid").expect("found");
+        assert_eq!(first, Bounds::new(-0.5, 0.5));
+        match brackets.try_next() {
+            Err(RootError::NonFiniteEvaluation { x }) => assert_eq!(x, 3.5),
+            _ => panic!("expected NaN evaluation"),
+        }
+    }
+
+    #[test]
+    fn test_expand_bracket() {
+        let fin = |x: f64| x - 3.0;
+        let f = RealFn::new(&fin);
+
+        let b = expand_bracket(&f, 0.0, 1.0, 2.0, 10)
+            .expect("valid")
+            .expect("found");
+        assert!(b.a <= 3.0 && 3.0 <= b.b);
+
+        assert_invalid_parameter(expand_bracket(&f, f64::NAN, 1.0, 2.0, 10), "x0");
+        assert_invalid_parameter(expand_bracket(&f, 0.0, 0.0, 2.0, 10), "initial_step");
+        assert_invalid_parameter(expand_bracket(&f, 0.0, 1.0, 1.0, 10), "factor");
+        assert_invalid_parameter(expand_bracket(&f, 0.0, 1.0, f64::INFINITY, 10), "factor");
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_find_brackets_parallel() {
+        let fin = |x: f64| x.sin();
+        let b = Bounds::new(-0.5, 4.0);
+        assert_eq!(
+            find_brackets_parallel(&fin, &b, 1.0).expect("valid").len(),
+            2
+        );
+        assert_invalid_parameter(find_brackets_parallel(&fin, &b, -1.0), "window_size");
+    }
+
+    #[test]
+    fn test_criteria() {
+        assert!(delta_x(1e-9).is_ok());
+        assert_invalid_parameter(delta_x(0.0), "epsilon_abs");
+        assert_invalid_parameter(delta_x(-1.0), "epsilon_abs");
+        assert_invalid_parameter(delta_x(f64::NAN), "epsilon_abs");
+
+        assert!(fn_residual(0.0).is_ok());
+        assert_invalid_parameter(fn_residual(-1.0), "epsilon_abs");
+        assert_invalid_parameter(fn_residual(f64::NAN), "epsilon_abs");
+
+        assert!(recording_delta_x(1e-9).is_ok());
+        assert_invalid_parameter(recording_delta_x(0.0), "epsilon_abs");
+
+        assert!(adaptive_residual(1e-3, 2).is_ok());
+        assert_invalid_parameter(adaptive_residual(0.0, 2), "epsilon_rel");
+        assert_invalid_parameter(adaptive_residual(1e-3, 0), "warmup");
+
+        assert!(significant_digits(15).is_ok());
+        assert_invalid_parameter(significant_digits(0), "digits");
+        assert_invalid_parameter(significant_digits(16), "digits");
+
+        let in_f = |x: f64| x;
+        let in_df = |_: f64| 1.0;
+        let f = RealFnAndFirst::new(&in_f, &in_df);
+        assert!(newton_step(&f, 1e-9).is_ok());
+        assert_invalid_parameter(newton_step(&f, f64::INFINITY), "epsilon_abs");
+
+        assert!(bracket_and_residual(1e-9, 0.0).is_ok());
+        assert_invalid_parameter(bracket_and_residual(0.0, 0.0), "width_eps");
+        assert_invalid_parameter(bracket_and_residual(1e-9, -1.0), "residual_eps");
+
+        assert!(target_crossed(2.0).is_ok());
+        assert_invalid_parameter(target_crossed(f64::NAN), "target");
+
+        assert!(stagnation(2, 1e-9).is_ok());
+        assert_invalid_parameter(stagnation(1, 1e-9), "window");
+        assert_invalid_parameter(stagnation(2, 0.0), "epsilon");
+    }
+
+    #[test]
+    fn test_function_wrappers() {
+        let in_f = |x: f64| x * x;
+        let in_df = |x: f64| 2.0 * x;
+
+        let f = numerical_diff(&in_f, 1e-6).expect("valid");
+        assert!((f.eval_df(3.0) - 6.0).abs() < 1e-6);
+        assert_invalid_parameter(numerical_diff(&in_f, 0.0), "h");
+
+        let f = numerical_second(&in_f, &in_df, 1e-4).expect("valid");
+        assert!((f.eval_d2f(3.0) - 2.0).abs() < 1e-6);
+        assert_invalid_parameter(numerical_second(&in_f, &in_df, f64::NAN), "h");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_caching_fn() {
+        let in_f = |x: f64| x * x;
+        let f = RealFn::new(&in_f);
+        assert!(caching_fn(&f, 4).is_ok());
+        assert_invalid_parameter(caching_fn(&f, 0), "capacity");
+    }
+
+    #[test]
+    fn test_find_roots() {
+        let roots = find_roots(|x: f64| x.sin(), -0.1, 6.3, 1e-9).expect("valid");
+        assert_eq!(roots.len(), 3);
+
+        assert_invalid_parameter(find_roots(|x: f64| x, f64::NAN, 1.0, 1e-9), "a");
+        assert_invalid_parameter(find_roots(|x: f64| x, -1.0, f64::INFINITY, 1e-9), "b");
+        assert_invalid_parameter(find_roots(|x: f64| x, 1.0, -1.0, 1e-9), "b");
+        assert_invalid_parameter(find_roots(|x: f64| x, -1.0, 1.0, 0.0), "tol");
+
+        let fin = |x: f64| if x > 0.5 { f64::NAN } else { x };
+        match find_roots(fin, -1.0, 1.0, 1e-9) {
+            Err(RootError::NonFiniteEvaluation { .. }) => {}
+            _ => panic!("expected NaN evaluation"),
+        }
+    }
+
+    #[test]
+    fn test_find_all_roots() {
+        let fin = |x: f64| x.sin();
+        let f = RealFn::new(&fin);
+        let b = Bounds::new(-0.1, 6.3);
+
+        let roots = find_all_roots(&f, &b, 0.5, BracketMethod::Brent, 100).expect("valid");
+        assert_eq!(roots.len(), 3);
+        assert_invalid_parameter(
+            find_all_roots(&f, &b, 0.0, BracketMethod::Brent, 100),
+            "window_size",
+        );
+
+        let fin = |x: f64| if x > 4.0 { f64::NAN } else { x.sin() };
+        let f = RealFn::new(&fin);
+        match find_all_roots(&f, &b, 0.5, BracketMethod::Illinois, 100) {
+            Err(RootError::NonFiniteEvaluation { .. }) => {}
+            _ => panic!("expected NaN evaluation"),
+        }
+    }
+
+    #[test]
+    fn test_newton_raphson() {
+        let in_f = |x| (x - 5.0) * (x - 4.0);
+        let in_df = |x| 2.0 * x - 9.0;
+        let f = RealFnAndFirst::new(&in_f, &in_df);
+        let conv = DeltaX::new(1e-9);
+
+        let root = newton_raphson(&f, 5.8, &conv, 100).expect("root");
+        assert!((root - 5.0).abs() < 1e-9);
+        assert_invalid_parameter(newton_raphson(&f, f64::NAN, &conv, 100), "start");
+    }
+
+    #[test]
+    fn test_newton_raphson_variants() {
+        let in_f = |x: f64| x * x - 2.0;
+        let in_df = |x: f64| 2.0 * x;
+        let f = RealFnAndFirst::new(&in_f, &in_df);
+        let conv = DeltaX::new(1e-9);
+
+        let root = newton_raphson_clamped(&f, 1.0, 0.25, &conv, 100).expect("root");
+        assert!((root - 2f64.sqrt()).abs() < 1e-9);
+        assert_invalid_parameter(newton_raphson_clamped(&f, 1.0, 0.0, &conv, 100), "max_step");
+        assert_invalid_parameter(
+            newton_raphson_clamped(&f, f64::NAN, 0.25, &conv, 100),
+            "start",
+        );
+
+        assert_invalid_parameter(newton_raphson_robust(&f, f64::NAN, &conv, 100, 3), "start");
+        assert_invalid_parameter(
+            newton_raphson_damped(&f, f64::INFINITY, &conv, 100, 3),
+            "start",
+        );
+
+        let root = solve_verified(&f, 1.0, 1e-9, 1e-12, 100).expect("root");
+        assert!((root - 2f64.sqrt()).abs() < 1e-9);
+        assert_invalid_parameter(solve_verified(&f, 1.0, 0.0, 1e-12, 100), "x_tol");
+        assert_invalid_parameter(solve_verified(&f, 1.0, 1e-9, -1.0, 100), "max_residual");
+    }
+
+    #[test]
+    fn test_safe_newton() {
+        let in_f = |x: f64| x * x - 2.0;
+        let in_df = |x: f64| 2.0 * x;
+        let f = RealFnAndFirst::new(&in_f, &in_df);
+        let conv = DeltaX::new(1e-9);
+        let b = Bounds::new(0.0, 2.0);
+
+        let root = safe_newton(&f, &b, &conv, 100).expect("root");
+        assert!((root - 2f64.sqrt()).abs() < 1e-9);
+        let root = solve_to_x_tol(&f, &b, 1e-9, 100).expect("root");
+        assert!((root - 2f64.sqrt()).abs() < 1e-9);
+        assert_invalid_parameter(solve_to_x_tol(&f, &b, 0.0, 100), "x_tol");
+
+        let (root, _) = race(&f, &b, 1.0, &conv, 100).expect("root");
+        assert!((root - 2f64.sqrt()).abs() < 1e-8);
+        assert_invalid_parameter(race(&f, &b, f64::NAN, &conv, 100), "guess");
+
+        // NaN endpoint
+        let in_f = |x: f64| if x > 1.5 { f64::NAN } else { x - 1.0 };
+        let f = RealFnAndFirst::new(&in_f, &in_df);
+        match safe_newton(&f, &b, &conv, 100) {
+            Err(RootError::NonFiniteEvaluation { x }) => assert_eq!(x, 2.0),
+            _ => panic!("expected NaN evaluation"),
+        }
+        match race(&f, &b, 1.0, &conv, 100) {
+            Err(RootError::NonFiniteEvaluation { x }) => assert_eq!(x, 2.0),
+            _ => panic!("expected NaN evaluation"),
+        }
+    }
+
+    #[test]
+    fn test_halley_method() {
+        let in_f = |x: f64| x.sin();
+        let in_df = |x: f64| x.cos();
+        let in_d2f = |x: f64| -x.sin();
+        let f = RealFnAndFirstSecond::new(&in_f, &in_df, &in_d2f);
+        let conv = DeltaX::new(1e-9);
+
+        assert_invalid_parameter(halley_method(&f, f64::INFINITY, &conv, 100), "start");
+        assert_invalid_parameter(halley_method_adaptive(&f, f64::NAN, &conv, 100), "start");
+        assert_invalid_parameter(schroder(&f, f64::NAN, &conv, 100), "start");
+
+        let in_d3f = |x: f64| -x.cos();
+        let f = RealFnThroughThird::new(&in_f, &in_df, &in_d2f, &in_d3f);
+        assert_invalid_parameter(householder3(&f, f64::NAN, &conv, 100), "start");
+    }
+
+    #[test]
+    fn test_polynomial_solvers() {
+        assert_invalid_parameter(polynomial(vec![]), "coeffs");
+        assert_invalid_parameter(polynomial(vec![1.0, f64::NAN]), "coeffs");
+
+        // (x - 1)(x - 2)(x^2 + 1)
+        let p = polynomial(vec![2.0, -3.0, 3.0, -3.0, 1.0]).expect("valid");
+        let root = laguerre(&p, 0.0, 1e-12, 100).expect("root");
+        assert!(p.eval(root).abs() < 1e-9);
+        assert_invalid_parameter(laguerre(&p, f64::NAN, 1e-12, 100), "start");
+        assert_invalid_parameter(laguerre(&p, 0.0, 0.0, 100), "tolerance");
+        let constant = polynomial(vec![3.0]).expect("valid");
+        assert_invalid_parameter(laguerre(&constant, 0.0, 1e-12, 100), "degree");
+
+        assert!(bairstow(&p, 0.0, 1.0, 100, 1e-12).is_ok());
+        assert_invalid_parameter(bairstow(&constant, 0.0, 1.0, 100, 1e-12), "degree");
+        assert_invalid_parameter(bairstow(&p, f64::NAN, 1.0, 100, 1e-12), "u0");
+        assert_invalid_parameter(bairstow(&p, 0.0, 1.0, 100, 0.0), "tol");
+
+        assert_eq!(bairstow_factors(&p, 100, 1e-12).expect("factors").len(), 2);
+        assert_invalid_parameter(bairstow_factors(&p, 100, -1.0), "tol");
+
+        assert_eq!(aberth(&p, 100, 1e-12).expect("roots").len(), 4);
+        assert_invalid_parameter(aberth(&p, 100, f64::NAN), "tol");
+    }
+
+    #[test]
+    fn test_complex_solvers() {
+        let in_f = |z: Complex<f64>| z * z + 1.0;
+        let in_df = |z: Complex<f64>| z * 2.0;
+        let i = Complex::new(0.0, 1.0);
+
+        let f = ComplexFn::new(&in_f);
+        let root = muller(&f, 0.5, 1.0, 1.5, 1e-12, 50).expect("root");
+        assert!((root - i).norm() < 1e-12 || (root + i).norm() < 1e-12);
+        assert_invalid_parameter(muller(&f, 0.5, f64::NAN, 1.5, 1e-12, 50), "x1");
+        assert_invalid_parameter(muller(&f, 0.5, 0.5, 1.5, 1e-12, 50), "x1");
+        assert_invalid_parameter(muller(&f, 0.5, 1.0, 1.0, 1e-12, 50), "x2");
+        assert_invalid_parameter(muller(&f, 0.5, 1.0, 1.5, 0.0, 50), "tolerance");
+
+        let f = ComplexFnAndFirst::new(&in_f, &in_df);
+        let conv = DeltaX::new(1e-12);
+        let root = complex_newton(&f, Complex::new(0.5, 0.5), &conv, 50).expect("root");
+        assert!((root - i).norm() < 1e-12);
+        assert_invalid_parameter(
+            complex_newton(&f, Complex::new(0.5, f64::NAN), &conv, 50),
+            "start",
+        );
+    }
+
+    #[test]
+    fn test_fixed_point_solvers() {
+        let g = |x: f64| x.cos();
+        let f = RealFn::new(&g);
+        let conv = DeltaX::new(1e-12);
+
+        assert!(fixed_point(&g, 1.0, &conv, 200).is_ok());
+        assert_invalid_parameter(fixed_point(&g, f64::NAN, &conv, 200), "start");
+        assert_invalid_parameter(aitken_accelerate(&g, f64::NAN, &conv, 200), "start");
+        assert_invalid_parameter(wegstein(&f, f64::INFINITY, &conv, 200), "x0");
+
+        let in_f = |x: f64| x * x - 2.0;
+        let f = RealFn::new(&in_f);
+        let root = inverse_quadratic(&f, 1.0, 1.5, 2.0, &conv, 100).expect("root");
+        assert!((root - 2f64.sqrt()).abs() < 1e-9);
+        assert_invalid_parameter(inverse_quadratic(&f, 1.0, 1.5, f64::NAN, &conv, 100), "x2");
+    }
+
+    #[test]
+    fn test_bisection() {
+        let in_f = |x: f64| x * x - 2.0;
+        let f = RealFn::new(&in_f);
+
+        let root = bisection(&f, &Bounds::new(0.0, 2.0), &DeltaX::new(1e-9), 100).expect("root");
+        assert!((root - 2f64.sqrt()).abs() < 1e-8);
+
+        match bisection(&f, &Bounds::new(-10.0, -5.0), &DeltaX::new(1e-9), 100) {
+            Err(RootError::NoSignChange { a, b }) => {
+                assert_eq!(a, -10.0);
+                assert_eq!(b, -5.0);
+            }
+            _ => panic!("expected no sign change"),
+        }
+
+        // NaN endpoint and NaN mid-solve
+        let in_f = |x: f64| if x > 0.75 { f64::NAN } else { x - 0.5 };
+        let f = RealFn::new(&in_f);
+        match bisection(&f, &Bounds::new(0.0, 1.0), &DeltaX::new(1e-9), 100) {
+            Err(RootError::NonFiniteEvaluation { x }) => assert_eq!(x, 1.0),
+            _ => panic!("expected NaN evaluation"),
+        }
+
+        let in_f = |x: f64| if x == 0.5 { f64::NAN } else { x - 0.75 };
+        let f = RealFn::new(&in_f);
+        match bisection(&f, &Bounds::new(0.0, 1.0), &DeltaX::new(1e-9), 100) {
+            Err(RootError::NonFiniteEvaluation { x }) => assert_eq!(x, 0.5),
+            _ => panic!("expected NaN evaluation"),
+        }
+    }
+
+    #[test]
+    fn test_bisection_variants() {
+        let in_f = |x: f64| x * x - 2.0;
+        let f = RealFn::new(&in_f);
+        let b = Bounds::new(0.0, 2.0);
+        let conv = DeltaX::new(1e-9);
+
+        let win = bisection_bracket(&f, &b, &conv, 100).expect("bracket");
+        assert!(win.a <= 2f64.sqrt() && 2f64.sqrt() <= win.b);
+
+        assert_eq!(bisection_iterations_for(&b, 0.5).expect("valid"), 2);
+        assert_invalid_parameter(bisection_iterations_for(&b, 0.0), "eps");
+
+        let win = tighten_bracket(&f, &b, 1e-3, 100).expect("bracket");
+        assert!(win.size() <= 1e-3);
+        assert_invalid_parameter(tighten_bracket(&f, &b, -1.0, 100), "target_width");
+
+        assert!(bisection_eps(&f, &b, 1e-12, 100).is_ok());
+        assert_invalid_parameter(bisection_eps(&f, &b, f64::NAN, 100), "zero_eps");
+        assert!(bisection_log(&f, &Bounds::new(1e-3, 1e3), 200).is_ok());
+        assert!(bisection_ulps(&f, &b, 1, 100).is_ok());
+        assert_invalid_parameter(bisection_ulps(&f, &b, 0, 100), "max_ulps");
+        assert!(steffensen_bracketed(&f, &b, &conv, 100).is_ok());
+
+        // endpoint zero needs no sign change
+        let root = bisection_eps(&f, &Bounds::new(2f64.sqrt(), 2.0), 1e-12, 100).expect("root");
+        assert_eq!(root, 2f64.sqrt());
+
+        // NaN endpoint
+        let in_f = |x: f64| if x > 1.5 { f64::NAN } else { x - 1.0 };
+        let f = RealFn::new(&in_f);
+        let expect_nan = |result: Result<f64, RootError>| match result {
+            Err(RootError::NonFiniteEvaluation { x }) => assert_eq!(x, 2.0),
+            _ => panic!("expected NaN evaluation"),
+        };
+        expect_nan(bisection_bracket(&f, &b, &conv, 100).map(|w| w.a));
+        expect_nan(tighten_bracket(&f, &b, 1e-3, 100).map(|w| w.a));
+        expect_nan(bisection_eps(&f, &b, 1e-12, 100));
+        expect_nan(bisection_log(&f, &b, 100));
+        expect_nan(bisection_ulps(&f, &b, 1, 100));
+        expect_nan(steffensen_bracketed(&f, &b, &conv, 100));
+    }
+
+    #[test]
+    fn test_false_position_illinios() {
+        let in_f = |x: f64| x * x - 2.0;
+        let f = RealFn::new(&in_f);
+
+        let root = false_position_illinios(&f, &Bounds::new(0.0, 2.0), 100).expect("root");
+        assert!((root - 2f64.sqrt()).abs() < 1e-8);
+
+        match false_position_illinios(&f, &Bounds::new(-10.0, -5.0), 100) {
+            Err(RootError::NoSignChange { .. }) => {}
+            _ => panic!("expected no sign change"),
+        }
+
+        // infinite endpoint
+        let in_f = |x: f64| if x == 0.0 { f64::NEG_INFINITY } else { x };
+        let f = RealFn::new(&in_f);
+        match false_position_illinios(&f, &Bounds::new(0.0, 1.0), 100) {
+            Err(RootError::NonFiniteEvaluation { x }) => assert_eq!(x, 0.0),
+            _ => panic!("expected non-finite evaluation"),
+        }
+    }
+
+    #[test]
+    fn test_false_position_variants() {
+        let in_f = |x: f64| x * x - 2.0;
+        let f = RealFn::new(&in_f);
+        let b = Bounds::new(0.0, 2.0);
+
+        for root in &[
+            false_position_illinios_eps(&f, &b, 1e-12, 100),
+            false_position_illinios_relative_eps(&f, &b, 1e-12, 100),
+            false_position_pegasus(&f, &b, 100),
+            false_position_biased(&f, &b, -2.0, 100),
+        ] {
+            match *root {
+                Ok(root) => assert!((root - 2f64.sqrt()).abs() < 1e-8),
+                Err(_) => panic!("expected root"),
+            }
+        }
+
+        assert_invalid_parameter(false_position_illinios_eps(&f, &b, -1.0, 100), "flat_eps");
+        assert_invalid_parameter(
+            false_position_illinios_relative_eps(&f, &b, f64::NAN, 100),
+            "flat_eps",
+        );
+        assert_invalid_parameter(
+            false_position_biased(&f, &b, f64::INFINITY, 100),
+            "initial_bias",
+        );
+
+        // infinite endpoint
+        let in_f = |x: f64| if x == 1.0 { f64::INFINITY } else { x };
+        let f = RealFn::new(&in_f);
+        let b = Bounds::new(-1.0, 1.0);
+        let expect_infinite = |result: Result<f64, RootError>| match result {
+            Err(RootError::NonFiniteEvaluation { x }) => assert_eq!(x, 1.0),
+            _ => panic!("expected non-finite evaluation"),
+        };
+        expect_infinite(false_position_illinios_eps(&f, &b, 1e-12, 100));
+        expect_infinite(false_position_illinios_relative_eps(&f, &b, 1e-12, 100));
+        expect_infinite(false_position_pegasus(&f, &b, 100));
+        expect_infinite(false_position_biased(&f, &b, 0.0, 100));
+    }
+
+    #[test]
+    fn test_brent_ridders_itp() {
+        let in_f = |x: f64| x * x - 2.0;
+        let f = RealFn::new(&in_f);
+        let b = Bounds::new(0.0, 2.0);
+
+        assert!((brent_dekker(&f, &b, 100).expect("root") - 2f64.sqrt()).abs() < 1e-8);
+        assert!((ridders(&f, &b, 100).expect("root") - 2f64.sqrt()).abs() < 1e-8);
+        assert!((itp(&f, &b, 0.1, 2.0, 1, 100).expect("root") - 2f64.sqrt()).abs() < 1e-8);
+
+        assert_invalid_parameter(itp(&f, &b, 0.0, 2.0, 1, 100), "k1");
+        assert_invalid_parameter(itp(&f, &b, 0.1, 0.5, 1, 100), "k2");
+        assert_invalid_parameter(itp(&f, &b, 0.1, 3.0, 1, 100), "k2");
+
+        // NaN endpoint
+        let in_f = |x: f64| if x < 0.5 { f64::NAN } else { x - 1.0 };
+        let f = RealFn::new(&in_f);
+        let expect_nan = |result: Result<f64, RootError>| match result {
+            Err(RootError::NonFiniteEvaluation { x }) => assert_eq!(x, 0.0),
+            _ => panic!("expected NaN evaluation"),
+        };
+        expect_nan(brent_dekker(&f, &b, 100));
+        expect_nan(ridders(&f, &b, 100));
+        expect_nan(itp(&f, &b, 0.1, 2.0, 1, 100));
+    }
+
+    #[test]
+    fn test_system_solvers() {
+        let f = |x: [f64; 2]| [x[0] * x[0] + x[1] * x[1] - 1.0, x[1] - x[0]];
+        let jac = |x: [f64; 2]| [[2.0 * x[0], 2.0 * x[1]], [-1.0, 1.0]];
+        assert!(newton2(f, jac, [1.0, 0.5], 1e-12, 50).is_ok());
+        assert_invalid_parameter(newton2(f, jac, [1.0, f64::NAN], 1e-12, 50), "start");
+        assert_invalid_parameter(newton2(f, jac, [1.0, 0.5], 0.0, 50), "tol");
+
+        let f = |x: &[f64]| vec![x[0] * x[0] + x[1] * x[1] - 1.0, x[1] - x[0]];
+        let jac = |x: &[f64]| vec![vec![2.0 * x[0], 2.0 * x[1]], vec![-1.0, 1.0]];
+        assert!(newton_nd(f, jac, &[1.0, 0.5], 1e-12, 50).is_ok());
+        assert_invalid_parameter(newton_nd(f, jac, &[f64::INFINITY, 0.5], 1e-12, 50), "start");
+        assert_invalid_parameter(newton_nd(f, jac, &[1.0, 0.5], -1.0, 50), "tol");
+    }
+}
@@ -7,10 +7,31 @@
 //! This module also supplies two canned criteria:
 //!
 //! * DeltaX - stops when the steps along x-axis, |x_pre - x_cur|, gets small enough.
+//! * RecordingDeltaX - DeltaX which also remembers the last step it measured.
 //! * FnResidual - stops when |f(x_cur)| gets small enough.
+//! * AdaptiveResidual - stops when |f(x_cur)| gets small relative to the typical
+//!   |f| seen over the first few iterations.
+//! * SignificantDigits - stops when x_pre and x_cur agree to a number of
+//!   significant decimal digits.
+//! * NewtonStep - stops when the next Newton step, |f(x_cur)/df(x_cur)|, gets
+//!   small enough.
+//! * BracketAndResidual - stops when a bracket is narrow and the residual is
+//!   small, for bracketing methods.
+//! * TargetCrossed - stops once the iterates pass a target x-value.
+//! * Stagnation - stops when the last few iterates stop making progress.
 //!
-//! It also provides a generic wrapper `DualCriteria` allowing two
-//! IsConverged implementations to be combined.
+//! It also provides generic wrappers `DualCriteria` and `EitherCriteria`
+//! allowing two IsConverged implementations to be combined, `AllCriteria` and
+//! `AnyCriteria` for combining any number of borrowed criteria, and
+//! `MinIterations` which holds off convergence until a minimum number of
+//! iterations have run.
+//!
+//! For one-off stopping rules, `ClosureCriteria` adapts a closure taking
+//! `(x_pre, x_cur, f_cur)` without defining a new type.
+//!
+//! `IsConverged` is object safe.  When the combination of criteria is only
+//! known at runtime (e.g. read from a config file), `BoxedCriteria` and
+//! `MultiCriteria` work with boxed trait objects instead.
 //!
 //! # Examples
 //! ```
@@ -26,14 +47,25 @@
 //! // both required conditions satisfied
 //! assert_eq!(finish.is_converged(0.1, 0.1+1e-7, 1e-12), true);
 //! ```
+use alloc::boxed::Box;
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use core::cell::{Cell, RefCell};
+
+use num_traits::Float;
+
+use wrap::RealDfEval;
 
 /// Type can check if iterative root-finding process has converged.
-pub trait IsConverged {
+///
+/// The scalar type defaults to f64.  DeltaX, FnResidual, and the generic
+/// combinators also work with any `num_traits::Float`, e.g. f32.
+pub trait IsConverged<T = f64> {
     /// Indicate whether root-finding has converged.
     ///
     /// The `x_pre` and `x_cur` are the previous and current iteration x values.
     /// The `f_cur` holds f(x_cur).
-    fn is_converged(&self, x_pre: f64, x_cur: f64, f_cur: f64) -> bool;
+    fn is_converged(&self, x_pre: T, x_cur: T, f_cur: T) -> bool;
 }
 
 /// DeltaX converges when the distance along the x-axis between successive
@@ -43,21 +75,55 @@ pub trait IsConverged {
 /// prematurely.  Specifically, for a method like Newton-Raphson, a massive
 /// first derivative means taking only a small step along the x-axis even when
 /// far from the actual root.
-pub struct DeltaX {
+pub struct DeltaX<T = f64> {
+    epsilon_abs: T,
+}
+
+impl<T: Float> DeltaX<T> {
+    pub fn new(epsilon_abs: T) -> DeltaX<T> {
+        assert!(epsilon_abs > T::zero());
+        assert!(epsilon_abs.is_finite());
+        DeltaX { epsilon_abs }
+    }
+}
+
+impl<T: Float> IsConverged<T> for DeltaX<T> {
+    fn is_converged(&self, x_pre: T, x_cur: T, _f_cur: T) -> bool {
+        (x_pre - x_cur).abs() < self.epsilon_abs
+    }
+}
+
+/// RecordingDeltaX behaves like DeltaX but records the last |x_pre - x_cur|.
+///
+/// After a successful solve, `last_delta()` is the size of the final step and
+/// so certifies the precision actually achieved, which may be well below
+/// epsilon_abs for quickly converging methods.
+pub struct RecordingDeltaX {
     epsilon_abs: f64,
+    last_delta: Cell<Option<f64>>,
 }
 
-impl DeltaX {
-    pub fn new(epsilon_abs: f64) -> DeltaX {
+impl RecordingDeltaX {
+    pub fn new(epsilon_abs: f64) -> RecordingDeltaX {
         assert!(epsilon_abs > 0.0);
         assert!(epsilon_abs.is_finite());
-        DeltaX { epsilon_abs }
+        RecordingDeltaX {
+            epsilon_abs,
+            last_delta: Cell::new(None),
+        }
+    }
+
+    /// Last measured step size, or None if convergence was never checked.
+    pub fn last_delta(&self) -> Option<f64> {
+        self.last_delta.get()
     }
 }
 
-impl IsConverged for DeltaX {
+impl IsConverged for RecordingDeltaX {
     fn is_converged(&self, x_pre: f64, x_cur: f64, _f_cur: f64) -> bool {
-        (x_pre - x_cur).abs() < self.epsilon_abs
+        let delta = (x_pre - x_cur).abs();
+        self.last_delta.set(Some(delta));
+        delta < self.epsilon_abs
     }
 }
 
@@ -66,58 +132,508 @@ impl IsConverged for DeltaX {
 /// Be aware that convergence can happen far from the actual root.  For example,
 /// f(x)=-1e-7x+0.01 has the root at 100000, but with an epsilon_abs of 1e-3 we
 /// would converge anywhere in the range [90000, 110000].
-pub struct FnResidual {
-    epsilon_abs: f64,
+pub struct FnResidual<T = f64> {
+    epsilon_abs: T,
 }
 
-impl FnResidual {
-    pub fn new(epsilon_abs: f64) -> FnResidual {
-        assert!(epsilon_abs >= 0.0);
+impl<T: Float> FnResidual<T> {
+    pub fn new(epsilon_abs: T) -> FnResidual<T> {
+        assert!(epsilon_abs >= T::zero());
         assert!(epsilon_abs.is_finite());
         FnResidual { epsilon_abs }
     }
 }
 
-impl IsConverged for FnResidual {
-    fn is_converged(&self, _x_pre: f64, _x_cur: f64, f_cur: f64) -> bool {
+impl<T: Float> IsConverged<T> for FnResidual<T> {
+    fn is_converged(&self, _x_pre: T, _x_cur: T, f_cur: T) -> bool {
         f_cur.abs() < self.epsilon_abs
     }
 }
 
+/// AdaptiveResidual converges when the residual is small relative to the
+/// function's own scale.
+///
+/// A fixed FnResidual epsilon stops prematurely on functions with tiny values
+/// and never stops on functions with huge ones.  Instead, this records the
+/// largest |f(x_cur)| over the first `warmup` checks as a baseline and then
+/// converges once |f(x_cur)| < epsilon_rel * baseline.  An exact zero always
+/// converges.
+///
+/// The baseline is tracked internally, so a fresh instance should be used for
+/// each solve (or `reset()` called in between).
+pub struct AdaptiveResidual {
+    epsilon_rel: f64,
+    warmup: usize,
+    calls: Cell<usize>,
+    baseline: Cell<f64>,
+}
+
+impl AdaptiveResidual {
+    pub fn new(epsilon_rel: f64, warmup: usize) -> AdaptiveResidual {
+        assert!(epsilon_rel > 0.0);
+        assert!(epsilon_rel.is_finite());
+        assert!(warmup > 0);
+        AdaptiveResidual {
+            epsilon_rel,
+            warmup,
+            calls: Cell::new(0),
+            baseline: Cell::new(0.0),
+        }
+    }
+
+    /// Current baseline estimate of the typical |f|.
+    pub fn baseline(&self) -> f64 {
+        self.baseline.get()
+    }
+
+    /// Forget the baseline so the criterion can be reused.
+    pub fn reset(&self) {
+        self.calls.set(0);
+        self.baseline.set(0.0);
+    }
+}
+
+impl IsConverged for AdaptiveResidual {
+    fn is_converged(&self, _x_pre: f64, _x_cur: f64, f_cur: f64) -> bool {
+        let calls = self.calls.get() + 1;
+        self.calls.set(calls);
+        if calls <= self.warmup && f_cur.abs() > self.baseline.get() {
+            self.baseline.set(f_cur.abs());
+        }
+        f_cur == 0.0 || f_cur.abs() < self.epsilon_rel * self.baseline.get()
+    }
+}
+
+/// SignificantDigits converges when successive iterations agree to the
+/// requested number of significant decimal digits.
+///
+/// Agreement is judged by the relative difference:
+///
+/// |x_pre - x_cur| <= 0.5 * 10^(-digits) * max(|x_pre|, |x_cur|)
+///
+/// Unlike DeltaX this is scale-invariant, so the same criterion works for roots
+/// near 1e-8 and near 1e8.  It cannot converge on a root at exactly zero unless
+/// the iterations land on it, so pair it with FnResidual in that case.
+pub struct SignificantDigits {
+    epsilon_rel: f64,
+}
+
+impl SignificantDigits {
+    /// Digits must be between 1 and 15, the limit of f64 precision.
+    pub fn new(digits: u32) -> SignificantDigits {
+        assert!((1..=15).contains(&digits));
+        SignificantDigits {
+            epsilon_rel: 0.5 * 10f64.powi(-(digits as i32)),
+        }
+    }
+}
+
+impl IsConverged for SignificantDigits {
+    fn is_converged(&self, x_pre: f64, x_cur: f64, _f_cur: f64) -> bool {
+        let scale = x_pre.abs().max(x_cur.abs());
+        (x_pre - x_cur).abs() <= self.epsilon_rel * scale
+    }
+}
+
+/// NewtonStep converges when the size of the next Newton-Raphson step,
+/// |f(x_cur)/df(x_cur)|, is smaller than epsilon_abs.
+///
+/// Near a simple root this estimates the distance to the root.  A zero or
+/// non-finite derivative never converges, which makes this useful alongside
+/// DeltaX when derivatives overflow (see test_pathology_microstep()).
+pub struct NewtonStep<'a, F: 'a + RealDfEval> {
+    f: &'a F,
+    epsilon_abs: f64,
+}
+
+impl<'a, F: 'a + RealDfEval> NewtonStep<'a, F> {
+    pub fn new(f: &'a F, epsilon_abs: f64) -> NewtonStep<'a, F> {
+        assert!(epsilon_abs > 0.0);
+        assert!(epsilon_abs.is_finite());
+        NewtonStep { f, epsilon_abs }
+    }
+}
+
+impl<'a, F: RealDfEval> IsConverged for NewtonStep<'a, F> {
+    fn is_converged(&self, _x_pre: f64, x_cur: f64, f_cur: f64) -> bool {
+        let df_cur = self.f.eval_df(x_cur);
+        if df_cur == 0.0 || !df_cur.is_finite() {
+            return false;
+        }
+        (f_cur / df_cur).abs() < self.epsilon_abs
+    }
+}
+
+/// BracketAndResidual converges when the bracket is narrow and the residual
+/// is small: |x_cur - x_pre| < width_eps and |f_cur| < residual_eps.
+///
+/// Meant for bracketing solvers such as `bisection`.  These do not pass the
+/// bracket endpoints; they pass successive midpoints as `x_pre` and `x_cur`,
+/// which are one bracket width apart, and the residual at the latest midpoint
+/// as `f_cur`.  Requiring both guards against a shallow slope (small residual
+/// far from the root) as well as a steep one (narrow bracket with large
+/// residual).  It is the same test as a DualCriteria of DeltaX and
+/// FnResidual, just without the nesting.
+pub struct BracketAndResidual {
+    width_eps: f64,
+    residual_eps: f64,
+}
+
+impl BracketAndResidual {
+    pub fn new(width_eps: f64, residual_eps: f64) -> BracketAndResidual {
+        assert!(width_eps > 0.0);
+        assert!(width_eps.is_finite());
+        assert!(residual_eps >= 0.0);
+        assert!(residual_eps.is_finite());
+        BracketAndResidual {
+            width_eps,
+            residual_eps,
+        }
+    }
+}
+
+impl IsConverged for BracketAndResidual {
+    fn is_converged(&self, x_pre: f64, x_cur: f64, f_cur: f64) -> bool {
+        (x_cur - x_pre).abs() < self.width_eps && f_cur.abs() < self.residual_eps
+    }
+}
+
+/// TargetCrossed converges once successive iterations straddle `target`.
+///
+/// This ignores accuracy entirely.  It is meant for drivers which advance a
+/// parameter until some threshold is passed, and typically gets combined with
+/// a real tolerance via `EitherCriteria`.  Landing exactly on `target` counts
+/// as crossing it.
+///
+/// # Examples
+/// ```
+/// use rootfind::convergence::*;
+/// use rootfind::solver::newton_raphson;
+/// use rootfind::wrap::RealFnAndFirst;
+///
+/// // Newton iterates for x^2 - 100 from x=60: 30.8, 16.0, 11.1, ...
+/// let in_f = |x: f64| x * x - 100.0;
+/// let in_df = |x: f64| 2.0 * x;
+/// let f = RealFnAndFirst::new(&in_f, &in_df);
+///
+/// // halts on the first iterate below 30, long before the root at 10
+/// let finish = TargetCrossed::new(30.0);
+/// let x = newton_raphson(&f, 60.0, &finish, 100).expect("crossing");
+/// assert!(x < 30.0 && x > 15.0);
+///
+/// // either crossing x=5 or converging on the root, whichever comes first
+/// let c1 = TargetCrossed::new(5.0);
+/// let c2 = DeltaX::new(1e-9);
+/// let finish = EitherCriteria::new(&c1, &c2);
+/// let x = newton_raphson(&f, 60.0, &finish, 100).expect("root");
+/// assert!((x - 10.0).abs() < 1e-9);
+/// ```
+pub struct TargetCrossed {
+    target: f64,
+}
+
+impl TargetCrossed {
+    pub fn new(target: f64) -> TargetCrossed {
+        assert!(target.is_finite());
+        TargetCrossed { target }
+    }
+}
+
+impl IsConverged for TargetCrossed {
+    fn is_converged(&self, x_pre: f64, x_cur: f64, _f_cur: f64) -> bool {
+        let d_pre = x_pre - self.target;
+        let d_cur = x_cur - self.target;
+        d_cur == 0.0 || (d_pre < 0.0) != (d_cur < 0.0)
+    }
+}
+
+/// Stagnation signals a stop once iteration has ground to a halt.
+///
+/// The last `window` values of x_cur are kept, and once the window is full
+/// their spread, max - min, is compared against epsilon.  This does not mean
+/// a root was found, so it is meant to be combined with a real tolerance via
+/// `EitherCriteria` or `AnyCriteria`.  After the solve, `stagnated()` tells
+/// the two outcomes apart.
+///
+/// The window is tracked internally, so a fresh instance should be used for
+/// each solve (or `reset()` called in between).
+pub struct Stagnation {
+    window: usize,
+    epsilon: f64,
+    recent: RefCell<VecDeque<f64>>,
+    stagnated: Cell<bool>,
+}
+
+impl Stagnation {
+    pub fn new(window: usize, epsilon: f64) -> Stagnation {
+        assert!(window > 1);
+        assert!(epsilon > 0.0);
+        assert!(epsilon.is_finite());
+        Stagnation {
+            window,
+            epsilon,
+            recent: RefCell::new(VecDeque::with_capacity(window)),
+            stagnated: Cell::new(false),
+        }
+    }
+
+    /// Whether the last check found the iteration stagnant.
+    pub fn stagnated(&self) -> bool {
+        self.stagnated.get()
+    }
+
+    /// Forget recorded iterates so the criterion can be reused.
+    pub fn reset(&self) {
+        self.recent.borrow_mut().clear();
+        self.stagnated.set(false);
+    }
+}
+
+impl IsConverged for Stagnation {
+    fn is_converged(&self, _x_pre: f64, x_cur: f64, _f_cur: f64) -> bool {
+        let mut recent = self.recent.borrow_mut();
+        if recent.len() == self.window {
+            recent.pop_front();
+        }
+        recent.push_back(x_cur);
+
+        let stagnated = recent.len() == self.window && {
+            let lo = recent.iter().cloned().fold(f64::INFINITY, f64::min);
+            let hi = recent.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+            hi - lo < self.epsilon
+        };
+        self.stagnated.set(stagnated);
+        stagnated
+    }
+}
+
 /// DualCriteria combines two IsConverged implementors.
 ///
 /// Both must be true for convergence.
-pub struct DualCriteria<'a, C1: 'a + IsConverged, C2: 'a + IsConverged> {
+pub struct DualCriteria<'a, C1: 'a, C2: 'a> {
     c1: &'a C1,
     c2: &'a C2,
 }
 
-impl<'a, C1: 'a + IsConverged, C2: 'a + IsConverged> DualCriteria<'a, C1, C2> {
+impl<'a, C1: 'a, C2: 'a> DualCriteria<'a, C1, C2> {
     pub fn new(c1: &'a C1, c2: &'a C2) -> DualCriteria<'a, C1, C2> {
         DualCriteria { c1, c2 }
     }
 }
 
-impl<'a, C1: IsConverged, C2: IsConverged> IsConverged for DualCriteria<'a, C1, C2> {
-    fn is_converged(&self, x_pre: f64, x_cur: f64, f_cur: f64) -> bool {
+impl<'a, T, C1, C2> IsConverged<T> for DualCriteria<'a, C1, C2>
+where
+    T: Copy,
+    C1: IsConverged<T>,
+    C2: IsConverged<T>,
+{
+    fn is_converged(&self, x_pre: T, x_cur: T, f_cur: T) -> bool {
         self.c1.is_converged(x_pre, x_cur, f_cur) && self.c2.is_converged(x_pre, x_cur, f_cur)
     }
 }
 
+/// EitherCriteria combines two IsConverged implementors.
+///
+/// Either being true is enough for convergence.  Both are always evaluated, so
+/// stateful criteria like `MinIterations` keep an accurate count.
+pub struct EitherCriteria<'a, C1: 'a, C2: 'a> {
+    c1: &'a C1,
+    c2: &'a C2,
+}
+
+impl<'a, C1: 'a, C2: 'a> EitherCriteria<'a, C1, C2> {
+    pub fn new(c1: &'a C1, c2: &'a C2) -> EitherCriteria<'a, C1, C2> {
+        EitherCriteria { c1, c2 }
+    }
+}
+
+impl<'a, T, C1, C2> IsConverged<T> for EitherCriteria<'a, C1, C2>
+where
+    T: Copy,
+    C1: IsConverged<T>,
+    C2: IsConverged<T>,
+{
+    fn is_converged(&self, x_pre: T, x_cur: T, f_cur: T) -> bool {
+        let r1 = self.c1.is_converged(x_pre, x_cur, f_cur);
+        let r2 = self.c2.is_converged(x_pre, x_cur, f_cur);
+        r1 || r2
+    }
+}
+
+/// AllCriteria combines any number of borrowed IsConverged implementors.
+///
+/// All must be true for convergence.  An empty list is always converged.
+///
+/// # Examples
+/// ```
+/// use rootfind::convergence::*;
+///
+/// let c1 = DeltaX::new(1e-6);
+/// let c2 = FnResidual::new(1e-9);
+/// let c3 = TargetCrossed::new(0.0);
+/// let finish = AllCriteria::new(vec![&c1, &c2, &c3]);
+///
+/// assert_eq!(finish.is_converged(0.1, 0.1+1e-7, 1e-12), false);
+/// assert_eq!(finish.is_converged(1e-8, -1e-8, 1e-12), true);
+/// ```
+pub struct AllCriteria<'a> {
+    criteria: Vec<&'a dyn IsConverged>,
+}
+
+impl<'a> AllCriteria<'a> {
+    pub fn new(criteria: Vec<&'a dyn IsConverged>) -> AllCriteria<'a> {
+        AllCriteria { criteria }
+    }
+}
+
+impl<'a> IsConverged for AllCriteria<'a> {
+    fn is_converged(&self, x_pre: f64, x_cur: f64, f_cur: f64) -> bool {
+        self.criteria
+            .iter()
+            .all(|c| c.is_converged(x_pre, x_cur, f_cur))
+    }
+}
+
+/// AnyCriteria combines any number of borrowed IsConverged implementors.
+///
+/// Any being true is enough for convergence.  An empty list never converges.
+/// As with `EitherCriteria`, every criterion is always evaluated.
+pub struct AnyCriteria<'a> {
+    criteria: Vec<&'a dyn IsConverged>,
+}
+
+impl<'a> AnyCriteria<'a> {
+    pub fn new(criteria: Vec<&'a dyn IsConverged>) -> AnyCriteria<'a> {
+        AnyCriteria { criteria }
+    }
+}
+
+impl<'a> IsConverged for AnyCriteria<'a> {
+    fn is_converged(&self, x_pre: f64, x_cur: f64, f_cur: f64) -> bool {
+        let mut converged = false;
+        for c in &self.criteria {
+            converged |= c.is_converged(x_pre, x_cur, f_cur);
+        }
+        converged
+    }
+}
+
+/// ClosureCriteria adapts a closure into an IsConverged implementor.
+///
+/// The closure receives `(x_pre, x_cur, f_cur)` and returns true once
+/// converged.
+///
+/// # Examples
+/// ```
+/// use rootfind::convergence::*;
+///
+/// // stop as soon as the iteration crosses x=2
+/// let finish = ClosureCriteria::new(|_x_pre: f64, x_cur: f64, _f_cur: f64| x_cur > 2.0);
+///
+/// assert_eq!(finish.is_converged(1.0, 1.9, 0.5), false);
+/// assert_eq!(finish.is_converged(1.9, 2.1, 0.5), true);
+/// ```
+pub struct ClosureCriteria<F: Fn(f64, f64, f64) -> bool> {
+    f: F,
+}
+
+impl<F: Fn(f64, f64, f64) -> bool> ClosureCriteria<F> {
+    pub fn new(f: F) -> ClosureCriteria<F> {
+        ClosureCriteria { f }
+    }
+}
+
+impl<F: Fn(f64, f64, f64) -> bool> IsConverged for ClosureCriteria<F> {
+    fn is_converged(&self, x_pre: f64, x_cur: f64, f_cur: f64) -> bool {
+        (self.f)(x_pre, x_cur, f_cur)
+    }
+}
+
+/// BoxedCriteria wraps a runtime-selected IsConverged implementor.
+pub struct BoxedCriteria(pub Box<dyn IsConverged>);
+
+impl IsConverged for BoxedCriteria {
+    fn is_converged(&self, x_pre: f64, x_cur: f64, f_cur: f64) -> bool {
+        self.0.is_converged(x_pre, x_cur, f_cur)
+    }
+}
+
+/// MultiCriteria combines any number of boxed IsConverged implementors.
+///
+/// All must be true for convergence.  An empty list is always converged.
+pub struct MultiCriteria {
+    criteria: Vec<Box<dyn IsConverged>>,
+}
+
+impl MultiCriteria {
+    pub fn new(criteria: Vec<Box<dyn IsConverged>>) -> MultiCriteria {
+        MultiCriteria { criteria }
+    }
+}
+
+impl IsConverged for MultiCriteria {
+    fn is_converged(&self, x_pre: f64, x_cur: f64, f_cur: f64) -> bool {
+        self.criteria
+            .iter()
+            .all(|c| c.is_converged(x_pre, x_cur, f_cur))
+    }
+}
+
+/// MinIterations forces at least `min_iter` iterations before deferring to the
+/// wrapped criterion.
+///
+/// This guards against a lucky-but-inaccurate early exit on noisy functions.
+/// The number of calls is tracked internally, so a fresh instance should be
+/// used for each solve (or `reset()` called in between).
+pub struct MinIterations<'a, C: 'a> {
+    inner: &'a C,
+    min_iter: usize,
+    calls: Cell<usize>,
+}
+
+impl<'a, C: 'a> MinIterations<'a, C> {
+    pub fn new(inner: &'a C, min_iter: usize) -> MinIterations<'a, C> {
+        MinIterations {
+            inner,
+            min_iter,
+            calls: Cell::new(0),
+        }
+    }
+
+    /// Number of times convergence has been checked.
+    pub fn calls(&self) -> usize {
+        self.calls.get()
+    }
+
+    /// Restart the iteration count so the criterion can be reused.
+    pub fn reset(&self) {
+        self.calls.set(0);
+    }
+}
+
+impl<'a, T, C: IsConverged<T>> IsConverged<T> for MinIterations<'a, C> {
+    fn is_converged(&self, x_pre: T, x_cur: T, f_cur: T) -> bool {
+        let calls = self.calls.get() + 1;
+        self.calls.set(calls);
+        calls >= self.min_iter && self.inner.is_converged(x_pre, x_cur, f_cur)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::f64;
+    use wrap::RealFnAndFirst;
 
     #[test]
     fn test_delta_x_convergence() {
         // too far apart
         let c = DeltaX::new(1e-9);
         let x_0 = 10.2;
-        assert_eq!(false, c.is_converged(x_0, x_0 + 1e-8, 10.0));
+        assert!(!c.is_converged(x_0, x_0 + 1e-8, 10.0));
 
         // just right
-        assert_eq!(true, c.is_converged(x_0, x_0 + 5e-10, 10.0));
+        assert!(c.is_converged(x_0, x_0 + 5e-10, 10.0));
     }
 
     #[test]
@@ -138,13 +654,25 @@ mod tests {
         let _ = DeltaX::new(f64::NAN);
     }
 
+    #[test]
+    fn test_recording_delta_x() {
+        let c = RecordingDeltaX::new(1e-9);
+        assert_eq!(None, c.last_delta());
+
+        assert!(!c.is_converged(1.0, 1.5, 0.0));
+        assert_eq!(Some(0.5), c.last_delta());
+
+        assert!(c.is_converged(2.0, 2.0, 0.0));
+        assert_eq!(Some(0.0), c.last_delta());
+    }
+
     #[test]
     fn test_fn_residual_convergence() {
         let c = FnResidual::new(1e-3);
-        assert_eq!(false, c.is_converged(0.0, 1e-10, 2e-3));
-        assert_eq!(false, c.is_converged(0.0, 1e-10, -2e-3));
-        assert_eq!(true, c.is_converged(0.0, 1e-10, 9e-4));
-        assert_eq!(true, c.is_converged(0.0, 1e-10, -9e-4));
+        assert!(!c.is_converged(0.0, 1e-10, 2e-3));
+        assert!(!c.is_converged(0.0, 1e-10, -2e-3));
+        assert!(c.is_converged(0.0, 1e-10, 9e-4));
+        assert!(c.is_converged(0.0, 1e-10, -9e-4));
     }
 
     #[test]
@@ -159,6 +687,102 @@ mod tests {
         let _ = FnResidual::new(f64::NAN);
     }
 
+    #[test]
+    fn test_adaptive_residual_convergence() {
+        let c = AdaptiveResidual::new(1e-6, 2);
+
+        // baseline is the largest residual over the warmup
+        assert!(!c.is_converged(0.0, 0.0, 10.0));
+        assert!(!c.is_converged(0.0, 0.0, -100.0));
+        assert_eq!(100.0, c.baseline());
+
+        // after warmup the baseline is frozen
+        assert!(!c.is_converged(0.0, 0.0, 1000.0));
+        assert_eq!(100.0, c.baseline());
+        assert!(!c.is_converged(0.0, 0.0, 2e-4));
+        assert!(c.is_converged(0.0, 0.0, 5e-5));
+
+        c.reset();
+        assert_eq!(0.0, c.baseline());
+        assert!(c.is_converged(0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_adaptive_residual_no_warmup() {
+        let _ = AdaptiveResidual::new(1e-6, 0);
+    }
+
+    #[test]
+    fn test_significant_digits_convergence() {
+        let c = SignificantDigits::new(6);
+
+        // scale invariant
+        for scale in [1e-12, 1.0, 1e12].iter() {
+            assert!(!c.is_converged(1.23456 * scale, 1.23466 * scale, 0.0));
+            assert!(c.is_converged(1.2345678 * scale, 1.2345679 * scale, 0.0));
+        }
+
+        // sign flips never agree
+        assert!(!c.is_converged(-1e-9, 1e-9, 0.0));
+
+        // landing exactly on zero
+        assert!(c.is_converged(0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_significant_digits_zero() {
+        let _ = SignificantDigits::new(0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_significant_digits_too_many() {
+        let _ = SignificantDigits::new(16);
+    }
+
+    #[test]
+    fn test_newton_step_convergence() {
+        let in_f = |x: f64| x * x - 4.0;
+        let in_df = |x: f64| 2.0 * x;
+        let f = RealFnAndFirst::new(&in_f, &in_df);
+        let c = NewtonStep::new(&f, 1e-6);
+
+        // step of 0.25 and 2.5e-7
+        assert!(!c.is_converged(0.0, 3.0, in_f(3.0)));
+        assert!(c.is_converged(0.0, 2.0000005, in_f(2.0000005)));
+
+        // flat or overflowing derivative
+        assert!(!c.is_converged(0.0, 0.0, 0.0));
+        let in_df = |_| f64::INFINITY;
+        let f = RealFnAndFirst::new(&in_f, &in_df);
+        let c = NewtonStep::new(&f, 1e-6);
+        assert!(!c.is_converged(0.0, 3.0, 5.0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_newton_step_epsabs_zero() {
+        let in_f = |x: f64| x;
+        let f = RealFnAndFirst::new(&in_f, &in_f);
+        let _ = NewtonStep::new(&f, 0.0);
+    }
+
+    #[test]
+    fn test_bracket_and_residual_convergence() {
+        let c = BracketAndResidual::new(1e-6, 1e-9);
+        assert!(!c.is_converged(1.0, 1.1, 1e-12));
+        assert!(!c.is_converged(1.0, 1.0 + 1e-7, 1e-8));
+        assert!(c.is_converged(1.0, 1.0 + 1e-7, 1e-10));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bracket_and_residual_width_zero() {
+        let _ = BracketAndResidual::new(0.0, 1e-9);
+    }
+
     #[test]
     fn test_dual_convergence() {
         let c1 = FnResidual::new(1e-4);
@@ -167,16 +791,190 @@ mod tests {
 
         // neither c1 nor c2
         let x_0 = -3.7;
-        assert_eq!(false, c.is_converged(x_0, x_0 + 1.0, 113456.987));
+        assert!(!c.is_converged(x_0, x_0 + 1.0, 113456.987));
 
         // c1 but not c2
-        assert_eq!(false, c.is_converged(x_0, x_0 + 5e-10, 113456.987));
+        assert!(!c.is_converged(x_0, x_0 + 5e-10, 113456.987));
 
         // c2 but not c1
-        assert_eq!(false, c.is_converged(x_0, x_0 + 1.0, 0.00008));
+        assert!(!c.is_converged(x_0, x_0 + 1.0, 0.00008));
 
         // both c1 and c2
-        assert_eq!(true, c.is_converged(0.0, 1e-10, 0.00008));
+        assert!(c.is_converged(0.0, 1e-10, 0.00008));
+    }
+
+    #[test]
+    fn test_either_convergence() {
+        let c1 = FnResidual::new(1e-4);
+        let c2 = DeltaX::new(1e-9);
+        let c = EitherCriteria::new(&c1, &c2);
+
+        // neither c1 nor c2
+        assert!(!c.is_converged(-3.7, -2.7, 113456.987));
+
+        // only one of them
+        assert!(c.is_converged(-3.7, -3.7 + 5e-10, 113456.987));
+        assert!(c.is_converged(-3.7, -2.7, 0.00008));
+    }
+
+    #[test]
+    fn test_target_crossed_convergence() {
+        let c = TargetCrossed::new(2.0);
+
+        // both sides, either direction
+        assert!(!c.is_converged(0.0, 1.9, 0.0));
+        assert!(c.is_converged(1.9, 2.1, 1e9));
+        assert!(!c.is_converged(3.0, 2.1, 0.0));
+        assert!(c.is_converged(2.1, 1.9, 1e9));
+
+        // landing on target exactly
+        assert!(c.is_converged(1.0, 2.0, 1e9));
+        assert!(c.is_converged(3.0, 2.0, 1e9));
+    }
+
+    #[test]
+    fn test_stagnation_convergence() {
+        let c = Stagnation::new(3, 1e-6);
+
+        // window not yet full
+        assert!(!c.is_converged(0.0, 1.0, 0.0));
+        assert!(!c.is_converged(1.0, 1.0, 0.0));
+
+        // full window, but spread too wide
+        assert!(!c.is_converged(1.0, 1.0 + 1e-5, 0.0));
+        assert!(!c.stagnated());
+
+        // wide value rolls out of the window
+        assert!(!c.is_converged(1.0, 1.0 + 1e-7, 0.0));
+        assert!(!c.is_converged(1.0, 1.0 + 1e-7, 0.0));
+        assert!(c.is_converged(1.0, 1.0 + 1e-7, 0.0));
+        assert!(c.stagnated());
+
+        // reset empties the window
+        c.reset();
+        assert!(!c.stagnated());
+        assert!(!c.is_converged(1.0, 1.0, 0.0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_stagnation_window_one() {
+        let _ = Stagnation::new(1, 1e-6);
     }
 
+    #[test]
+    fn test_boxed_convergence() {
+        let c = BoxedCriteria(Box::new(FnResidual::new(1e-3)));
+        assert!(!c.is_converged(0.0, 1e-10, 2e-3));
+        assert!(c.is_converged(0.0, 1e-10, 9e-4));
+    }
+
+    #[test]
+    fn test_multi_convergence() {
+        let c = MultiCriteria::new(vec![
+            Box::new(FnResidual::new(1e-4)),
+            Box::new(DeltaX::new(1e-9)),
+            Box::new(BoxedCriteria(Box::new(FnResidual::new(1e-5)))),
+        ]);
+
+        let x_0 = -3.7;
+        assert!(!c.is_converged(x_0, x_0 + 1.0, 0.00008));
+        assert!(!c.is_converged(x_0, x_0 + 5e-10, 0.00008));
+        assert!(c.is_converged(x_0, x_0 + 5e-10, 0.000008));
+
+        // vacuously true
+        let c = MultiCriteria::new(Vec::new());
+        assert!(c.is_converged(x_0, x_0 + 1.0, 113456.987));
+    }
+
+    /// Converges once x_cur is at or above a threshold.
+    struct AtLeast(f64);
+
+    impl IsConverged for AtLeast {
+        fn is_converged(&self, _x_pre: f64, x_cur: f64, _f_cur: f64) -> bool {
+            x_cur >= self.0
+        }
+    }
+
+    #[test]
+    fn test_all_convergence() {
+        let c1 = FnResidual::new(1e-4);
+        let c2 = DeltaX::new(1e-9);
+        let c3 = AtLeast(0.0);
+        let c = AllCriteria::new(vec![&c1, &c2, &c3]);
+
+        // missing one of the three
+        assert!(!c.is_converged(1.0, 2.0, 0.00008));
+        assert!(!c.is_converged(1.0, 1.0 + 5e-10, 0.5));
+        assert!(!c.is_converged(-1.0, -1.0 + 5e-10, 0.00008));
+
+        // all three
+        assert!(c.is_converged(1.0, 1.0 + 5e-10, 0.00008));
+
+        // vacuously true
+        let c = AllCriteria::new(Vec::new());
+        assert!(c.is_converged(1.0, 2.0, 113456.987));
+    }
+
+    #[test]
+    fn test_any_convergence() {
+        let c1 = FnResidual::new(1e-4);
+        let c2 = DeltaX::new(1e-9);
+        let c3 = AtLeast(0.0);
+        let c = AnyCriteria::new(vec![&c1, &c2, &c3]);
+
+        // none of the three
+        assert!(!c.is_converged(-2.0, -1.0, 0.5));
+
+        // each one alone
+        assert!(c.is_converged(-2.0, -1.0, 0.00008));
+        assert!(c.is_converged(-1.0, -1.0 + 5e-10, 0.5));
+        assert!(c.is_converged(-2.0, 1.0, 0.5));
+
+        // never converges
+        let c = AnyCriteria::new(Vec::new());
+        assert!(!c.is_converged(1.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn test_any_evaluates_all() {
+        let inner = FnResidual::new(1e-3);
+        let m = MinIterations::new(&inner, 0);
+        let c1 = AtLeast(0.0);
+        let c = AnyCriteria::new(vec![&c1, &m]);
+
+        assert!(c.is_converged(0.0, 1.0, 0.5));
+        assert!(c.is_converged(0.0, 1.0, 0.5));
+        assert_eq!(2, m.calls());
+    }
+
+    #[test]
+    fn test_min_iterations_convergence() {
+        let inner = FnResidual::new(1e-3);
+        let c = MinIterations::new(&inner, 3);
+
+        // inner satisfied, but too early
+        assert!(!c.is_converged(0.0, 1e-10, 0.0));
+        assert!(!c.is_converged(0.0, 1e-10, 0.0));
+
+        // defers to inner once enough iterations elapsed
+        assert!(!c.is_converged(0.0, 1e-10, 2e-3));
+        assert!(c.is_converged(0.0, 1e-10, 0.0));
+        assert_eq!(4, c.calls());
+
+        // reset starts the count over
+        c.reset();
+        assert!(!c.is_converged(0.0, 1e-10, 0.0));
+    }
+
+    #[test]
+    fn test_min_iterations_dual() {
+        let c1 = DeltaX::new(1e-6);
+        let c2 = FnResidual::new(1e-9);
+        let dual = DualCriteria::new(&c1, &c2);
+        let c = MinIterations::new(&dual, 2);
+
+        assert!(!c.is_converged(0.1, 0.1 + 1e-7, 1e-12));
+        assert!(c.is_converged(0.1, 0.1 + 1e-7, 1e-12));
+    }
 }
@@ -2,7 +2,25 @@
 //!
 //!  This package aims to provide robust numerical methods suitable for production use.
 //!
+//!  The default `std` feature can be disabled for `no_std` targets.  The crate
+//!  then needs only `core` and `alloc`, with `libm` supplying the floating
+//!  point math.  `Deadline` and `CachingFn` are unavailable without `std`.
+//!
+//!  The optional `rayon` feature adds `bracket::find_brackets_parallel`.
+//!
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[macro_use]
+extern crate alloc;
+#[cfg(any(feature = "std", test))]
+extern crate core;
+extern crate num_complex;
+extern crate num_traits;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+
 pub mod bracket;
+pub mod checked;
 pub mod convergence;
 pub mod solver;
 pub mod wrap;
@@ -0,0 +1,67 @@
+//! Wall-clock budgets for solves.
+use std::time::{Duration, Instant};
+
+use super::RootError;
+
+/// Wall-clock budget for a solve, measured from construction.
+///
+/// Some user functions are slow enough that `max_iter` is a poor proxy for
+/// how long a solve takes.  Solvers accepting a Deadline check it once per
+/// iteration and give up with `RootError::Timeout` once it has passed.  The
+/// check happens between iterations, so a single slow evaluation of f can
+/// still overrun the budget.
+#[derive(Clone, Copy, Debug)]
+pub struct Deadline {
+    start: Instant,
+    budget: Duration,
+}
+
+impl Deadline {
+    /// Deadline expiring `budget` from now.
+    pub fn new(budget: Duration) -> Deadline {
+        Deadline {
+            start: Instant::now(),
+            budget,
+        }
+    }
+
+    /// Time since the deadline was created.
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// Check if the budget has been used up.
+    pub fn expired(&self) -> bool {
+        self.elapsed() >= self.budget
+    }
+
+    /// Returns `RootError::Timeout` holding `last_x` once expired.
+    pub(crate) fn check(&self, last_x: f64) -> Result<(), RootError> {
+        let elapsed = self.elapsed();
+        if elapsed >= self.budget {
+            return Err(RootError::Timeout { last_x, elapsed });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deadline_expired() {
+        let d = Deadline::new(Duration::from_secs(3600));
+        assert!(!d.expired());
+        assert!(d.check(1.0).is_ok());
+
+        let d = Deadline::new(Duration::from_secs(0));
+        assert!(d.expired());
+        match d.check(1.5) {
+            Err(RootError::Timeout { last_x, .. }) => assert_eq!(last_x, 1.5),
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+    }
+}
@@ -1,4 +1,6 @@
-use super::RootError;
+use num_traits::Float;
+
+use super::{check_sign_change, to_f64, RootError, RootResult};
 use bracket::{is_sign_change, Bounds};
 use convergence::IsConverged;
 use wrap::RealFnEval;
@@ -8,17 +10,60 @@ use wrap::RealFnEval;
 /// Allows for arbitrary iteration functions and converge criteria.  The user
 /// function 'f' is kept compatible with the iteration routine using trait
 /// bounds defined in 'wrap' module.
-pub fn iterative_root_find<F, I, C>(
+pub fn iterative_root_find<T, F, I, C>(
     f: &F,
     iterate: &I,
-    start: f64,
+    start: T,
     finish: &C,
     max_iter: usize,
-) -> Result<f64, RootError>
+) -> Result<T, RootError>
 where
-    F: RealFnEval,
-    I: Fn(&F, f64, f64) -> Result<f64, RootError>,
-    C: IsConverged,
+    T: Float,
+    F: RealFnEval<T>,
+    I: Fn(&F, T, T) -> Result<T, RootError>,
+    C: IsConverged<T>,
+{
+    iterative_root_find_verbose(f, iterate, start, finish, max_iter).map(|r| r.root)
+}
+
+/// Driver for iterative root finders reporting how convergence was reached.
+pub fn iterative_root_find_verbose<T, F, I, C>(
+    f: &F,
+    iterate: &I,
+    start: T,
+    finish: &C,
+    max_iter: usize,
+) -> Result<RootResult<T>, RootError>
+where
+    T: Float,
+    F: RealFnEval<T>,
+    I: Fn(&F, T, T) -> Result<T, RootError>,
+    C: IsConverged<T>,
+{
+    iterative_root_find_core(f, iterate, start, finish, max_iter, None)
+}
+
+/// Driver shared by the plain, verbose, trace, and traced entry points.
+///
+/// When `on_step` is supplied, it is called as `on_step(iteration, x_cur,
+/// f_cur)` after every iteration, before convergence is checked.  Iterations
+/// count from 1, matching `RootResult::iterations`.
+///
+/// After every evaluation `f.trapped()` is consulted, so a `TrapNaNFn` ends
+/// the solve with `RootError::NonFiniteEvaluation` right away.
+pub fn iterative_root_find_core<T, F, I, C>(
+    f: &F,
+    iterate: &I,
+    start: T,
+    finish: &C,
+    max_iter: usize,
+    mut on_step: Option<&mut dyn FnMut(usize, T, T)>,
+) -> Result<RootResult<T>, RootError>
+where
+    T: Float,
+    F: RealFnEval<T>,
+    I: Fn(&F, T, T) -> Result<T, RootError>,
+    C: IsConverged<T>,
 {
     assert!(start.is_finite());
 
@@ -26,36 +71,72 @@ where
     let mut x_cur = start;
 
     let mut f_pre = f.eval_f(x_pre);
+    check_trap(f)?;
     let mut f_cur;
 
     // stay inside maximum iteration count
-    for _ in 0..max_iter {
-        // invoke iteration method
-        x_cur = iterate(f, x_pre, f_pre)?;
+    for i in 0..max_iter {
+        // invoke iteration method, which may evaluate f and its derivatives
+        let step = iterate(f, x_pre, f_pre);
+        check_trap(f)?;
+        x_cur = step?;
         f_cur = f.eval_f(x_cur);
+        check_trap(f)?;
+        if let Some(ref mut cb) = on_step {
+            cb(i + 1, x_cur, f_cur);
+        }
 
         // check convergence
         if finish.is_converged(x_pre, x_cur, f_cur) {
-            return Ok(x_cur);
+            return Ok(RootResult {
+                root: x_cur,
+                iterations: i + 1,
+                final_step: (x_cur - x_pre).abs(),
+                residual: f_cur,
+                start,
+                net_displacement: x_cur - start,
+            });
         }
 
         x_pre = x_cur;
         f_pre = f_cur;
     }
-    return Err(RootError::IterationLimit { last_x: x_cur });
+    Err(RootError::IterationLimit {
+        last_x: to_f64(x_cur),
+    })
+}
+
+/// Report the first non-finite evaluation trapped by `f`, if any.
+fn check_trap<T, F>(f: &F) -> Result<(), RootError>
+where
+    F: RealFnEval<T>,
+{
+    match f.trapped() {
+        Some(x) => Err(RootError::NonFiniteEvaluation { x }),
+        None => Ok(()),
+    }
 }
 
-/// Safe solver hybidizes iterative method to ensure convergence.
+/// Bracket-guarded driver for iterative root finders.
 ///
-/// Do not use this!
+/// A step of `iterate` is accepted only if it lands inside the current
+/// bracket.  Otherwise, or if the step fails (e.g. zero derivative), the
+/// bracket is bisected instead.  Either way the bracket is updated by the sign
+/// of f at the new point, so it always holds a sign change.  A bisection is
+/// also forced whenever two iterations fail to halve the bracket, so the
+/// solve converges at least half as fast as plain bisection.
 ///
-/// The code is both messy and untrustworthy.  I had to tighten to convergence
-/// criteria to get expected results, for reasons I don't yet understand.
+/// The first `initial_bisections` iterations bisect unconditionally, shrinking
+/// a wide bracket before the iterative method is trusted with it.
 ///
+/// Convergence is checked as `finish.is_converged(x_pre, x_cur, f_cur)` after
+/// every step.  Landing exactly on a zero of f, or shrinking the bracket down
+/// to adjacent floats, also ends the solve.
 pub fn safe_iterative_root_find<F, I, C>(
     f: &F,
     iterate: &I,
     bounds: &Bounds,
+    initial_bisections: usize,
     finish: &C,
     max_iter: usize,
 ) -> Result<f64, RootError>
@@ -65,73 +146,61 @@ where
     C: IsConverged,
 {
     // solver always kept in window
-    let mut window = bounds.clone();
+    let mut window = *bounds;
     let mut f_a = f.eval_f(window.a);
-    assert!(is_sign_change(f_a, f.eval_f(window.b)));
+    check_sign_change(bounds, f_a, f.eval_f(window.b))?;
 
-    // bisect once to start in middle of bracket
-    let mut must_bisect = true;
+    let mut bisections_left = initial_bisections;
+    let mut force_bisect = false;
     let mut x_pre = window.a;
     let mut f_pre = f_a;
 
-    // previous window sizes
+    // window sizes after the previous two iterations
     let mut sz_pre = window.size();
     let mut sz_pre2 = window.size();
 
-    println!("\n== SOLVER");
     for _ in 0..max_iter {
-        let x_cur;
-        let f_cur;
-
-        if must_bisect {
-            println!("--> bisection");
-            x_cur = window.middle();
-            f_cur = f.eval_f(x_cur);
-
-            if is_sign_change(f_a, f_cur) {
-                window.b = x_cur;
-            } else {
-                window.a = x_cur;
-                f_a = f_cur;
-            }
-            must_bisect = false;
+        let step = if bisections_left > 0 || force_bisect {
+            None
         } else {
-            match iterate(f, x_pre, f_pre) {
-                Ok(x_new) if window.contains(x_new) => {
-                    println!("--> iteration");
-                    x_cur = x_new;
-                    f_cur = f.eval_f(x_cur);
-
-                    if is_sign_change(f_a, f_cur) {
-                        window.b = x_cur;
-                    } else {
-                        window.a = x_cur;
-                        f_a = f_cur;
-                    }
-                }
-                _ => {
-                    println!("--> failed iteration");
-                    // out of bounds or other error (e.g. zero derivative)
-                    must_bisect = true;
-                    continue;
-                }
+            iterate(f, x_pre, f_pre)
+                .ok()
+                .filter(|&x_new| window.contains(x_new))
+        };
+        let x_cur = match step {
+            Some(x_new) => x_new,
+            None => {
+                bisections_left = bisections_left.saturating_sub(1);
+                force_bisect = false;
+                window.middle()
             }
+        };
+
+        let f_cur = f.eval_f(x_cur);
+        if f_cur.is_nan() {
+            return Err(RootError::NonFiniteEvaluation { x: x_cur });
+        }
+        if f_cur == 0.0 {
+            return Ok(x_cur);
+        }
+
+        if is_sign_change(f_a, f_cur) {
+            window.b = x_cur;
+        } else {
+            window.a = x_cur;
+            f_a = f_cur;
         }
 
-        println!("~~> [{}, {}]", window.a, window.b);
         // check convergence
-        //if window.size() < 1e-9 {
-        //    return Ok(window.middle());
-        //}
-        if finish.is_converged(x_pre, x_cur, f_cur) {
+        let mid = window.middle();
+        if finish.is_converged(x_pre, x_cur, f_cur) || mid == window.a || mid == window.b {
             return Ok(x_cur);
         }
 
         // insufficient progress
         let sz_cur = window.size();
-        if sz_pre2 * 0.5 < sz_cur {
-            println!("--> SHRINK YOU APE!");
-            must_bisect = true;
+        if sz_cur > 0.5 * sz_pre2 {
+            force_bisect = true;
         }
 
         sz_pre2 = sz_pre;
@@ -6,6 +6,12 @@
 //! Custom convergence criteria can be supplied.  Canned ones exist in the
 //! `convergence` module.
 //!
+//! The scalar solvers do not allocate unless documented otherwise.  Calling
+//! Newton-Raphson, Halley's method, bisection, or false position in a hot loop
+//! costs no heap traffic.  Functions returning a `Vec`, such as the `_trace`
+//! variants and `basin_map`, allocate their result.  For traces, a reusable
+//! `SolverScratch` avoids that.  The `system` solvers allocate per iteration.
+//!
 //! # Examples
 //! Using Newton-Raphson:
 //!
@@ -34,6 +40,7 @@
 //!
 //! ```
 //! use rootfind::bracket::Bounds;
+//! use rootfind::convergence::DeltaX;
 //! use rootfind::solver::{bisection, false_position_illinios};
 //! use rootfind::wrap::RealFn;
 //!
@@ -42,8 +49,8 @@
 //! let f = RealFn::new(&in_f);
 //!
 //! // invoke bisection
-//! let max_iterations = 20;
-//! let root = bisection(&f, &Bounds::new(2.0, 3.0), 100).expect("root");
+//! let finish = DeltaX::new(1e-9);
+//! let root = bisection(&f, &Bounds::new(2.0, 3.0), &finish, 100).expect("root");
 //! assert!((root-2.41421356237).abs() < 1e-9);
 //!
 //! // invoke false position
@@ -51,12 +58,28 @@
 //! let root = false_position_illinios(&f, &Bounds::new(2.0, 3.0), 100).expect("root");
 //! assert!((root-2.41421356237).abs() < 1e-9);
 //! ```
-use std::f64;
-use bracket::{is_sign_change, Bounds};
-use wrap::{RealD2fEval, RealDfEval, RealFnEval};
-use convergence::IsConverged;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use bracket::{is_sign_change, is_sign_change_eps, Bounds, BracketGenerator, Crossing};
+use convergence::{ClosureCriteria, DeltaX, DualCriteria, IsConverged, NewtonStep};
+use core::cell::Cell;
+use core::f64;
+use core::time::Duration;
+use num_complex::Complex;
+use num_traits::Float;
+use wrap::{
+    ComplexDfEval, ComplexFnEval, Polynomial, RealD2fEval, RealD3fEval, RealDfEval, RealFn,
+    RealFnAndFirstNumericalSecond, RealFnEval,
+};
 
+#[cfg(feature = "std")]
+mod deadline;
 mod driver;
+pub mod system;
+pub mod system2;
+
+#[cfg(feature = "std")]
+pub use self::deadline::Deadline;
 
 /// Root finding error conditions.
 ///
@@ -73,475 +96,3427 @@ pub enum RootError {
 
     /// Iteration limit was reached.
     IterationLimit { last_x: f64 },
+
+    /// A history-collecting solver failed.  The `(x, f(x))` pairs visited
+    /// before the failure are kept alongside the underlying error.
+    NotConverged {
+        history: Vec<(f64, f64)>,
+        cause: Box<RootError>,
+    },
+
+    /// The iteration moved steadily away from a solution rather than toward
+    /// one, e.g. a fixed-point map that is not a contraction.
+    Diverged { last_x: f64 },
+
+    /// Backtracking along the search direction never reduced the residual
+    /// |f|, so no acceptable step was found from `x_cur`.
+    LineSearchFailed { x_cur: f64 },
+
+    /// The solver claimed convergence but the residual |f(x)| at the claimed
+    /// root remained too large.
+    SuspectConvergence { x: f64, residual: f64 },
+
+    /// The bracket endpoints do not straddle a sign change.
+    NoSignChange { a: f64, b: f64 },
+
+    /// A bracketing solver closed in on a sign change where |f| blew up rather
+    /// than vanished, i.e. a pole like 1/x at zero rather than a root.
+    ConvergedToSingularity { x: f64, residual: f64 },
+
+    /// The bracket has zero width, [x, x], so cannot hold a sign change.
+    DegenerateBracket { x: f64 },
+
+    /// The function evaluated to NaN or infinity where a finite value was needed.
+    NonFiniteEvaluation { x: f64 },
+
+    /// The bracket scan saw exactly the same f(x) across [a, b] and gave up.
+    FlatFunction { a: f64, b: f64 },
+
+    /// A parameter was outside its valid range (e.g. a non-finite start or a
+    /// non-positive tolerance).
+    InvalidParameter { name: &'static str, value: f64 },
+
+    /// The 2x2 Jacobian was singular, so the Newton step is undefined.
+    ZeroDeterminant { x_cur: [f64; 2] },
+
+    /// The Jacobian was numerically singular, so the Newton step is undefined.
+    SingularJacobian { x_cur: Vec<f64> },
+
+    /// Iteration limit was reached by a solver for a system of equations.
+    SystemIterationLimit { last_x: Vec<f64> },
+
+    /// The solve ran past its `Deadline`.
+    Timeout { last_x: f64, elapsed: Duration },
 }
 
-/// Root finding using Newton-Raphson.
+/// Detailed outcome of a successful root finding run.
 ///
-/// The `start` indicates the initial guess.  For guesses sufficiently close to
-/// the root this algorithm has quadratic convergence.
+/// Returned by the `_verbose` solver variants, which are otherwise identical
+/// to the plain ones.  Useful for benchmarks or retry logic needing to know
+/// how much work was done and how close f(x) got to zero.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RootResult<T = f64> {
+    /// The converged root.
+    pub root: T,
+
+    /// Number of iterations taken.
+    pub iterations: usize,
+
+    /// Size of the final step, |x_pre - x_cur|, when convergence was declared.
+    pub final_step: T,
+
+    /// Residual at the root, f(root).  This is the value the convergence
+    /// criteria saw, so costs no extra evaluation.
+    pub residual: T,
+
+    /// The initial guess.
+    pub start: T,
+
+    /// Net distance travelled, root - start.  The path taken may have been
+    /// far longer.
+    pub net_displacement: T,
+}
+
+impl RootResult {
+    /// Check whether the method converged somewhere other than the expected
+    /// root, typically the root nearest the start.
+    ///
+    /// Methods like Newton-Raphson can overshoot from a poor start and settle
+    /// on a distant root.  Roots within sqrt(ε) * max(|expected_nearest|, 1)
+    /// count as the same.
+    pub fn jumped_basin(&self, expected_nearest: f64) -> bool {
+        let tol = f64::EPSILON.sqrt() * expected_nearest.abs().max(1.0);
+        (self.root - expected_nearest).abs() > tol
+    }
+}
+
+/// Find all roots of f in [a, b] in one call.
 ///
-/// This algorithm requires the first derivative of f(x).
+/// This is the no-fuss entry point: the range is scanned with a
+/// `BracketGenerator` using a thousandth of its width as the window size, and
+/// each bracket found is polished by bisection until narrower than `tol`.  No
+/// wrapping of f is needed.  The roots are returned in increasing order.
 ///
-/// * If the second derivative is also available, consider Halley's method.
-/// * If analytically computed derivatives are not available, consider Brent-Decker.
+/// The usual caveats of bracketing apply.  Roots closer together than the
+/// window size may be missed, as are roots of even multiplicity.  Brackets on
+/// which f evaluates to NaN are skipped.  When more control is needed, use
+/// `BracketGenerator` and the individual solvers directly.
 ///
-/// A fascinating history of how the algorithm developed, including the
-/// contributions of Newton, Raphson, and Simpson can be found in:
+/// # Examples
+/// ```
+/// use rootfind::solver::find_roots;
 ///
-/// *Ypma, T. J. (1995). Historical development of the Newton–Raphson method.
-/// SIAM review, 37(4), 531-551.*
+/// let roots = find_roots(|x: f64| x.sin(), -0.1, 6.3, 1e-9);
+///
+/// // 0, pi, 2pi
+/// assert_eq!(roots.len(), 3);
+/// assert!((roots[1] - std::f64::consts::PI).abs() < 1e-9);
+/// ```
+pub fn find_roots<F>(f: F, a: f64, b: f64, tol: f64) -> Vec<f64>
+where
+    F: Fn(f64) -> f64,
+{
+    assert!(tol > 0.0);
+    try_find_roots(&f, &Bounds::new(a, b), tol).expect("f(x) evaluated to NaN")
+}
+
+/// Sweep behind `find_roots`, reporting NaN function values as errors.
+pub(crate) fn try_find_roots<F>(f: &F, bounds: &Bounds, tol: f64) -> Result<Vec<f64>, RootError>
+where
+    F: Fn(f64) -> f64,
+{
+    let window_size = bounds.size() / 1000.0;
+    if window_size == 0.0 {
+        return Ok(Vec::new());
+    }
+
+    let f = RealFn::new(f);
+    let finish = DeltaX::new(tol);
+    let mut brackets = BracketGenerator::new(&f, *bounds, window_size);
+    let mut roots = Vec::new();
+    while let Some(bracket) = brackets.try_next()? {
+        // tolerances below the float spacing never converge, so take the
+        // best available
+        let max_iter = bisection_iterations_for(&bracket, tol) + 1;
+        match bisection(&f, &bracket, &finish, max_iter) {
+            Ok(root) => roots.push(root),
+            Err(RootError::IterationLimit { last_x }) => roots.push(last_x),
+            Err(_) => {}
+        }
+    }
+    Ok(roots)
+}
+
+/// Bracketing solver used to polish each bracket in `find_all_roots`.
+///
+/// Only bisection takes convergence criteria.  Brent and Illinois stop at
+/// their built-in tolerance of about 1e-9.
+#[derive(Clone, Copy)]
+pub enum BracketMethod<'a> {
+    /// `bisection` with the given criteria.
+    Bisection(&'a dyn IsConverged),
+    /// `brent_dekker`.
+    Brent,
+    /// `false_position_illinios`.
+    Illinois,
+}
+
+/// Find all roots of f within `bounds` using the chosen bracketing method.
 ///
-pub fn newton_raphson<F, C>(
+/// Sweeps `bounds` with a `BracketGenerator` of the given `window_size` and
+/// solves each bracket found with `method`, allowing up to `max_iter`
+/// iterations per bracket.  Brackets where the solver fails or yields a
+/// non-finite root are skipped.  The roots are returned in increasing order.
+///
+/// The caveats of `find_roots` apply: roots closer together than the window
+/// size may be missed, as are roots of even multiplicity.
+pub fn find_all_roots<F>(
     f: &F,
-    start: f64,
-    finish: &C,
+    bounds: &Bounds,
+    window_size: f64,
+    method: BracketMethod,
     max_iter: usize,
-) -> Result<f64, RootError>
+) -> Vec<f64>
 where
-    F: RealFnEval + RealDfEval,
-    C: IsConverged,
+    F: RealFnEval,
 {
-    driver::iterative_root_find(f, &nr_step, start, finish, max_iter)
+    try_find_all_roots(f, bounds, window_size, method, max_iter).expect("f(x) evaluated to NaN")
 }
 
-/// Evaluate a single iteration for the Newton-Raphson method.  Returns x_new on
-/// success.
-fn nr_step<F>(f: &F, x_cur: f64, f_cur: f64) -> Result<f64, RootError>
+/// Sweep behind `find_all_roots`, reporting NaN function values as errors.
+pub(crate) fn try_find_all_roots<F>(
+    f: &F,
+    bounds: &Bounds,
+    window_size: f64,
+    method: BracketMethod,
+    max_iter: usize,
+) -> Result<Vec<f64>, RootError>
 where
-    F: RealDfEval,
+    F: RealFnEval,
 {
-    let denom = f.eval_df(x_cur);
-    if denom == 0.0 {
-        return Err(RootError::ZeroDerivative { x_cur });
-    }
-    let x_new = x_cur - f_cur / denom;
-    if !x_new.is_finite() {
-        return Err(RootError::IteratedToNaN { x_new });
+    let mut brackets = BracketGenerator::new(f, *bounds, window_size);
+    let mut roots = Vec::new();
+    while let Some(bracket) = brackets.try_next()? {
+        let result = match method {
+            BracketMethod::Bisection(finish) => {
+                let finish = ClosureCriteria::new(|x_pre: f64, x_cur: f64, f_cur: f64| {
+                    finish.is_converged(x_pre, x_cur, f_cur)
+                });
+                bisection(f, &bracket, &finish, max_iter)
+            }
+            BracketMethod::Brent => brent_dekker(f, &bracket, max_iter),
+            BracketMethod::Illinois => false_position_illinios(f, &bracket, max_iter),
+        };
+        if let Some(root) = result.ok().filter(|x| x.is_finite()) {
+            roots.push(root);
+        }
     }
-    Ok(x_new)
+    roots.sort_by(|a, b| a.total_cmp(b));
+    Ok(roots)
 }
 
-/// Root finding using Halley's method.
+/// Root finding using Newton-Raphson.
 ///
 /// The `start` indicates the initial guess.  For guesses sufficiently close to
-/// the root this algorithm has cubic convergence.
+/// the root this algorithm has quadratic convergence.
 ///
-/// This algorithm requires both the first and second derivatives of f(x).
+/// This algorithm requires the first derivative of f(x).
 ///
-/// * If only the first derivative is available, consider Newton-Raphson.
-/// * If analytically computed derivatives are not available, consider Brent-Decker.
+/// * If the second derivative is also available, consider Halley's method.
+/// * If analytically computed derivatives are not available, consider Brent-Dekker.
 ///
-/// A good overview of the derivation, history, and geometric interpretation of
-/// Halley's method is in:
+/// A fascinating history of how the algorithm developed, including the
+/// contributions of Newton, Raphson, and Simpson can be found in:
 ///
-/// *Scavo, T. R.; Thoo, J. B. (1995). "On the geometry of Halley's method".
-/// American Mathematical Monthly. 102 (5): 417–426.*
+/// *Ypma, T. J. (1995). Historical development of the Newton–Raphson method.
+/// SIAM review, 37(4), 531-551.*
 ///
-pub fn halley_method<F, C>(f: &F, start: f64, finish: &C, max_iter: usize) -> Result<f64, RootError>
+pub fn newton_raphson<T, F, C>(f: &F, start: T, finish: &C, max_iter: usize) -> Result<T, RootError>
 where
-    F: RealFnEval + RealDfEval + RealD2fEval,
-    C: IsConverged,
+    T: Float,
+    F: RealFnEval<T> + RealDfEval<T>,
+    C: IsConverged<T>,
 {
-    driver::iterative_root_find(f, &halley_step, start, finish, max_iter)
+    driver::iterative_root_find(f, &nr_step, start, finish, max_iter)
 }
 
-/// Evaluate a single iteration for Halley's method.  Returns x_new on success.
-fn halley_step<F>(f: &F, x_cur: f64, f_cur: f64) -> Result<f64, RootError>
+/// Fluent configuration for Newton-Raphson.
+///
+/// Equivalent to calling `newton_raphson` directly, but with defaults for
+/// anything left unset: 100 iterations and `DeltaX::new(1e-9)`.  The starting
+/// guess has no sensible default, so `solve` returns
+/// `RootError::InvalidParameter` if it was never given.
+///
+/// ```
+/// use rootfind::convergence::DeltaX;
+/// use rootfind::solver::{newton_raphson, NewtonBuilder};
+/// use rootfind::wrap::RealFnAndFirst;
+///
+/// let in_f = |x: f64| -x*x + 2.0*x + 1.0;
+/// let in_df = |x: f64| -2.0*x + 2.0;
+/// let f = RealFnAndFirst::new(&in_f, &in_df);
+/// let conv = DeltaX::new(1e-9);
+///
+/// let root = NewtonBuilder::new(&f).start(3.0).convergence(&conv).solve().expect("root");
+/// assert_eq!(root, newton_raphson(&f, 3.0, &conv, 100).expect("root"));
+///
+/// // root at x=1+sqrt(2)
+/// assert!((root-2.41421356237).abs() < 1e-9);
+/// ```
+pub struct NewtonBuilder<'a, F>
 where
-    F: RealDfEval + RealD2fEval,
+    F: 'a + RealFnEval + RealDfEval,
 {
-    let df_cur = f.eval_df(x_cur);
-    let d2f_cur = f.eval_d2f(x_cur);
-
-    if df_cur == 0.0 {
-        return Err(RootError::ZeroDerivative { x_cur });
-    }
-
-    let x_new = x_cur - (2.0 * f_cur * df_cur) / (2.0 * df_cur * df_cur - f_cur * d2f_cur);
-    if !x_new.is_finite() {
-        return Err(RootError::IteratedToNaN { x_new });
-    }
-    Ok(x_new)
+    f: &'a F,
+    start: Option<f64>,
+    max_iter: usize,
+    finish: Option<&'a dyn IsConverged>,
 }
 
-/// Root finding via Bisection Method.
-///
-/// It always converges given a valid starting bracket, but the speed of
-/// convergence is linear.
-pub fn bisection<F>(f: &F, bounds: &Bounds, max_iter: usize) -> Result<f64, RootError>
+impl<'a, F> NewtonBuilder<'a, F>
 where
-    F: RealFnEval,
+    F: 'a + RealFnEval + RealDfEval,
 {
-    let mut window: Bounds = (*bounds).clone();
-    let mut f_a = f.eval_f(window.a);
+    pub fn new(f: &'a F) -> NewtonBuilder<'a, F> {
+        NewtonBuilder {
+            f,
+            start: None,
+            max_iter: 100,
+            finish: None,
+        }
+    }
 
-    // ensure we started with valid bracket
-    assert!(is_sign_change(f_a, f.eval_f(window.b)));
+    /// Initial guess.
+    pub fn start(self, x: f64) -> NewtonBuilder<'a, F> {
+        NewtonBuilder {
+            start: Some(x),
+            ..self
+        }
+    }
 
-    for _ in 0..max_iter {
-        let mid = window.middle();
-        let f_mid = f.eval_f(mid);
+    /// Iteration limit, 100 by default.
+    pub fn max_iter(self, n: usize) -> NewtonBuilder<'a, F> {
+        NewtonBuilder {
+            max_iter: n,
+            ..self
+        }
+    }
 
-        if is_sign_change(f_a, f_mid) {
-            window.b = mid;
-        } else {
-            window.a = mid;
-            f_a = f_mid;
+    /// Convergence criteria, `DeltaX::new(1e-9)` by default.
+    pub fn convergence(self, finish: &'a dyn IsConverged) -> NewtonBuilder<'a, F> {
+        NewtonBuilder {
+            finish: Some(finish),
+            ..self
         }
+    }
 
-        // convergence criteria
-        if window.size() < 1e-9 {
-            return Ok(window.a);
+    /// Run Newton-Raphson with the configured settings.
+    pub fn solve(&self) -> Result<f64, RootError> {
+        let start = match self.start {
+            Some(x) => x,
+            None => {
+                return Err(RootError::InvalidParameter {
+                    name: "start",
+                    value: f64::NAN,
+                })
+            }
+        };
+        match self.finish {
+            Some(finish) => {
+                let finish = ClosureCriteria::new(|x_pre, x_cur, f_cur| {
+                    finish.is_converged(x_pre, x_cur, f_cur)
+                });
+                newton_raphson(self.f, start, &finish, self.max_iter)
+            }
+            None => newton_raphson(self.f, start, &DeltaX::new(1e-9), self.max_iter),
         }
     }
-    Err(RootError::IterationLimit {
-        last_x: window.middle(),
-    })
 }
 
-/// Illinois variant of Regula Falsi.
+/// Root finding with a user supplied iteration step.
 ///
-/// Detailed analysis of false position variants is in:
+/// This runs the same driver as Newton-Raphson and Halley's method, so custom
+/// methods get the convergence criteria, iteration limit, and error handling
+/// for free.  The step function is called as `iterate(f, x_cur, f_cur)` with
+/// f_cur = f(x_cur) already evaluated, and returns the next iterate x_new.
+/// The driver then evaluates f(x_new) and checks `finish` with
+/// `(x_cur, x_new, f(x_new))`.
 ///
-/// *Ford, J. A. (1995). Improved algorithms of illinois-type for the numerical
-/// solution of nonlinear equations. University of Essex, Department of Computer
-/// Science.*
+/// Steps should return a `RootError` rather than a non-finite x_new, e.g.
+/// `RootError::ZeroDerivative` or `RootError::IteratedToNaN`.  Any error ends
+/// the solve and is returned as is.
 ///
-pub fn false_position_illinios<F>(f: &F, bounds: &Bounds, max_iter: usize) -> Result<f64, RootError>
+/// # Examples
+/// A relaxed Newton step, taking only a fraction of the full Newton step:
+///
+/// ```
+/// use rootfind::convergence::DeltaX;
+/// use rootfind::solver::{custom_iterative, RootError};
+/// use rootfind::wrap::{RealDfEval, RealFnAndFirst};
+///
+/// let in_f = |x: f64| x * x - 2.0;
+/// let in_df = |x: f64| 2.0 * x;
+/// let f = RealFnAndFirst::new(&in_f, &in_df);
+///
+/// let relaxed_newton = |f: &RealFnAndFirst<_, _>, x_cur: f64, f_cur: f64| {
+///     let df_cur = f.eval_df(x_cur);
+///     if df_cur == 0.0 {
+///         return Err(RootError::ZeroDerivative { x_cur });
+///     }
+///     Ok(x_cur - 0.8 * f_cur / df_cur)
+/// };
+///
+/// let conv = DeltaX::new(1e-12);
+/// let root = custom_iterative(&f, &relaxed_newton, 1.0, &conv, 100).expect("root");
+/// assert!((root - 2.0f64.sqrt()).abs() < 1e-10);
+/// ```
+pub fn custom_iterative<F, I, C>(
+    f: &F,
+    iterate: &I,
+    start: f64,
+    finish: &C,
+    max_iter: usize,
+) -> Result<f64, RootError>
 where
     F: RealFnEval,
+    I: Fn(&F, f64, f64) -> Result<f64, RootError>,
+    C: IsConverged,
 {
-    let mut window: Bounds = (*bounds).clone();
-    let mut f_a = f.eval_f(window.a);
-    let mut f_b = f.eval_f(window.b);
-    assert!(f_a.is_finite());
-    assert!(f_b.is_finite());
-    assert!(is_sign_change(f_a, f_b));
+    driver::iterative_root_find(f, iterate, start, finish, max_iter)
+}
 
-    let mut bias: f64 = 0.0;
-    for _ in 0..max_iter {
-        let fga = if bias < 0.0 { f_a * -bias } else { f_a };
-        let fgb = if bias > 0.0 { f_b * bias } else { f_b };
+/// Newton-Raphson which restarts from a perturbed point at flat spots.
+///
+/// Plain Newton gives up with `RootError::ZeroDerivative` when it lands where
+/// df(x) = 0, and a derivative which is merely tiny sends the step off to
+/// infinity.  Often a small nudge escapes the flat spot.  This variant handles
+/// either case by moving x_cur a tenth of the previous Newton step further
+/// along and retrying from there.  The previous step points toward where the
+/// iteration was heading, so the nudge rarely moves away from the root.  Before
+/// any step has been taken the nudge is 1e-3 * max(|x_cur|, 1) instead.
+///
+/// At most `max_restarts` perturbations are made over the whole solve before
+/// the original error is returned.  Each costs one extra evaluation of f.
+pub fn newton_raphson_robust<F, C>(
+    f: &F,
+    start: f64,
+    finish: &C,
+    max_iter: usize,
+    max_restarts: usize,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval + RealDfEval,
+    C: IsConverged,
+{
+    let restarts = Cell::new(0);
+    let last_step = Cell::new(0.0);
 
-        // interpolant too flat, bisect instead
-        if (fga * fgb).abs() < 1e-12 {
-            let x_mid = window.middle();
-            let f_mid = f.eval_f(x_mid);
-            if is_sign_change(f_a, f_mid) {
-                window.b = x_mid;
-                f_b = f_mid;
-            } else {
-                window.a = x_mid;
-                f_a = f_mid;
+    let step = |f: &F, x_cur: f64, f_cur: f64| {
+        let mut x = x_cur;
+        let mut f_x = f_cur;
+        loop {
+            match nr_step(f, x, f_x) {
+                Err(RootError::ZeroDerivative { .. }) | Err(RootError::IteratedToNaN { .. })
+                    if restarts.get() < max_restarts =>
+                {
+                    restarts.set(restarts.get() + 1);
+                    x += if last_step.get() != 0.0 {
+                        0.1 * last_step.get()
+                    } else {
+                        1e-3 * x.abs().max(1.0)
+                    };
+                    f_x = f.eval_f(x);
+                }
+                Ok(x_new) => {
+                    last_step.set(x_new - x);
+                    return Ok(x_new);
+                }
+                err => return err,
             }
-            bias = 0.0;
         }
-        // false position step
-        else {
-            let x_new = (window.a * fgb - window.b * fga) / (fgb - fga);
-            let f_new = f.eval_f(x_new);
-            assert!(window.contains(x_new));
-            assert!(f_new.is_finite());
+    };
+    driver::iterative_root_find(f, &step, start, finish, max_iter)
+}
 
-            if is_sign_change(f_a, f_new) {
-                window.b = x_new;
-                f_b = f_new;
+/// Newton-Raphson with each step limited to at most `max_step` in magnitude.
+///
+/// Where df is small far from the root, the raw Newton step can fling the
+/// iterate arbitrarily far away.  Any step longer than `max_step` is cut back
+/// to exactly `max_step` in the same direction.  Near the root the steps are
+/// short, so the quadratic convergence of Newton-Raphson is unaffected.
+pub fn newton_raphson_clamped<F, C>(
+    f: &F,
+    start: f64,
+    max_step: f64,
+    finish: &C,
+    max_iter: usize,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval + RealDfEval,
+    C: IsConverged,
+{
+    assert!(max_step > 0.0);
 
-                if bias >= 0.0 {
-                    bias = -1.0;
-                } else {
-                    bias *= 0.5;
-                }
-            } else {
-                window.a = x_new;
-                f_a = f_new;
+    let step = |f: &F, x_cur: f64, f_cur: f64| {
+        let x_new = nr_step(f, x_cur, f_cur)?;
+        let delta = x_new - x_cur;
+        if delta.abs() > max_step {
+            Ok(x_cur + max_step.copysign(delta))
+        } else {
+            Ok(x_new)
+        }
+    };
+    driver::iterative_root_find(f, &step, start, finish, max_iter)
+}
 
-                if bias <= 0.0 {
-                    bias = 1.0;
-                } else {
-                    bias *= 0.5;
-                }
+/// Newton-Raphson globalized by a backtracking line search.
+///
+/// The full Newton step is accepted only if it reduces the residual, i.e.
+/// |f(x_new)| < |f(x_cur)|.  Otherwise the step is halved, up to
+/// `max_backtracks` times, until it does.  If no halving helps, the solve
+/// returns `RootError::LineSearchFailed`.  A step too small to change x is
+/// always accepted, so residual noise at the root cannot fail the search.
+///
+/// Each iteration costs an extra evaluation of f, plus one per halving.  See
+/// `armijo_backtrack` for a stricter sufficient decrease condition.
+pub fn newton_raphson_damped<F, C>(
+    f: &F,
+    start: f64,
+    finish: &C,
+    max_iter: usize,
+    max_backtracks: usize,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval + RealDfEval,
+    C: IsConverged,
+{
+    let step = |f: &F, x_cur: f64, f_cur: f64| {
+        let direction = nr_step(f, x_cur, f_cur)? - x_cur;
+        let mut t = 1.0;
+        for _ in 0..=max_backtracks {
+            let x_new = x_cur + t * direction;
+            if x_new == x_cur || f.eval_f(x_new).abs() < f_cur.abs() {
+                return Ok(x_new);
             }
+            t *= 0.5;
         }
+        Err(RootError::LineSearchFailed { x_cur })
+    };
+    driver::iterative_root_find(f, &step, start, finish, max_iter)
+}
 
-        // convergence criteria
-        if window.size() < 1e-9 {
-            return Ok(window.middle());
-        }
-    }
-    Err(RootError::IterationLimit {
-        last_x: window.middle(),
-    })
+/// Newton-Raphson giving up once `deadline` has passed.
+///
+/// The deadline is checked before each iteration.  On expiry this returns
+/// `RootError::Timeout` with the latest iterate.
+#[cfg(feature = "std")]
+pub fn newton_raphson_deadline<F, C>(
+    f: &F,
+    start: f64,
+    finish: &C,
+    max_iter: usize,
+    deadline: &Deadline,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval + RealDfEval,
+    C: IsConverged,
+{
+    let step = |f: &F, x_cur: f64, f_cur: f64| {
+        deadline.check(x_cur)?;
+        nr_step(f, x_cur, f_cur)
+    };
+    driver::iterative_root_find(f, &step, start, finish, max_iter)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use convergence::{DeltaX, DualCriteria, FnResidual};
-    use wrap::{RealFn, RealFnAndFirst, RealFnAndFirstSecond};
+/// Newton-Raphson returning a `RootResult` rather than just the root.
+pub fn newton_raphson_verbose<T, F, C>(
+    f: &F,
+    start: T,
+    finish: &C,
+    max_iter: usize,
+) -> Result<RootResult<T>, RootError>
+where
+    T: Float,
+    F: RealFnEval<T> + RealDfEval<T>,
+    C: IsConverged<T>,
+{
+    driver::iterative_root_find_verbose(f, &nr_step, start, finish, max_iter)
+}
 
-    struct RootTest {
-        name: String,
-        f: Box<Fn(f64) -> f64>,
-        df: Box<Fn(f64) -> f64>,
-        d2f: Box<Fn(f64) -> f64>,
-        roots: Vec<f64>,
-        guesses: Vec<f64>,
-        brackets: Vec<Bounds>,
-    }
+/// Newton-Raphson also returning every iterate, ending with the root.
+///
+/// The trace is reserved up front for `max_iter` entries, so no reallocation
+/// happens during the solve.
+pub fn newton_raphson_trace<F, C>(
+    f: &F,
+    start: f64,
+    finish: &C,
+    max_iter: usize,
+) -> Result<(f64, Vec<f64>), RootError>
+where
+    F: RealFnEval + RealDfEval,
+    C: IsConverged,
+{
+    let mut trace = Vec::with_capacity(max_iter);
+    let root = newton_raphson_traced(f, start, finish, max_iter, |_, x, _| trace.push(x))?;
+    Ok((root, trace))
+}
 
-    /// The Ford95 tests are from:
-    ///
-    /// *Ford, J. A. (1995). Improved algorithms of illinois-type for the numerical
-    /// solution of nonlinear equations. University of Essex, Department of Computer
-    /// Science.*
-    ///
-    fn make_root_tests_ford95() -> Vec<RootTest> {
-        vec![
-            RootTest {
-                name: "Ford95 Example One".to_owned(),
-                f: Box::new(|x| 4. * x.cos() - x.exp()),
-                df: Box::new(|x| -4. * x.sin() - x.exp()),
-                d2f: Box::new(|x| -4. * x.cos() - x.exp()),
-                roots: vec![0.90478821787302],
-                guesses: vec![5.0],
-                brackets: vec![Bounds::new(-1.5, 6.0)],
-            },
-            RootTest {
-                name: "Ford95 Example Three".to_owned(),
-                f: Box::new(|x| 2. * x * (-20f64).exp() + 1. - 2. * (-20. * x).exp()),
-                df: Box::new(|x| 40. * (-20. * x).exp() + 2. * (20f64).exp().recip()),
-                d2f: Box::new(|x| -800. * (-20. * x).exp()),
-                roots: vec![0.034657358821882],
-                guesses: vec![-2.5],
-                brackets: vec![Bounds::new(-1.0, 4.0)],
-            },
-            RootTest {
-                name: "Ford95 Example Four".to_owned(),
-                f: Box::new(|x| (x.recip() - 25.).exp() - 1.),
-                df: Box::new(|x| -(x.recip() - 25.).exp() * x.powi(-2)),
-                d2f: Box::new(|x| (x.recip() - 25.).exp() * (2. * x + 1.) * x.powi(-4)),
-                roots: vec![0.04],
-                guesses: vec![0.035],
-                brackets: vec![Bounds::new(0.02, 1.0)],
-            },
-            RootTest {
-                name: "Ford95 Example Six".to_owned(),
-                f: Box::new(|x| 10000000000. * x.powf(x.recip()) - 1.0),
-                df: Box::new(|x| -10000000000. * x.powf(x.recip() - 2.) * (x.ln() - 1.)),
-                d2f: Box::new(|x| {
-                    10000000000. * x.powf(x.recip() - 4.)
-                        * (-3. * x + x.ln().powi(2) + 2. * (x - 1.) * x.ln() + 1.)
-                }),
-                roots: vec![0.1],
-                guesses: vec![0.15],
-                brackets: vec![Bounds::new(0.05, 0.2)],
-            },
-            RootTest {
-                name: "Ford95 Example Seven".to_owned(),
-                f: Box::new(|x| x.powi(20) - 1.),
-                df: Box::new(|x| 20. * x.powi(19)),
-                d2f: Box::new(|x| 380. * x.powi(18)),
-                roots: vec![1.0],
-                guesses: vec![1.2],
-                brackets: vec![Bounds::new(-0.5, 5.0)],
-            },
-            RootTest {
-                name: "Ford95 Example Eight".to_owned(),
-                f: Box::new(|x| (21000. / x).exp() / (1.11 * 100000000000. * x * x) - 1.),
-                df: Box::new(|x| {
-                    (-1.8018e-11 * (21000. / x).exp() * (x + 10500.)) / (x * x * x * x)
-                }),
-                d2f: Box::new(|x| {
-                    (21000. / x).exp() * (5.40541e-11 * x * x + 1.13514e-6 * x + 0.00397297)
-                        / x.powi(6)
-                }),
-                roots: vec![551.77382493033],
-                guesses: vec![400.0],
-                brackets: vec![Bounds::new(350.0, 850.0)],
-            },
-            RootTest {
-                name: "Ford95 Example Nine".to_owned(),
-                f: Box::new(|x| x.recip() + x.ln() - 100.),
-                df: Box::new(|x| (x - 1.0) / (x * x)),
-                d2f: Box::new(|x| (2. - x) / (x * x * x)),
-                roots: vec![0.0095556044375379],
-                guesses: vec![0.01],
-                brackets: vec![Bounds::new(0.001, 100.0)],
-            },
-            RootTest {
-                name: "Ford95 Example Ten".to_owned(),
-                f: Box::new(|x| x.exp().exp() - (1.0f64).exp().exp()),
-                df: Box::new(|x| (x + x.exp()).exp()),
-                d2f: Box::new(|x| (x + x.exp()).exp() * (x.exp() + 1.)),
-                roots: vec![1.0],
-                guesses: vec![1.8],
-                brackets: vec![Bounds::new(0.5, 3.5)],
-            },
-            RootTest {
-                name: "Ford95 Example Eleven".to_owned(),
-                f: Box::new(|x| (0.01 / x).sin() - 0.01),
-                df: Box::new(|x| -0.01 * (0.01 / x).cos() / (x * x)),
-                d2f: Box::new(|x| {
-                    (0.02 * x * (0.01 / x).cos() - 0.0001 * (0.01 / x).sin()) / (x * x * x * x)
-                }),
-                roots: vec![0.99998333286109],
-                guesses: vec![0.55],
-                brackets: vec![Bounds::new(0.004, 200.0)],
-            },
-        ]
+/// Newton-Raphson also returning the `(x, f(x))` pair of every iterate.
+///
+/// Unlike `newton_raphson_trace`, the path is kept on failure too: any error
+/// is returned as `RootError::NotConverged` holding the history so far and
+/// the original error as its `cause`.
+pub fn newton_raphson_with_history<F, C>(
+    f: &F,
+    start: f64,
+    finish: &C,
+    max_iter: usize,
+) -> Result<(f64, Vec<(f64, f64)>), RootError>
+where
+    F: RealFnEval + RealDfEval,
+    C: IsConverged,
+{
+    let mut history = Vec::with_capacity(max_iter);
+    match newton_raphson_traced(f, start, finish, max_iter, |_, x, fx| history.push((x, fx))) {
+        Ok(root) => Ok((root, history)),
+        Err(e) => Err(RootError::NotConverged {
+            history,
+            cause: Box::new(e),
+        }),
     }
+}
 
-    /// The Costabile06 tests are from:
-    ///
-    /// *Costabile, F., Gualtieri, M. I., & Luceri, R. (2006). A modification of
-    /// Muller’s method. Calcolo, 43(1), 39-50.*
-    ///
-    /// They include cases which are particularly tough for pure forms of
-    /// Newton-Raphson and Halley's Method.  See the code comments on examples
-    /// twenty two through twenty eight.  These all come from:
-    ///
-    fn make_root_tests_costabile06() -> Vec<RootTest> {
-        vec![
-            RootTest {
-                name: "Costabile06 Example One".to_owned(),
-                f: Box::new(|x| x * x * x - 1.),
-                df: Box::new(|x| 3. * x * x),
-                d2f: Box::new(|x| 6. * x),
-                roots: vec![1.0],
-                guesses: vec![0.1],
-                brackets: vec![Bounds::new(0.1, 1.3)],
-            },
-            RootTest {
-                name: "Costabile06 Example Two".to_owned(),
-                f: Box::new(|x| {
-                    x * x * (x * x / 3. + 2.0f64.sqrt() * x.sin()) - 3.0f64.sqrt() / 18.
-                }),
-                df: Box::new(|x| {
-                    4. * x * x * x / 3. + 2.0f64.sqrt() * x * x * x.cos()
-                        + 2. * 2.0f64.sqrt() * x * x.sin()
-                }),
-                d2f: Box::new(|x| {
-                    4. * x * x - 2.0f64.sqrt() * x * x * x.sin() + 2. * 2.0f64.sqrt() * x.sin()
-                        + 4. * 2.0f64.sqrt() * x * x.cos()
-                }),
-                roots: vec![0.39942229171096819451],
-                guesses: vec![1.0],
-                brackets: vec![Bounds::new(0.1, 1.0)],
-            },
-            RootTest {
-                name: "Costabile06 Example Three".to_owned(),
-                f: Box::new(|x| 2. * x * (-10.0f64).exp() + 1. - 2. * (-10. * x).exp()),
-                df: Box::new(|x| 20. * (-10. * x).exp() + 2. * (-10.0f64).exp()),
-                d2f: Box::new(|x| -200. * (-10. * x).exp()),
-                roots: vec![0.069314088687023473303],
-                guesses: vec![0.0],
-                brackets: vec![Bounds::new(0.0, 1.0)],
-            },
-            RootTest {
-                name: "Costabile06 Example Four".to_owned(),
-                f: Box::new(|x| 2. * x * (-20.0f64).exp() + 1. - 2. * (-20. * x).exp()),
-                df: Box::new(|x| 40. * (-20. * x).exp() + 2. * (-20.0f64).exp()),
-                d2f: Box::new(|x| -800. * (-20. * x).exp()),
-                roots: vec![0.034657359020853851362],
-                guesses: vec![0.2],
-                brackets: vec![Bounds::new(0.0, 1.0)],
-            },
-            RootTest {
-                name: "Costabile06 Example Five".to_owned(),
-                f: Box::new(|x| (1. + (1. - 5.0f64).powi(2)) * x * x - (1. - 5. * x).powi(2)),
-                df: Box::new(|x| 10. - 16. * x),
-                d2f: Box::new(|_| -16.),
-                roots: vec![0.109611796797792],
-                guesses: vec![0.4],
-                brackets: vec![Bounds::new(0.0, 1.0)],
-            },
-            RootTest {
-                name: "Costabile06 Example Six".to_owned(),
-                f: Box::new(|x| (1. + (1. - 10.0f64).powi(2)) * x * x - (1. - 10. * x).powi(2)),
-                df: Box::new(|x| 20. - 36. * x),
-                d2f: Box::new(|_| -36.),
-                roots: vec![0.0524786034368102],
-                guesses: vec![0.4],
-                brackets: vec![Bounds::new(0.0, 1.0)],
-            },
-            RootTest {
-                name: "Costabile06 Example Seven".to_owned(),
-                f: Box::new(|x| (1. + (1. - 20.0f64).powi(2)) * x * x - (1. - 20. * x).powi(2)),
-                df: Box::new(|x| 40. - 76. * x),
-                d2f: Box::new(|_| -76.),
-                roots: vec![0.0256237476199882],
-                guesses: vec![0.4],
-                brackets: vec![Bounds::new(0.0, 1.0)],
-            },
-            RootTest {
-                name: "Costabile06 Example Eight".to_owned(),
-                f: Box::new(|x| x * x - (1. - x).powi(5)),
-                df: Box::new(|x| 5. * (1. - x).powi(4) + 2. * x),
-                d2f: Box::new(|x| 20. * (1. - x).powi(3) + 2.),
-                roots: vec![0.34595481584824201796],
-                guesses: vec![1.0],
-                brackets: vec![Bounds::new(0.0, 1.0)],
-            },
-            RootTest {
-                name: "Costabile06 Example Nine".to_owned(),
-                f: Box::new(|x| (1. + (1. - 5.0f64).powi(4)) * x - (1. - 5. * x).powi(4)),
-                df: Box::new(|x| 20. * (1. - 5. * x).powi(3) + 257.),
-                d2f: Box::new(|x| -300. * (1. - 5. * x).powi(2)),
-                roots: vec![0.00361710817890406],
-                guesses: vec![0.5],
-                brackets: vec![Bounds::new(0.0, 1.0)],
-            },
-            RootTest {
-                name: "Costabile06 Example Ten".to_owned(),
-                f: Box::new(|x| (1. + (1. - 10.0f64).powi(4)) * x - (1. - 10. * x).powi(4)),
-                df: Box::new(|x| 40. * (1. - 10. * x).powi(3) + 6562.),
-                d2f: Box::new(|x| -1200. * (1. - 10. * x).powi(2)),
-                roots: vec![0.000151471],
-                guesses: vec![0.5],
-                brackets: vec![Bounds::new(0.0, 1.0)],
-            },
-            RootTest {
-                name: "Costabile06 Example Eleven".to_owned(),
-                f: Box::new(|x| (1. + (1. - 20.0f64).powi(4)) * x - (1. - 20. * x).powi(4)),
-                df: Box::new(|x| 80. * (1. - 20. * x).powi(3) + 130322.),
-                d2f: Box::new(|x| -4800. * (1. - 20. * x).powi(2)),
-                roots: vec![7.6686e-6],
-                guesses: vec![0.5],
-                brackets: vec![Bounds::new(0.0, 1.0)],
-            },
-            RootTest {
-                name: "Costabile06 Example Twelve".to_owned(),
-                f: Box::new(|x| x * x + (x / 5.).sin() - 0.25),
-                df: Box::new(|x| 2. * x + (1. / 5.) * (x / 5.).cos()),
-                d2f: Box::new(|x| 2. - (1. / 25.) * (x / 5.).sin()),
-                roots: vec![0.40999201798913713162125838],
-                guesses: vec![0.0],
-                brackets: vec![Bounds::new(0.0, 1.0)],
-            },
-            RootTest {
-                name: "Costabile06 Example Thirteen".to_owned(),
-                f: Box::new(|x| x * x + (x / 10.).sin() - 0.25),
-                df: Box::new(|x| 2. * x + (1. / 10.) * (x / 10.).cos()),
-                d2f: Box::new(|x| 2. - (1. / 100.) * (x / 100.).sin()),
-                roots: vec![0.45250914557764122545806719],
-                guesses: vec![0.0],
-                brackets: vec![Bounds::new(0.0, 1.0)],
-            },
-            RootTest {
-                name: "Costabile06 Example Fourteen".to_owned(),
-                f: Box::new(|x| x * x + (x / 20.).sin() - 0.25),
-                df: Box::new(|x| 2. * x + (1. / 20.) * (x / 20.).cos()),
-                d2f: Box::new(|x| 2. - (1. / 400.) * (x / 20.).sin()),
-                roots: vec![0.47562684859606241311984234],
-                guesses: vec![0.0],
-                brackets: vec![Bounds::new(0.0, 1.0)],
-            },
-            RootTest {
+/// Newton-Raphson calling `on_step(iteration, x_cur, f_cur)` every iteration.
+///
+/// Useful for debugging slow convergence or collecting convergence histories
+/// for plotting.  Iterations count from 1.
+pub fn newton_raphson_traced<F, C, T>(
+    f: &F,
+    start: f64,
+    finish: &C,
+    max_iter: usize,
+    mut on_step: T,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval + RealDfEval,
+    C: IsConverged,
+    T: FnMut(usize, f64, f64),
+{
+    driver::iterative_root_find_core(f, &nr_step, start, finish, max_iter, Some(&mut on_step))
+        .map(|r| r.root)
+}
+
+/// Empirical order of convergence of a sequence of iterates.
+///
+/// With errors e_n = |x_n - root|, the order q satisfies e_{n+1} ≈ C e_n^q,
+/// giving the estimate
+///
+/// q ≈ log(e_{n+1} / e_n) / log(e_n / e_{n-1})
+///
+/// which unlike log(e_{n+1}) / log(e_n) does not depend on the constant C.
+/// The estimate from the last three usable errors is returned, since that is
+/// closest to the asymptotic regime.
+///
+/// Errors at or below the rounding noise around the root are trimmed, as is
+/// everything after them.  Returns NaN if fewer than three errors remain.
+/// Pairs naturally with `newton_raphson_trace`.
+pub fn estimate_convergence_order(iterates: &[f64], root: f64) -> f64 {
+    assert!(root.is_finite());
+    let noise = 4.0 * f64::EPSILON * root.abs().max(1.0);
+    let usable = iterates
+        .iter()
+        .position(|x| (x - root).abs() <= noise)
+        .unwrap_or(iterates.len());
+    if usable < 3 {
+        return f64::NAN;
+    }
+
+    let e = |i: usize| (iterates[i] - root).abs();
+    let n = usable - 1;
+    (e(n) / e(n - 1)).ln() / (e(n - 1) / e(n - 2)).ln()
+}
+
+/// Reusable working storage for solvers which need buffers.
+///
+/// Buffers are cleared but keep their capacity between solves, so reusing one
+/// scratch across many calls stops allocating once the buffers have grown
+/// large enough.
+#[derive(Clone, Debug, Default)]
+pub struct SolverScratch {
+    trace: Vec<f64>,
+}
+
+impl SolverScratch {
+    pub fn new() -> SolverScratch {
+        SolverScratch { trace: Vec::new() }
+    }
+
+    /// Iterates recorded by the most recent solve using this scratch.
+    pub fn trace(&self) -> &[f64] {
+        &self.trace
+    }
+}
+
+/// Newton-Raphson recording every iterate into a reusable scratch buffer.
+///
+/// Same as `newton_raphson_trace` but allocation-free once the scratch has
+/// capacity for `max_iter` iterates.  Read the trace back with
+/// `scratch.trace()`.
+pub fn newton_raphson_trace_scratch<F, C>(
+    f: &F,
+    start: f64,
+    finish: &C,
+    max_iter: usize,
+    scratch: &mut SolverScratch,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval + RealDfEval,
+    C: IsConverged,
+{
+    let trace = &mut scratch.trace;
+    trace.clear();
+    trace.reserve(max_iter);
+    newton_raphson_traced(f, start, finish, max_iter, |_, x, _| trace.push(x))
+}
+
+/// Newton-Raphson kept inside a bracket by falling back to bisection.
+///
+/// Newton steps which leave the bracket, or fail on a zero derivative, are
+/// replaced by a bisection step, and the bracket keeps its sign change
+/// throughout.  So unlike plain Newton-Raphson this converges from any valid
+/// bracket, while keeping Newton's fast convergence close to the root.
+///
+/// Bisects once before the first Newton step.  See
+/// `safe_newton_with_bisections` to change that.
+pub fn safe_newton<F, C>(
+    f: &F,
+    bounds: &Bounds,
+    finish: &C,
+    max_iter: usize,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval + RealDfEval,
+    C: IsConverged,
+{
+    safe_newton_with_bisections(f, bounds, 1, finish, max_iter)
+}
+
+/// Bracket-guarded Newton-Raphson with a configurable number of bisections up
+/// front.
+///
+/// A wide or poorly-conditioned bracket can place the first Newton steps far
+/// outside the region where Newton converges.  Bisecting a few times first
+/// shrinks the bracket so that Newton starts closer to the root.
+pub fn safe_newton_with_bisections<F, C>(
+    f: &F,
+    bounds: &Bounds,
+    initial_bisections: usize,
+    finish: &C,
+    max_iter: usize,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval + RealDfEval,
+    C: IsConverged,
+{
+    check_degenerate(bounds)?;
+    driver::safe_iterative_root_find(f, &nr_step, bounds, initial_bisections, finish, max_iter)
+}
+
+/// Bracket-guarded Newton-Raphson stopping once the root is known to within
+/// `x_tol` in x.
+///
+/// An x tolerance maps to a residual tolerance through the local slope: near a
+/// simple root, |x - root| ≈ |f(x)| / |df(x)|.  So the solve stops once
+/// |f(x)| < x_tol * |df(x)|, i.e. once the next Newton step would be shorter
+/// than `x_tol`.  This is the `NewtonStep` criteria.  Unlike a fixed residual
+/// tolerance it adapts to steep and shallow functions alike.
+///
+/// The estimate is first order, so it can be off where df varies strongly
+/// over a distance of x_tol.  Since Newton converges quadratically, the
+/// achieved error is typically well below `x_tol`.
+pub fn solve_to_x_tol<F>(
+    f: &F,
+    bounds: &Bounds,
+    x_tol: f64,
+    max_iter: usize,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval + RealDfEval,
+{
+    assert!(x_tol > 0.0);
+    assert!(x_tol.is_finite());
+    let finish = NewtonStep::new(f, x_tol);
+    safe_newton(f, bounds, &finish, max_iter)
+}
+
+/// Method which produced a root, as reported by `race`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Method {
+    Newton,
+    Bisection,
+}
+
+/// Race Newton-Raphson against bisection, returning whichever converges first.
+///
+/// Each loop takes one Newton step from `guess` and one bisection step on
+/// `bounds`.  Newton is usually far faster but may diverge; bisection always
+/// converges.  Once Newton fails (zero derivative, non-finite step, etc) it
+/// drops out of the race and bisection carries on alone.
+///
+/// Both methods share `finish`.  Newton checks it as usual, while bisection
+/// passes its previous and current midpoints and f at the current one.  At
+/// worst this costs twice the evaluations of bisection alone.
+pub fn race<F, C>(
+    f: &F,
+    bounds: &Bounds,
+    guess: f64,
+    finish: &C,
+    max_iter: usize,
+) -> Result<(f64, Method), RootError>
+where
+    F: RealFnEval + RealDfEval,
+    C: IsConverged,
+{
+    check_degenerate(bounds)?;
+    assert!(guess.is_finite());
+    let f_a = f.eval_f(bounds.a);
+    let f_b = f.eval_f(bounds.b);
+    check_sign_change(bounds, f_a, f_b)?;
+
+    let mut b = Bisector::new(bounds, f_a, f_b);
+    let mut newton = Some((guess, f.eval_f(guess)));
+    let mut mid_pre = bounds.a;
+
+    for _ in 0..max_iter {
+        if let Some((x_pre, f_pre)) = newton {
+            newton = match nr_step(f, x_pre, f_pre) {
+                Ok(x_cur) => {
+                    let f_cur = f.eval_f(x_cur);
+                    if finish.is_converged(x_pre, x_cur, f_cur) {
+                        return Ok((x_cur, Method::Newton));
+                    }
+                    Some((x_cur, f_cur)).filter(|_| f_cur.is_finite())
+                }
+                Err(_) => None,
+            };
+        }
+
+        let mid = b.window.middle();
+        let f_mid = b.split(f, mid)?;
+        if finish.is_converged(mid_pre, mid, f_mid) {
+            return Ok((mid, Method::Bisection));
+        }
+        mid_pre = mid;
+    }
+    Err(RootError::IterationLimit {
+        last_x: b.window.middle(),
+    })
+}
+
+/// Evaluate a single iteration for the Newton-Raphson method.  Returns x_new on
+/// success.
+fn nr_step<T, F>(f: &F, x_cur: T, f_cur: T) -> Result<T, RootError>
+where
+    T: Float,
+    F: RealDfEval<T>,
+{
+    let denom = f.eval_df(x_cur);
+    if denom == T::zero() {
+        return Err(RootError::ZeroDerivative {
+            x_cur: to_f64(x_cur),
+        });
+    }
+    let x_new = x_cur - f_cur / denom;
+    if !x_new.is_finite() {
+        return Err(RootError::IteratedToNaN {
+            x_new: to_f64(x_new),
+        });
+    }
+    Ok(x_new)
+}
+
+/// Map each starting guess to the root Newton-Raphson converges on.
+///
+/// Entries are None where the solve failed (zero derivative, iteration limit,
+/// etc).  Useful for studying how robust a method is across its basins of
+/// attraction.  See `count_basins` to summarize the result.
+pub fn basin_map<F, C>(f: &F, starts: &[f64], finish: &C, max_iter: usize) -> Vec<Option<f64>>
+where
+    F: RealFnEval + RealDfEval,
+    C: IsConverged,
+{
+    starts
+        .iter()
+        .map(|&start| newton_raphson(f, start, finish, max_iter).ok())
+        .collect()
+}
+
+/// Map each starting guess to the root Halley's method converges on.
+///
+/// Same as `basin_map` but using Halley's method.
+pub fn halley_basin_map<F, C>(
+    f: &F,
+    starts: &[f64],
+    finish: &C,
+    max_iter: usize,
+) -> Vec<Option<f64>>
+where
+    F: RealFnEval + RealDfEval + RealD2fEval,
+    C: IsConverged,
+{
+    starts
+        .iter()
+        .map(|&start| halley_method(f, start, finish, max_iter).ok())
+        .collect()
+}
+
+/// Count the distinct roots in a basin map.
+///
+/// Roots closer than `tol` are considered the same.  Failed starts are ignored.
+pub fn count_basins(map: &[Option<f64>], tol: f64) -> usize {
+    let mut roots: Vec<f64> = Vec::new();
+    for root in map.iter().filter_map(|r| *r) {
+        if roots.iter().all(|r| (r - root).abs() >= tol) {
+            roots.push(root);
+        }
+    }
+    roots.len()
+}
+
+/// Estimate the multiplicity of a root.
+///
+/// Near a root of multiplicity m, u(x) = f(x)/f'(x) ≈ (x - root)/m.  The slope
+/// of u is therefore 1/m, which is estimated by a central difference at
+///
+/// x = root ± h, with h = 1e-3 * max(|root|, 1)
+///
+/// and rounded to the nearest integer.  Using the slope rather than u itself
+/// makes the estimate insensitive to small errors in `root`, which is helpful
+/// since Newton converges slowly and inaccurately on multiple roots.
+///
+/// This is a heuristic.  It is fooled if another root lies within h, and for
+/// high multiplicities f is tiny around the root so rounding noise in f can
+/// swamp the estimate.  Returns 1 if the estimate is not finite.
+pub fn estimate_multiplicity<F>(f: &F, root: f64) -> u32
+where
+    F: RealFnEval + RealDfEval,
+{
+    assert!(root.is_finite());
+    let u = |x: f64| f.eval_f(x) / f.eval_df(x);
+
+    let h = 1e-3 * root.abs().max(1.0);
+    let m = 2.0 * h / (u(root + h) - u(root - h));
+    if !m.is_finite() {
+        return 1;
+    }
+    m.round().max(1.0) as u32
+}
+
+/// Refine an approximate root with a fixed number of Newton steps.
+///
+/// Intended as a post-processor for bracketing methods: bisection to a loose
+/// tolerance followed by one or two Newton steps squares the remaining error
+/// each time, reaching near machine precision cheaply.
+///
+/// Polishing stops early, returning the latest estimate unchanged, if the
+/// derivative vanishes or f is exactly zero.
+pub fn polish_newton<F>(f: &F, approx: f64, iters: usize) -> Result<f64, RootError>
+where
+    F: RealFnEval + RealDfEval,
+{
+    let mut x_cur = approx;
+    for _ in 0..iters {
+        let f_cur = f.eval_f(x_cur);
+        if f_cur == 0.0 {
+            break;
+        }
+        x_cur = match nr_step(f, x_cur, f_cur) {
+            Ok(x_new) => x_new,
+            Err(RootError::ZeroDerivative { .. }) => break,
+            Err(e) => return Err(e),
+        };
+    }
+    Ok(x_cur)
+}
+
+/// Newton-Raphson with verification of the residual at the claimed root.
+///
+/// A step-size criterion like DeltaX can converge far from the actual root when
+/// the derivative is huge (see test_pathology_microstep).  This runs Newton with
+/// `DeltaX::new(x_tol)` and then checks |f(root)| against `max_residual`.  If the
+/// check fails, Newton is restarted from the claimed root with the tighter
+/// combination of DeltaX and NewtonStep.
+///
+/// Returns `RootError::SuspectConvergence` if the residual is still too large
+/// (or the retry itself fails).
+pub fn solve_verified<F>(
+    f: &F,
+    start: f64,
+    x_tol: f64,
+    max_residual: f64,
+    max_iter: usize,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval + RealDfEval,
+{
+    assert!(max_residual >= 0.0);
+
+    let root = newton_raphson(f, start, &DeltaX::new(x_tol), max_iter)?;
+    let residual = f.eval_f(root);
+    if residual.abs() <= max_residual {
+        return Ok(root);
+    }
+
+    // retry with a stricter criterion
+    let c1 = DeltaX::new(x_tol);
+    let c2 = NewtonStep::new(f, x_tol);
+    let conv = DualCriteria::new(&c1, &c2);
+    match newton_raphson(f, root, &conv, max_iter) {
+        Ok(x) => {
+            let residual = f.eval_f(x);
+            if residual.abs() <= max_residual {
+                Ok(x)
+            } else {
+                Err(RootError::SuspectConvergence { x, residual })
+            }
+        }
+        Err(_) => Err(RootError::SuspectConvergence { x: root, residual }),
+    }
+}
+
+/// Armijo backtracking line search on the residual |f|.
+///
+/// Starting from the full step t = 1, halves t until the sufficient decrease
+/// condition
+///
+/// |f(x + t*direction)| <= (1 - c*t) * |f_x|
+///
+/// holds.  Returns the accepted step length, or 2^-max_halvings if no larger
+/// step is accepted.  The caller supplies
+/// f_x = f(x) and the search direction, e.g. -f(x)/df(x) for damped Newton.
+///
+/// The constant `c` must lie in (0, 1); values around 1e-4 are customary.
+pub fn armijo_backtrack<F>(
+    f: &F,
+    x: f64,
+    f_x: f64,
+    direction: f64,
+    c: f64,
+    max_halvings: usize,
+) -> f64
+where
+    F: RealFnEval,
+{
+    assert!(c > 0.0 && c < 1.0);
+
+    let mut t = 1.0;
+    for _ in 0..max_halvings {
+        if f.eval_f(x + t * direction).abs() <= (1.0 - c * t) * f_x.abs() {
+            return t;
+        }
+        t *= 0.5;
+    }
+    t
+}
+
+/// Root finding using Halley's method.
+///
+/// The `start` indicates the initial guess.  For guesses sufficiently close to
+/// the root this algorithm has cubic convergence.
+///
+/// This algorithm requires both the first and second derivatives of f(x).
+///
+/// * If only the first derivative is available, consider Newton-Raphson.
+/// * If analytically computed derivatives are not available, consider Brent-Dekker.
+///
+/// A good overview of the derivation, history, and geometric interpretation of
+/// Halley's method is in:
+///
+/// *Scavo, T. R.; Thoo, J. B. (1995). "On the geometry of Halley's method".
+/// American Mathematical Monthly. 102 (5): 417–426.*
+///
+pub fn halley_method<T, F, C>(f: &F, start: T, finish: &C, max_iter: usize) -> Result<T, RootError>
+where
+    T: Float,
+    F: RealFnEval<T> + RealDfEval<T> + RealD2fEval<T>,
+    C: IsConverged<T>,
+{
+    driver::iterative_root_find(f, &halley_step, start, finish, max_iter)
+}
+
+/// Halley's method returning a `RootResult` rather than just the root.
+pub fn halley_method_verbose<T, F, C>(
+    f: &F,
+    start: T,
+    finish: &C,
+    max_iter: usize,
+) -> Result<RootResult<T>, RootError>
+where
+    T: Float,
+    F: RealFnEval<T> + RealDfEval<T> + RealD2fEval<T>,
+    C: IsConverged<T>,
+{
+    driver::iterative_root_find_verbose(f, &halley_step, start, finish, max_iter)
+}
+
+/// Halley's method using only f(x) and df(x).
+///
+/// The second derivative is approximated by the central difference of df
+///
+/// d2f(x) ≈ (df(x+h) - df(x-h)) / 2h
+///
+/// with step h = cbrt(ε) * max(|x|, 1).  Each iteration costs two extra
+/// evaluations of df.  This is `halley_method` on a
+/// `RealFnAndFirstNumericalSecond`, which offers a configurable step.
+///
+/// The approximated d2f carries a relative error around 1e-10 or worse where df
+/// varies rapidly.  Since d2f only enters the correction term this barely
+/// slows convergence near the root, but the final digits may be slightly less
+/// reliable than with an analytic d2f.
+pub fn halley_from_first<F1, F2, C>(
+    f: &F1,
+    df: &F2,
+    start: f64,
+    finish: &C,
+    max_iter: usize,
+) -> Result<f64, RootError>
+where
+    F1: ?Sized + Fn(f64) -> f64,
+    F2: ?Sized + Fn(f64) -> f64,
+    C: IsConverged,
+{
+    let wrapped = RealFnAndFirstNumericalSecond::new(f, df);
+    driver::iterative_root_find(&wrapped, &halley_step, start, finish, max_iter)
+}
+
+/// Halley's method guarded against steps which increase the residual.
+///
+/// Halley's cubic step can badly overshoot from a poor guess, and its direction
+/// may even point away from the root when f(x) * d2f(x) is large.  Whenever the
+/// full Halley step would increase |f|, this instead takes a Newton step
+/// damped by `armijo_backtrack`, halving the step until the residual drops.
+/// The Newton direction decreases |f| for a small enough step, so the residual
+/// is non-increasing from one iteration to the next.  Should 30 halvings still
+/// not reduce it, e.g. because df is inaccurate, the solve returns
+/// `RootError::LineSearchFailed`.  As in `newton_raphson_damped`, a step too
+/// small to change x is always accepted.
+///
+/// Each iteration costs an extra evaluation of f, plus one per halving when the
+/// fallback kicks in.  The pure `halley_method` remains the faster choice from
+/// a good guess.
+pub fn halley_method_adaptive<F, C>(
+    f: &F,
+    start: f64,
+    finish: &C,
+    max_iter: usize,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval + RealDfEval + RealD2fEval,
+    C: IsConverged,
+{
+    driver::iterative_root_find(f, &halley_adaptive_step, start, finish, max_iter)
+}
+
+/// Evaluate a single guarded iteration for Halley's method.
+fn halley_adaptive_step<F>(f: &F, x_cur: f64, f_cur: f64) -> Result<f64, RootError>
+where
+    F: RealFnEval + RealDfEval + RealD2fEval,
+{
+    let x_new = halley_step(f, x_cur, f_cur)?;
+    if f.eval_f(x_new).abs() <= f_cur.abs() {
+        return Ok(x_new);
+    }
+
+    // damped newton fallback
+    let direction = nr_step(f, x_cur, f_cur)? - x_cur;
+    let t = armijo_backtrack(f, x_cur, f_cur, direction, 1e-4, 30);
+    let x_new = x_cur + t * direction;
+    if x_new != x_cur && f.eval_f(x_new).abs() > f_cur.abs() {
+        return Err(RootError::LineSearchFailed { x_cur });
+    }
+    Ok(x_new)
+}
+
+/// Evaluate a single iteration for Halley's method.  Returns x_new on success.
+fn halley_step<T, F>(f: &F, x_cur: T, f_cur: T) -> Result<T, RootError>
+where
+    T: Float,
+    F: RealDfEval<T> + RealD2fEval<T>,
+{
+    let df_cur = f.eval_df(x_cur);
+    let d2f_cur = f.eval_d2f(x_cur);
+
+    if df_cur == T::zero() {
+        return Err(RootError::ZeroDerivative {
+            x_cur: to_f64(x_cur),
+        });
+    }
+
+    let two = T::one() + T::one();
+    let x_new = x_cur - (two * f_cur * df_cur) / (two * df_cur * df_cur - f_cur * d2f_cur);
+    if !x_new.is_finite() {
+        return Err(RootError::IteratedToNaN {
+            x_new: to_f64(x_new),
+        });
+    }
+    Ok(x_new)
+}
+
+/// Root finding using Householder's method of order 3.
+///
+/// Newton-Raphson and Halley's method are the order 1 and 2 members of
+/// Householder's family.  The next member uses f through its third derivative:
+///
+/// x_new = x - (6 f df^2 - 3 f^2 d2f) / (6 df^3 - 6 f df d2f + f^2 d3f)
+///
+/// For guesses sufficiently close to a simple root this converges quartically.
+/// Each iteration evaluates all four functions, so it only pays off when the
+/// derivatives are cheap relative to f.  A zero denominator returns
+/// `RootError::ZeroDerivative`.
+///
+/// *Householder, A. S. (1970). The Numerical Treatment of a Single Nonlinear
+/// Equation. McGraw-Hill.*
+///
+pub fn householder3<F, C>(f: &F, start: f64, finish: &C, max_iter: usize) -> Result<f64, RootError>
+where
+    F: RealFnEval + RealDfEval + RealD2fEval + RealD3fEval,
+    C: IsConverged,
+{
+    driver::iterative_root_find(f, &householder3_step, start, finish, max_iter)
+}
+
+/// Evaluate a single iteration for Householder's order 3 method.  Returns
+/// x_new on success.
+fn householder3_step<F>(f: &F, x_cur: f64, f_cur: f64) -> Result<f64, RootError>
+where
+    F: RealDfEval + RealD2fEval + RealD3fEval,
+{
+    let df_cur = f.eval_df(x_cur);
+    let d2f_cur = f.eval_d2f(x_cur);
+    let d3f_cur = f.eval_d3f(x_cur);
+
+    let denom =
+        6.0 * df_cur * df_cur * df_cur - 6.0 * f_cur * df_cur * d2f_cur + f_cur * f_cur * d3f_cur;
+    if denom == 0.0 {
+        return Err(RootError::ZeroDerivative { x_cur });
+    }
+
+    let x_new = x_cur - (6.0 * f_cur * df_cur * df_cur - 3.0 * f_cur * f_cur * d2f_cur) / denom;
+    if !x_new.is_finite() {
+        return Err(RootError::IteratedToNaN { x_new });
+    }
+    Ok(x_new)
+}
+
+/// Root finding via Schröder's method.
+///
+/// Near a root of multiplicity m, Newton-Raphson and Halley's method slow to
+/// linear convergence.  Schröder's method instead applies Newton to
+/// u(x) = f(x)/df(x), which has a simple root wherever f has a root of any
+/// multiplicity:
+///
+/// x_new = x - f(x) df(x) / (df(x)^2 - f(x) d2f(x))
+///
+/// This restores quadratic convergence without knowing m, at the cost of
+/// needing the second derivative.  At simple roots plain Newton-Raphson or
+/// Halley's method are preferable.
+///
+/// Landing exactly on a root stops the iteration there.  A zero denominator
+/// returns `RootError::ZeroDerivative`.
+///
+/// *Schröder, E. (1870). Über unendlich viele Algorithmen zur Auflösung der
+/// Gleichungen. Mathematische Annalen, 2(2), 317-365.*
+pub fn schroder<F, C>(f: &F, start: f64, finish: &C, max_iter: usize) -> Result<f64, RootError>
+where
+    F: RealFnEval + RealDfEval + RealD2fEval,
+    C: IsConverged,
+{
+    driver::iterative_root_find(f, &schroder_step, start, finish, max_iter)
+}
+
+/// Evaluate a single iteration for Schröder's method.  Returns x_new on
+/// success.
+fn schroder_step<F>(f: &F, x_cur: f64, f_cur: f64) -> Result<f64, RootError>
+where
+    F: RealDfEval + RealD2fEval,
+{
+    if f_cur == 0.0 {
+        return Ok(x_cur);
+    }
+
+    let df_cur = f.eval_df(x_cur);
+    let d2f_cur = f.eval_d2f(x_cur);
+
+    let denom = df_cur * df_cur - f_cur * d2f_cur;
+    if denom == 0.0 {
+        return Err(RootError::ZeroDerivative { x_cur });
+    }
+
+    let x_new = x_cur - f_cur * df_cur / denom;
+    if !x_new.is_finite() {
+        return Err(RootError::IteratedToNaN { x_new });
+    }
+    Ok(x_new)
+}
+
+/// Laguerre's method for a real root of a polynomial.
+///
+/// Uses p, p', p'' and the degree `n` to fit the step
+///
+/// x_new = x_cur - n / (G ± sqrt((n-1)(nH - G^2)))
+///
+/// where `G = p'/p` and `H = G^2 - p''/p`.  The sign is chosen to match `G`,
+/// maximizing the magnitude of the denominator.  Convergence is cubic near
+/// simple roots and the method tolerates poor initial guesses far better than
+/// Newton-Raphson.
+///
+/// Iteration stays on the real line: a negative discriminant, which signals
+/// nearby complex roots, is clamped to zero.  From some starting points the
+/// iterates may therefore cycle instead of converging.  Iteration stops once
+/// the step size falls below `tolerance`.
+///
+/// *Press, W. H., et al. (2007). Numerical Recipes (3rd ed.), section 9.5.*
+///
+pub fn laguerre(
+    poly: &Polynomial,
+    start: f64,
+    tolerance: f64,
+    max_iter: usize,
+) -> Result<f64, RootError> {
+    assert!(poly.degree() > 0, "polynomial must be non-constant");
+    assert!(start.is_finite());
+    assert!(tolerance > 0.0);
+
+    let n = poly.degree() as f64;
+    let mut x_cur = start;
+
+    for _ in 0..max_iter {
+        let (p, dp, d2p) = poly.eval_with_derivatives(x_cur);
+        if p == 0.0 {
+            return Ok(x_cur);
+        }
+
+        let g = dp / p;
+        let h = g * g - d2p / p;
+        let sq = ((n - 1.0) * (n * h - g * g)).max(0.0).sqrt();
+        let denom = if g >= 0.0 { g + sq } else { g - sq };
+        if denom == 0.0 {
+            return Err(RootError::ZeroDerivative { x_cur });
+        }
+
+        let step = n / denom;
+        let x_new = x_cur - step;
+        if !x_new.is_finite() {
+            return Err(RootError::IteratedToNaN { x_new });
+        }
+        if step.abs() < tolerance {
+            return Ok(x_new);
+        }
+        x_cur = x_new;
+    }
+    Err(RootError::IterationLimit { last_x: x_cur })
+}
+
+/// Muller's method, which can find complex roots of real functions.
+///
+/// Fits a parabola through the three most recent points and steps to the
+/// nearer root of that parabola:
+///
+/// x_new = x2 - 2c / (b ± sqrt(b^2 - 4ac))
+///
+/// with the sign chosen to maximize the magnitude of the denominator.  All
+/// arithmetic is complex, so a negative discriminant carries the iteration
+/// off the real line even from real seeds.  The order of convergence is about
+/// 1.84 and no derivatives are needed.  Iteration stops once the step size
+/// falls below `tolerance`, or on hitting f(z) == 0 exactly.
+///
+/// Errors report the real part of the offending iterate.  A vanishing
+/// denominator, e.g. for a constant f, returns `RootError::ZeroDerivative`.
+///
+/// *Muller, D. E. (1956). A method for solving algebraic equations using an
+/// automatic computer. Mathematical Tables and Other Aids to Computation,
+/// 10(56), 208-215.*
+///
+pub fn muller<F>(
+    f: &F,
+    x0: f64,
+    x1: f64,
+    x2: f64,
+    tolerance: f64,
+    max_iter: usize,
+) -> Result<Complex<f64>, RootError>
+where
+    F: ComplexFnEval,
+{
+    assert!(x0.is_finite() && x1.is_finite() && x2.is_finite());
+    assert!(x0 != x1 && x1 != x2 && x0 != x2, "seeds must be distinct");
+    assert!(tolerance > 0.0);
+
+    let mut z0 = Complex::new(x0, 0.0);
+    let mut z1 = Complex::new(x1, 0.0);
+    let mut z2 = Complex::new(x2, 0.0);
+    let mut f0 = f.eval_complex(z0);
+    let mut f1 = f.eval_complex(z1);
+    let mut f2 = f.eval_complex(z2);
+
+    for _ in 0..max_iter {
+        if f2.norm_sqr() == 0.0 {
+            return Ok(z2);
+        }
+
+        // parabola through the three points, centered on z2
+        let h1 = z1 - z0;
+        let h2 = z2 - z1;
+        let d1 = (f1 - f0) / h1;
+        let d2 = (f2 - f1) / h2;
+        let a = (d2 - d1) / (h2 + h1);
+        let b = a * h2 + d2;
+        let c = f2;
+
+        let sq = (b * b - a * c * 4.0).sqrt();
+        let denom = if (b + sq).norm() >= (b - sq).norm() {
+            b + sq
+        } else {
+            b - sq
+        };
+        if denom.norm_sqr() == 0.0 {
+            return Err(RootError::ZeroDerivative { x_cur: z2.re });
+        }
+
+        let dz = -c * 2.0 / denom;
+        let z_new = z2 + dz;
+        if !(z_new.re.is_finite() && z_new.im.is_finite()) {
+            return Err(RootError::IteratedToNaN { x_new: z_new.re });
+        }
+        if dz.norm() < tolerance {
+            return Ok(z_new);
+        }
+
+        z0 = z1;
+        f0 = f1;
+        z1 = z2;
+        f1 = f2;
+        z2 = z_new;
+        f2 = f.eval_complex(z2);
+    }
+    Err(RootError::IterationLimit { last_x: z2.re })
+}
+
+/// Newton-Raphson over the complex plane.
+///
+/// Iterates z_new = z - f(z) / df(z) with complex arithmetic throughout, so
+/// complex roots are reachable from complex seeds.  A real seed of a real
+/// polynomial stays on the real line; use a seed with nonzero imaginary part
+/// to find complex conjugate roots.
+///
+/// `finish` sees moduli rather than points: it is called with `x_pre = 0`,
+/// `x_cur = |z_new - z|` and `f_cur = |f(z_new)|`.  Criteria which compare
+/// the two x values, such as `DeltaX`, therefore bound the complex step, and
+/// `FnResidual` bounds the complex residual.  Criteria which treat the x
+/// values as positions on the real line (`BracketAndResidual`,
+/// `SignificantDigits`, `TargetCrossed`, `NewtonStep`) are not meaningful here.  Hitting f(z) == 0 exactly also
+/// stops the iteration.
+///
+/// Errors report the real part of the offending iterate.  A zero derivative
+/// returns `RootError::ZeroDerivative`.
+pub fn complex_newton<F, C>(
+    f: &F,
+    start: Complex<f64>,
+    finish: &C,
+    max_iter: usize,
+) -> Result<Complex<f64>, RootError>
+where
+    F: ComplexFnEval + ComplexDfEval,
+    C: IsConverged,
+{
+    assert!(start.re.is_finite() && start.im.is_finite());
+
+    let mut z = start;
+    let mut f_z = f.eval_complex(z);
+    for _ in 0..max_iter {
+        if f_z.norm_sqr() == 0.0 {
+            return Ok(z);
+        }
+
+        let df_z = f.eval_complex_df(z);
+        if df_z.norm_sqr() == 0.0 {
+            return Err(RootError::ZeroDerivative { x_cur: z.re });
+        }
+
+        let z_new = z - f_z / df_z;
+        if !(z_new.re.is_finite() && z_new.im.is_finite()) {
+            return Err(RootError::IteratedToNaN { x_new: z_new.re });
+        }
+        let f_new = f.eval_complex(z_new);
+        if finish.is_converged(0.0, (z_new - z).norm(), f_new.norm()) {
+            return Ok(z_new);
+        }
+
+        z = z_new;
+        f_z = f_new;
+    }
+    Err(RootError::IterationLimit { last_x: z.re })
+}
+
+/// Fixed-point iteration accelerated by Wegstein's method.
+///
+/// Solves `x = g(x)` rather than `f(x) = 0`.  Each step estimates the slope `s`
+/// of `g` from the two most recent iterates and applies the relaxation factor
+/// `q = s/(s-1)`:
+///
+/// x_new = q * x_cur + (1 - q) * g(x_cur)
+///
+/// This is a secant method on `g(x) - x` in disguise and typically converges
+/// far faster than plain fixed-point iteration.  It remains popular in chemical
+/// engineering flowsheet solvers.  When the slope is undefined or exactly one,
+/// a plain fixed-point step is taken instead.
+///
+/// The convergence criteria receives `g(x_cur) - x_cur` as the residual.
+///
+/// *Wegstein, J. H. (1958). Accelerating convergence of iterative processes.
+/// Communications of the ACM, 1(6), 9-13.*
+///
+pub fn wegstein<F, C>(g: &F, x0: f64, finish: &C, max_iter: usize) -> Result<f64, RootError>
+where
+    F: RealFnEval,
+    C: IsConverged,
+{
+    assert!(x0.is_finite());
+
+    let mut x_pre = x0;
+    let mut g_pre = g.eval_f(x_pre);
+
+    // plain fixed-point step to get a second point for the slope
+    let mut x_cur = g_pre;
+    if !x_cur.is_finite() {
+        return Err(RootError::IteratedToNaN { x_new: x_cur });
+    }
+
+    for _ in 0..max_iter {
+        let g_cur = g.eval_f(x_cur);
+
+        // check convergence
+        if finish.is_converged(x_pre, x_cur, g_cur - x_cur) {
+            return Ok(x_cur);
+        }
+
+        let dx = x_cur - x_pre;
+        let s = (g_cur - g_pre) / dx;
+        let x_new = if dx == 0.0 || s == 1.0 || !s.is_finite() {
+            g_cur
+        } else {
+            let q = s / (s - 1.0);
+            q * x_cur + (1.0 - q) * g_cur
+        };
+        if !x_new.is_finite() {
+            return Err(RootError::IteratedToNaN { x_new });
+        }
+
+        x_pre = x_cur;
+        g_pre = g_cur;
+        x_cur = x_new;
+    }
+    Err(RootError::IterationLimit { last_x: x_cur })
+}
+
+/// Plain fixed-point iteration, x_new = g(x_cur).
+///
+/// Solves `x = g(x)` directly.  Convergence is linear with rate |g'| near the
+/// fixed point, so this only works where g is a contraction.  For anything
+/// slower than a few dozen iterations `wegstein` is usually the better choice.
+///
+/// The convergence criteria receives `g(x_cur) - x_cur` as the residual.
+///
+/// For a contraction that residual shrinks every step.  If it instead grows
+/// for three steps in a row, `RootError::Diverged` is returned.
+///
+pub fn fixed_point<G, C>(g: &G, start: f64, finish: &C, max_iter: usize) -> Result<f64, RootError>
+where
+    G: Fn(f64) -> f64,
+    C: IsConverged,
+{
+    assert!(start.is_finite());
+
+    let mut x_pre = start;
+    let mut x_cur = g(start);
+    if !x_cur.is_finite() {
+        return Err(RootError::IteratedToNaN { x_new: x_cur });
+    }
+    let mut resid_pre = (x_cur - x_pre).abs();
+    let mut growing = 0;
+
+    for _ in 0..max_iter {
+        let g_cur = g(x_cur);
+        let resid = g_cur - x_cur;
+
+        // check convergence
+        if finish.is_converged(x_pre, x_cur, resid) {
+            return Ok(x_cur);
+        }
+
+        if resid.abs() > resid_pre {
+            growing += 1;
+            if growing >= 3 {
+                return Err(RootError::Diverged { last_x: x_cur });
+            }
+        } else {
+            growing = 0;
+        }
+
+        if !g_cur.is_finite() {
+            return Err(RootError::IteratedToNaN { x_new: g_cur });
+        }
+
+        x_pre = x_cur;
+        x_cur = g_cur;
+        resid_pre = resid.abs();
+    }
+    Err(RootError::IterationLimit { last_x: x_cur })
+}
+
+/// Fixed-point iteration accelerated by Aitken's delta-squared process.
+///
+/// Solves `x = g(x)`.  Each cycle takes two plain steps from `x_cur`, giving
+/// `x1 = g(x_cur)` and `x2 = g(x1)`, then extrapolates
+///
+/// x_new = x_cur - (x1 - x_cur)^2 / (x2 - 2 x1 + x_cur)
+///
+/// and restarts from `x_new`.  This is Steffensen's method applied to
+/// `g(x) - x` and turns linear convergence into quadratic.  If the second
+/// difference vanishes, `x2` is used as is.
+///
+/// The convergence criteria receives `g(x_new) - x_new` as the residual.
+/// That evaluation doubles as the first step of the next cycle, so each cycle
+/// costs two calls to g.
+///
+/// *Aitken, A. C. (1926). On Bernoulli's numerical solution of algebraic
+/// equations. Proceedings of the Royal Society of Edinburgh, 46, 289-305.*
+///
+pub fn aitken_accelerate<G, C>(
+    g: &G,
+    start: f64,
+    finish: &C,
+    max_iter: usize,
+) -> Result<f64, RootError>
+where
+    G: Fn(f64) -> f64,
+    C: IsConverged,
+{
+    assert!(start.is_finite());
+
+    let mut x_cur = start;
+    let mut g_cur = g(x_cur);
+
+    for _ in 0..max_iter {
+        let x1 = g_cur;
+        let x2 = g(x1);
+
+        let d1 = x1 - x_cur;
+        let d2 = x2 - 2.0 * x1 + x_cur;
+        let x_new = if d2 == 0.0 { x2 } else { x_cur - d1 * d1 / d2 };
+        if !x_new.is_finite() {
+            return Err(RootError::IteratedToNaN { x_new });
+        }
+
+        let g_new = g(x_new);
+        if finish.is_converged(x_cur, x_new, g_new - x_new) {
+            return Ok(x_new);
+        }
+
+        x_cur = x_new;
+        g_cur = g_new;
+    }
+    Err(RootError::IterationLimit { last_x: x_cur })
+}
+
+/// Root finding via inverse quadratic interpolation.
+///
+/// Fits x as a quadratic in f through the three most recent points and steps
+/// to where that quadratic gives `f = 0`.  This is the fast engine inside
+/// Brent's method, used here without the bisection safeguard, so it is best
+/// kept to smooth functions and starting points near a simple root.  The
+/// order of convergence is about 1.84.
+///
+/// Points with coinciding f-values make the interpolation degenerate.  If
+/// only the oldest point is affected a secant step through the two newest is
+/// taken instead, otherwise `RootError::ZeroDerivative` is returned.
+///
+/// Convergence is checked with the two most recent points, `x2` being the
+/// newest of the starting three.
+///
+pub fn inverse_quadratic<F, C>(
+    f: &F,
+    x0: f64,
+    x1: f64,
+    x2: f64,
+    finish: &C,
+    max_iter: usize,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval,
+    C: IsConverged,
+{
+    assert!(x0.is_finite() && x1.is_finite() && x2.is_finite());
+
+    let (mut x0, mut x1, mut x2) = (x0, x1, x2);
+    let (mut f0, mut f1, mut f2) = (f.eval_f(x0), f.eval_f(x1), f.eval_f(x2));
+
+    for _ in 0..max_iter {
+        if f1 == f2 {
+            return Err(RootError::ZeroDerivative { x_cur: x2 });
+        }
+
+        let x_new = if f0 == f1 || f0 == f2 {
+            x2 - f2 * (x2 - x1) / (f2 - f1)
+        } else {
+            x0 * f1 * f2 / ((f0 - f1) * (f0 - f2))
+                + x1 * f0 * f2 / ((f1 - f0) * (f1 - f2))
+                + x2 * f0 * f1 / ((f2 - f0) * (f2 - f1))
+        };
+        if !x_new.is_finite() {
+            return Err(RootError::IteratedToNaN { x_new });
+        }
+
+        let f_new = f.eval_f(x_new);
+        if finish.is_converged(x2, x_new, f_new) {
+            return Ok(x_new);
+        }
+
+        x0 = x1;
+        f0 = f1;
+        x1 = x2;
+        f1 = f2;
+        x2 = x_new;
+        f2 = f_new;
+    }
+    Err(RootError::IterationLimit { last_x: x2 })
+}
+
+/// Root finding via Bisection Method.
+///
+/// It always converges given a valid starting bracket, but the speed of
+/// convergence is linear.
+///
+/// Convergence is checked as `finish.is_converged(x_pre, x_cur, f_cur)` with
+/// the previous and latest midpoints, just like the iterative methods.  On the
+/// first iteration `x_pre` is the bracket's lower end.  On convergence the
+/// latest midpoint is returned.  `DeltaX::new(1e-9)` gives a root within 1e-9.
+///
+/// Before the first iteration, `finish` sees the starting bracket as `x_pre =
+/// a` and `x_cur = b`, with whichever of f(a) and f(b) is larger in magnitude.
+/// A bracket which already satisfies it returns its middle without further
+/// evaluations.  This pre-check is a call like any other, so stateful criteria
+/// count it: `MinIterations::new(&c, n)` may stop after n - 1 splits, and
+/// `Stagnation` and `AdaptiveResidual` take b and that residual as their
+/// first sample.
+///
+/// A zero-width bracket returns `RootError::DegenerateBracket` and one without
+/// a sign change returns `RootError::NoSignChange`, as do the other bracketing
+/// solvers.
+///
+/// A sign change may come from a singularity instead of a root, e.g. 1/x
+/// across zero.  Bisection closes in on either, so on convergence the residual
+/// is checked against the starting ones.  If |f| on both sides of the final
+/// bracket exceeds |f| at both starting endpoints,
+/// `RootError::ConvergedToSingularity` is returned.  This needs
+/// no extra function evaluations.  The same check applies to the other
+/// bisection variants.
+pub fn bisection<T, F, C>(
+    f: &F,
+    bounds: &Bounds<T>,
+    finish: &C,
+    max_iter: usize,
+) -> Result<T, RootError>
+where
+    T: Float,
+    F: RealFnEval<T>,
+    C: IsConverged<T>,
+{
+    check_degenerate(bounds)?;
+    let f_a = f.eval_f(bounds.a);
+    let f_b = f.eval_f(bounds.b);
+
+    // ensure we started with valid bracket
+    check_sign_change(bounds, f_a, f_b)?;
+
+    bisection_from(f, bounds, f_a, f_b, finish, max_iter, None)
+}
+
+/// Bisection returning the final bracket rather than a point estimate.
+///
+/// Runs exactly as `bisection`, with the same errors, but on convergence
+/// returns the last window, which still shows a sign change.  The root
+/// `bisection` returns is one of its endpoints.  With `DeltaX` the width is
+/// within the tolerance, giving a rigorous enclosure of the root.  See
+/// `tighten_bracket` to bisect down to a target width instead.
+pub fn bisection_bracket<T, F, C>(
+    f: &F,
+    bounds: &Bounds<T>,
+    finish: &C,
+    max_iter: usize,
+) -> Result<Bounds<T>, RootError>
+where
+    T: Float,
+    F: RealFnEval<T>,
+    C: IsConverged<T>,
+{
+    check_degenerate(bounds)?;
+    let f_a = f.eval_f(bounds.a);
+    let f_b = f.eval_f(bounds.b);
+    check_sign_change(bounds, f_a, f_b)?;
+
+    let no_check = |_: &Bounds<T>| Ok(());
+    bisection_loop(
+        f,
+        bounds,
+        f_a,
+        f_b,
+        finish,
+        max_iter,
+        None,
+        &Bounds::middle,
+        &is_sign_change,
+        &no_check,
+    )
+    .map(|(_, window)| window)
+}
+
+/// Bisection with the fixed tolerance of earlier releases.
+///
+/// Equivalent to `bisection` with `DeltaX::new(1e-9)`.
+#[deprecated(note = "use `bisection` with an explicit `DeltaX::new(1e-9)`")]
+pub fn bisection_default_tol<F>(f: &F, bounds: &Bounds, max_iter: usize) -> Result<f64, RootError>
+where
+    F: RealFnEval,
+{
+    bisection(f, bounds, &DeltaX::new(1e-9), max_iter)
+}
+
+/// Bisection given precomputed f(a) and f(b).
+///
+/// Callers that already evaluated f at the bracket endpoints, such as when
+/// scanning for brackets, can pass those values to save two evaluations.  The
+/// supplied values must still show a sign change.
+pub fn bisection_with_endpoints<F>(
+    f: &F,
+    bounds: &Bounds,
+    f_a: f64,
+    f_b: f64,
+    max_iter: usize,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval,
+{
+    check_degenerate(bounds)?;
+    check_sign_change(bounds, f_a, f_b)?;
+    bisection_from(f, bounds, f_a, f_b, &DeltaX::new(1e-9), max_iter, None)
+}
+
+/// Number of bisection iterations needed to shrink a bracket to width `eps`.
+///
+/// Each iteration halves the bracket, so this is ceil(log2((b - a) / eps)), or
+/// zero if the bracket is already that narrow.  Handy for sizing `max_iter`.
+pub fn bisection_iterations_for(bounds: &Bounds, eps: f64) -> usize {
+    assert!(eps > 0.0);
+    assert!(eps.is_finite());
+    ((bounds.b - bounds.a) / eps).log2().ceil().max(0.0) as usize
+}
+
+/// Bisection giving up once `deadline` has passed.
+///
+/// The deadline is checked before each iteration.  On expiry this returns
+/// `RootError::Timeout` with the middle of the current bracket.
+#[cfg(feature = "std")]
+pub fn bisection_deadline<F>(
+    f: &F,
+    bounds: &Bounds,
+    max_iter: usize,
+    deadline: &Deadline,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval,
+{
+    check_degenerate(bounds)?;
+    let f_a = f.eval_f(bounds.a);
+    let f_b = f.eval_f(bounds.b);
+    check_sign_change(bounds, f_a, f_b)?;
+
+    let check = |window: &Bounds| deadline.check(window.middle());
+    let finish = DeltaX::new(1e-9);
+    bisection_loop(
+        f,
+        bounds,
+        f_a,
+        f_b,
+        &finish,
+        max_iter,
+        None,
+        &Bounds::middle,
+        &is_sign_change,
+        &check,
+    )
+    .map(|(root, _)| root)
+}
+
+/// Bisect until the bracket is no wider than `target_width`, returning the
+/// bracket rather than a point estimate.
+///
+/// The returned bracket still shows a sign change, so (for continuous f)
+/// encloses a root.  Useful as a starting point for interval arithmetic or
+/// when an error bound matters more than a single value.  Returns
+/// `RootError::IterationLimit` if `max_iter` bisections are not enough.
+pub fn tighten_bracket<F>(
+    f: &F,
+    bounds: &Bounds,
+    target_width: f64,
+    max_iter: usize,
+) -> Result<Bounds, RootError>
+where
+    F: RealFnEval,
+{
+    assert!(target_width >= 0.0);
+    let f_a = f.eval_f(bounds.a);
+    let f_b = f.eval_f(bounds.b);
+    check_sign_change(bounds, f_a, f_b)?;
+
+    let mut b = Bisector::new(bounds, f_a, f_b);
+    for _ in 0..max_iter {
+        if b.window.size() <= target_width {
+            return Ok(b.window);
+        }
+        let mid = b.window.middle();
+        b.split(f, mid)?;
+    }
+    if b.window.size() <= target_width {
+        return Ok(b.window);
+    }
+    Err(RootError::IterationLimit {
+        last_x: b.window.middle(),
+    })
+}
+
+/// Bisection treating |f| below `zero_eps` as an exact root.
+///
+/// Close to a root f is often dominated by rounding noise, so its sign flips
+/// at random and plain bisection keeps chattering until the bracket width
+/// tolerance is met.  This instead stops at the first point, endpoints
+/// included, where f is exactly zero or |f| < zero_eps, and otherwise stops once the bracket is
+/// narrower than 1e-9.  The half to keep is chosen with `is_sign_change_eps`,
+/// so values below `zero_eps` never count as a sign change.
+pub fn bisection_eps<F>(
+    f: &F,
+    bounds: &Bounds,
+    zero_eps: f64,
+    max_iter: usize,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval,
+{
+    check_degenerate(bounds)?;
+    assert!(zero_eps >= 0.0);
+    let f_a = f.eval_f(bounds.a);
+    let f_b = f.eval_f(bounds.b);
+
+    // endpoint is already a root
+    let is_zero = |x: f64| x == 0.0 || x.abs() < zero_eps;
+    if is_zero(f_a) {
+        return Ok(bounds.a);
+    }
+    if is_zero(f_b) {
+        return Ok(bounds.b);
+    }
+    check_sign_change(bounds, f_a, f_b)?;
+
+    let finish =
+        ClosureCriteria::new(|a: f64, b: f64, f_mid: f64| (b - a).abs() < 1e-9 || is_zero(f_mid));
+    let changes = |lhs: f64, rhs: f64| is_sign_change_eps(lhs, rhs, zero_eps);
+    bisection_loop(
+        f,
+        bounds,
+        f_a,
+        f_b,
+        &finish,
+        max_iter,
+        None,
+        &Bounds::middle,
+        &changes,
+        &|_: &Bounds| Ok(()),
+    )
+    .map(|(root, _)| root)
+}
+
+/// Bisection splitting at the geometric midpoint sqrt(a*b).
+///
+/// For strictly positive brackets spanning many decades, with the root near
+/// the small end, this needs far fewer iterations than plain bisection.  Once
+/// the bracket is narrow the two midpoints nearly coincide.  Brackets that are
+/// not strictly positive fall back to the arithmetic midpoint.
+pub fn bisection_log<F>(f: &F, bounds: &Bounds, max_iter: usize) -> Result<f64, RootError>
+where
+    F: RealFnEval,
+{
+    check_degenerate(bounds)?;
+    let f_a = f.eval_f(bounds.a);
+    let f_b = f.eval_f(bounds.b);
+
+    // ensure we started with valid bracket
+    check_sign_change(bounds, f_a, f_b)?;
+
+    let middle = |b: &Bounds| b.geometric_middle().unwrap_or_else(|| b.middle());
+    let finish = DeltaX::new(1e-9);
+    bisection_loop(
+        f,
+        bounds,
+        f_a,
+        f_b,
+        &finish,
+        max_iter,
+        None,
+        &middle,
+        &is_sign_change,
+        &|_: &Bounds| Ok(()),
+    )
+    .map(|(root, _)| root)
+}
+
+/// Bisection splitting in the middle of the representable floats.
+///
+/// Plain bisection halves the bracket width and stops below an absolute 1e-9.
+/// For a bracket like [-1e300, 1e300] that takes over a thousand iterations,
+/// and a root far from zero can never get that tight since adjacent floats are
+/// further apart.  Here the bracket is instead split halfway between the
+/// endpoints' positions in the ordered sequence of f64 values, so each step
+/// halves the number of floats in the bracket.  Any finite bracket converges
+/// in at most 64 iterations, regardless of scale.
+///
+/// Stops once the bracket holds no more than `max_ulps` steps between floats,
+/// or immediately on hitting f(x) == 0 exactly.  The downside is that the
+/// early iterations split near zero rather than halving the width, so brackets
+/// which do not span many orders of magnitude are better off with `bisection`.
+pub fn bisection_ulps<F>(
+    f: &F,
+    bounds: &Bounds,
+    max_ulps: u64,
+    max_iter: usize,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval,
+{
+    check_degenerate(bounds)?;
+    assert!(max_ulps > 0);
+    let f_a = f.eval_f(bounds.a);
+    let f_b = f.eval_f(bounds.b);
+    check_sign_change(bounds, f_a, f_b)?;
+
+    let mut b = Bisector::new(bounds, f_a, f_b);
+    for _ in 0..max_iter {
+        let k_a = ordered_bits(b.window.a) as i128;
+        let k_b = ordered_bits(b.window.b) as i128;
+        if (k_b - k_a) as u64 <= max_ulps {
+            return Ok(b.window.a);
+        }
+
+        let mid = from_ordered_bits(((k_a + k_b) / 2) as i64);
+        if b.split(f, mid)? == 0.0 {
+            return Ok(mid);
+        }
+    }
+    Err(RootError::IterationLimit { last_x: b.window.a })
+}
+
+/// Map a float to an integer preserving order, with adjacent floats mapping to
+/// adjacent integers.  Both signed zeros map to zero.
+fn ordered_bits(x: f64) -> i64 {
+    let bits = x.to_bits() as i64;
+    if bits < 0 {
+        i64::MIN - bits
+    } else {
+        bits
+    }
+}
+
+/// Inverse of `ordered_bits`.
+fn from_ordered_bits(k: i64) -> f64 {
+    if k < 0 {
+        f64::from_bits((i64::MIN - k) as u64)
+    } else {
+        f64::from_bits(k as u64)
+    }
+}
+
+/// Bisection requiring the bracket to cross zero with a given orientation.
+///
+/// Returns `RootError::NoSignChange` if f(a) and f(b) do not change sign as
+/// required by `crossing`, e.g. a NegToPos bracket given PosToNeg.
+pub fn bisection_crossing<F>(
+    f: &F,
+    bounds: &Bounds,
+    crossing: Crossing,
+    max_iter: usize,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval,
+{
+    check_degenerate(bounds)?;
+    let f_a = f.eval_f(bounds.a);
+    let f_b = f.eval_f(bounds.b);
+    if !crossing.matches(f_a, f_b) {
+        return Err(RootError::NoSignChange {
+            a: bounds.a,
+            b: bounds.b,
+        });
+    }
+    bisection_from(f, bounds, f_a, f_b, &DeltaX::new(1e-9), max_iter, None)
+}
+
+/// Bisection also returning the midpoint evaluated at each iteration.
+///
+/// The trace is reserved up front for `max_iter` entries.  The returned root
+/// is the last midpoint, converged with `DeltaX::new(1e-9)`.
+pub fn bisection_trace<F>(
+    f: &F,
+    bounds: &Bounds,
+    max_iter: usize,
+) -> Result<(f64, Vec<f64>), RootError>
+where
+    F: RealFnEval,
+{
+    check_degenerate(bounds)?;
+    let f_a = f.eval_f(bounds.a);
+    let f_b = f.eval_f(bounds.b);
+    check_sign_change(bounds, f_a, f_b)?;
+
+    let mut trace = Vec::with_capacity(max_iter);
+    let finish = DeltaX::new(1e-9);
+    let root = bisection_from(f, bounds, f_a, f_b, &finish, max_iter, Some(&mut trace))?;
+    Ok((root, trace))
+}
+
+/// Widen a scalar to f64 for error reporting.
+pub(crate) fn to_f64<T: Float>(x: T) -> f64 {
+    x.to_f64().unwrap_or(f64::NAN)
+}
+
+/// Reject zero-width brackets, which can never show a sign change.
+pub(crate) fn check_degenerate<T: Float>(bounds: &Bounds<T>) -> Result<(), RootError> {
+    if bounds.a == bounds.b {
+        return Err(RootError::DegenerateBracket {
+            x: to_f64(bounds.a),
+        });
+    }
+    Ok(())
+}
+
+/// Reject brackets whose endpoint values f(a) and f(b) do not change sign.
+pub(crate) fn check_sign_change<T: Float>(
+    bounds: &Bounds<T>,
+    f_a: T,
+    f_b: T,
+) -> Result<(), RootError> {
+    if !is_sign_change(f_a, f_b) {
+        return Err(RootError::NoSignChange {
+            a: to_f64(bounds.a),
+            b: to_f64(bounds.b),
+        });
+    }
+    Ok(())
+}
+
+/// Bisection loop given a validated bracket with f(a) and f(b).
+pub(crate) fn bisection_from<T, F, C>(
+    f: &F,
+    bounds: &Bounds<T>,
+    f_a: T,
+    f_b: T,
+    finish: &C,
+    max_iter: usize,
+    trace: Option<&mut Vec<T>>,
+) -> Result<T, RootError>
+where
+    T: Float,
+    F: RealFnEval<T>,
+    C: IsConverged<T>,
+{
+    let no_check = |_: &Bounds<T>| Ok(());
+    bisection_loop(
+        f,
+        bounds,
+        f_a,
+        f_b,
+        finish,
+        max_iter,
+        trace,
+        &Bounds::middle,
+        &is_sign_change,
+        &no_check,
+    )
+    .map(|(root, _)| root)
+}
+
+/// Bracket shared by the bisection loops, with f known at both ends.
+///
+/// Every variant splits through `split`, so they all spend one evaluation
+/// per step and agree on which half keeps the sign change.
+struct Bisector<T> {
+    window: Bounds<T>,
+    f_a: T,
+    f_b: T,
+}
+
+impl<T: Float> Bisector<T> {
+    fn new(bounds: &Bounds<T>, f_a: T, f_b: T) -> Bisector<T> {
+        Bisector {
+            window: *bounds,
+            f_a,
+            f_b,
+        }
+    }
+
+    /// Split the window at `mid`, keeping the half which changes sign, and
+    /// return f(mid).
+    fn split<F>(&mut self, f: &F, mid: T) -> Result<T, RootError>
+    where
+        F: RealFnEval<T>,
+    {
+        self.split_by(f, mid, &is_sign_change)
+    }
+
+    /// As `split`, but deciding which half changes sign with `changes`.
+    fn split_by<F, S>(&mut self, f: &F, mid: T, changes: &S) -> Result<T, RootError>
+    where
+        F: RealFnEval<T>,
+        S: Fn(T, T) -> bool,
+    {
+        let f_mid = f.eval_f(mid);
+        if f_mid.is_nan() {
+            return Err(RootError::NonFiniteEvaluation { x: to_f64(mid) });
+        }
+        if changes(self.f_a, f_mid) {
+            self.window.b = mid;
+            self.f_b = f_mid;
+        } else {
+            self.window.a = mid;
+            self.f_a = f_mid;
+        }
+        Ok(f_mid)
+    }
+}
+
+/// Bisection loop splitting each window at the point chosen by `middle`.
+///
+/// The half kept is the one where `changes` reports a sign change.  The `check` is run on the window before each iteration and any error it
+/// returns ends the loop.  On convergence, returns the latest midpoint along
+/// with the final window.
+#[allow(clippy::too_many_arguments)]
+fn bisection_loop<T, F, C, M, S, K>(
+    f: &F,
+    bounds: &Bounds<T>,
+    f_a: T,
+    f_b: T,
+    finish: &C,
+    max_iter: usize,
+    mut trace: Option<&mut Vec<T>>,
+    middle: &M,
+    changes: &S,
+    check: &K,
+) -> Result<(T, Bounds<T>), RootError>
+where
+    T: Float,
+    F: RealFnEval<T>,
+    C: IsConverged<T>,
+    M: Fn(&Bounds<T>) -> T,
+    S: Fn(T, T) -> bool,
+    K: Fn(&Bounds<T>) -> Result<(), RootError>,
+{
+    let mut b = Bisector::new(bounds, f_a, f_b);
+    let f_start = f_a.abs().max(f_b.abs());
+    let mut x_pre = bounds.a;
+
+    // residual outgrew both starting values, so this is a pole not a root
+    let accept = |mid: T, b: &Bisector<T>| {
+        let residual = b.f_a.abs().min(b.f_b.abs());
+        if residual > f_start {
+            return Err(RootError::ConvergedToSingularity {
+                x: to_f64(mid),
+                residual: to_f64(residual),
+            });
+        }
+        Ok((mid, b.window))
+    };
+
+    // already converged, e.g. tight bracket from isolate_roots
+    let f_worse = if f_a.abs() > f_b.abs() { f_a } else { f_b };
+    if finish.is_converged(bounds.a, bounds.b, f_worse) {
+        return accept(bounds.middle(), &b);
+    }
+
+    for _ in 0..max_iter {
+        check(&b.window)?;
+        let mid = middle(&b.window);
+        let f_mid = b.split_by(f, mid, changes)?;
+        if let Some(ref mut t) = trace {
+            t.push(mid);
+        }
+
+        // convergence criteria
+        if finish.is_converged(x_pre, mid, f_mid) {
+            return accept(mid, &b);
+        }
+        x_pre = mid;
+    }
+    Err(RootError::IterationLimit {
+        last_x: to_f64(b.window.middle()),
+    })
+}
+
+/// Steffensen's method safeguarded by a bracket.
+///
+/// Steffensen's step
+///
+/// x_new = x - f(x)^2 / (f(x + f(x)) - f(x))
+///
+/// converges quadratically near a simple root without needing derivatives.  It
+/// is accepted only when it lands inside the current bracket and reduces the
+/// residual |f|; otherwise a bisection step is taken.  Either way the bracket
+/// is updated by sign, so convergence is guaranteed just as for bisection.
+///
+/// Each Steffensen step starts from the bracket endpoint with the smaller
+/// residual.  If the bracket fails to halve over two iterations, a bisection
+/// step is forced.
+/// Since a tiny step can satisfy a step-size criterion far from the root,
+/// convergence is only accepted once a sign change is confirmed one step length
+/// away.  Each Steffensen attempt costs two evaluations of f.  Since the probe point
+/// x + f(x) mixes x and f(x) units, the step is most effective when f is of
+/// modest magnitude near the root.
+pub fn steffensen_bracketed<F, C>(
+    f: &F,
+    bounds: &Bounds,
+    finish: &C,
+    max_iter: usize,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval,
+    C: IsConverged,
+{
+    check_degenerate(bounds)?;
+    let mut window: Bounds = *bounds;
+    let mut f_a = f.eval_f(window.a);
+    let mut f_b = f.eval_f(window.b);
+
+    // ensure we started with valid bracket
+    check_sign_change(bounds, f_a, f_b)?;
+
+    // bracket sizes from the previous two iterations
+    let mut sz_pre = f64::INFINITY;
+    let mut sz_pre2 = f64::INFINITY;
+
+    let mut x_cur = if f_a.abs() <= f_b.abs() {
+        window.a
+    } else {
+        window.b
+    };
+
+    for _ in 0..max_iter {
+        // accelerate from the endpoint with the smaller residual
+        let (x_base, f_base) = if f_a.abs() <= f_b.abs() {
+            (window.a, f_a)
+        } else {
+            (window.b, f_b)
+        };
+
+        // accelerated step, unless the bracket stopped halving
+        let accelerated = if sz_pre2 * 0.5 < window.size() {
+            None
+        } else {
+            steffensen_step(f, x_base, f_base)
+                .filter(|x_s| window.contains(*x_s))
+                .map(|x_s| (x_s, f.eval_f(x_s)))
+                .filter(|(_, f_s)| f_s.abs() < f_base.abs())
+        };
+        let (x_pre, x_new, f_new) = match accelerated {
+            Some((x_s, f_s)) => (x_base, x_s, f_s),
+            None => {
+                let x_mid = window.middle();
+                (x_cur, x_mid, f.eval_f(x_mid))
+            }
+        };
+        if f_new.is_nan() {
+            return Err(RootError::NonFiniteEvaluation { x: x_new });
+        }
+        if f_new == 0.0 {
+            return Ok(x_new);
+        }
+
+        if is_sign_change(f_a, f_new) {
+            window.b = x_new;
+            f_b = f_new;
+        } else {
+            window.a = x_new;
+            f_a = f_new;
+        }
+
+        // tiny accelerated steps can stall far from the root, so confirm by
+        // probing for a sign change one step length toward the bracket interior
+        if finish.is_converged(x_pre, x_new, f_new) {
+            let delta = (x_new - x_pre).abs();
+            if delta == 0.0 {
+                return Ok(x_new);
+            }
+            let x_probe = if x_new == window.b {
+                (x_new - delta).max(window.a)
+            } else {
+                (x_new + delta).min(window.b)
+            };
+            let f_probe = f.eval_f(x_probe);
+            if f_probe.is_nan() {
+                return Err(RootError::NonFiniteEvaluation { x: x_probe });
+            }
+            if is_sign_change(f_probe, f_new) {
+                return Ok(x_new);
+            }
+            if x_new == window.b {
+                window.b = x_probe;
+                f_b = f_probe;
+            } else {
+                window.a = x_probe;
+                f_a = f_probe;
+            }
+        }
+        sz_pre2 = sz_pre;
+        sz_pre = window.size();
+        x_cur = x_new;
+    }
+    Err(RootError::IterationLimit { last_x: x_cur })
+}
+
+/// Steffensen step from x_cur, or None if it is undefined.
+fn steffensen_step<F>(f: &F, x_cur: f64, f_cur: f64) -> Option<f64>
+where
+    F: RealFnEval,
+{
+    let denom = f.eval_f(x_cur + f_cur) - f_cur;
+    if denom == 0.0 || !denom.is_finite() {
+        return None;
+    }
+    let x_new = x_cur - f_cur * f_cur / denom;
+    if x_new.is_finite() {
+        Some(x_new)
+    } else {
+        None
+    }
+}
+
+/// Illinois variant of Regula Falsi.
+///
+/// Detailed analysis of false position variants is in:
+///
+/// *Ford, J. A. (1995). Improved algorithms of illinois-type for the numerical
+/// solution of nonlinear equations. University of Essex, Department of Computer
+/// Science.*
+///
+/// Falls back to bisection when |f(a) * f(b)| < 1e-12.  Use
+/// `false_position_illinios_eps` for functions of very small or very large
+/// magnitude, or `false_position_illinios_relative_eps` for a threshold which
+/// scales with f.
+///
+/// Unlike `false_position_pegasus`, this keeps the absolute threshold so that
+/// existing callers get the same iterates as before.  The relative flat test
+/// is opt-in through `false_position_illinios_relative_eps`.
+pub fn false_position_illinios<F>(f: &F, bounds: &Bounds, max_iter: usize) -> Result<f64, RootError>
+where
+    F: RealFnEval,
+{
+    false_position_illinios_eps(f, bounds, 1e-12, max_iter)
+}
+
+/// Illinois variant of Regula Falsi with explicit flat-interpolant threshold.
+///
+/// Whenever the (biased) endpoint values satisfy |f(a) * f(b)| < flat_eps, a
+/// bisection step is taken instead of interpolating.  The threshold is compared
+/// against a product of function values, so scaling f by s calls for scaling
+/// flat_eps by s^2.
+pub fn false_position_illinios_eps<F>(
+    f: &F,
+    bounds: &Bounds,
+    flat_eps: f64,
+    max_iter: usize,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval,
+{
+    check_degenerate(bounds)?;
+    assert!(flat_eps >= 0.0);
+    let f_a = f.eval_f(bounds.a);
+    let f_b = f.eval_f(bounds.b);
+    assert!(f_a.is_finite());
+    assert!(f_b.is_finite());
+    check_sign_change(bounds, f_a, f_b)?;
+
+    false_position_illinios_from(f, bounds, f_a, f_b, 0.0, flat_eps, 1.0, max_iter, None)
+}
+
+/// Illinois variant of Regula Falsi with a flat-interpolant threshold relative
+/// to the magnitude of f.
+///
+/// Whenever the (biased) endpoint values satisfy |f(a) * f(b)| < flat_eps * s^2,
+/// with s the larger of |f| at the original endpoints, a bisection step is taken
+/// instead of interpolating.  Scaling f by a constant does not change the
+/// iteration.
+pub fn false_position_illinios_relative_eps<F>(
+    f: &F,
+    bounds: &Bounds,
+    flat_eps: f64,
+    max_iter: usize,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval,
+{
+    check_degenerate(bounds)?;
+    assert!(flat_eps >= 0.0);
+    let f_a = f.eval_f(bounds.a);
+    let f_b = f.eval_f(bounds.b);
+    assert!(f_a.is_finite());
+    assert!(f_b.is_finite());
+    check_sign_change(bounds, f_a, f_b)?;
+
+    let scale = f_a.abs().max(f_b.abs());
+    false_position_illinios_from(f, bounds, f_a, f_b, 0.0, flat_eps, scale, max_iter, None)
+}
+
+/// Pegasus variant of Regula Falsi.
+///
+/// Like Illinois, it stops an endpoint from being retained indefinitely by
+/// scaling down its function value.  Whenever the new point lands on the same
+/// side as the previous one, the kept endpoint's value is multiplied by
+///
+/// f_old / (f_old + f_new)
+///
+/// where f_old belongs to the endpoint being replaced.  This adapts to how far
+/// the iteration moved rather than always halving, and per Ford's comparison
+/// usually needs fewer evaluations than Illinois.
+///
+/// *Dowell, M., & Jarratt, P. (1972). The "Pegasus" method for computing the
+/// root of an equation. BIT Numerical Mathematics, 12(4), 503-508.*
+///
+/// As with `false_position_illinios_relative_eps`, a bisection step is taken
+/// when the interpolant becomes too flat to trust, here with a relative
+/// threshold of 1e-12.  Landing exactly on a root stops the iteration there.
+pub fn false_position_pegasus<F>(f: &F, bounds: &Bounds, max_iter: usize) -> Result<f64, RootError>
+where
+    F: RealFnEval,
+{
+    check_degenerate(bounds)?;
+    let mut f_a = f.eval_f(bounds.a);
+    let mut f_b = f.eval_f(bounds.b);
+    assert!(f_a.is_finite());
+    assert!(f_b.is_finite());
+    check_sign_change(bounds, f_a, f_b)?;
+
+    let mut window: Bounds = *bounds;
+    if false_position_converged(&window) {
+        return Ok(window.middle());
+    }
+
+    // flatness is judged relative to the starting magnitudes
+    let scale = f_a.abs().max(f_b.abs());
+
+    // side replaced most recently, b counts as the newer starting point
+    let mut replaced_b = true;
+    for _ in 0..max_iter {
+        match false_position_step(&window, f_a, f_b, scale, 1e-12) {
+            // interpolant too flat, bisect instead
+            None => {
+                let x_mid = window.middle();
+                let f_mid = f.eval_f(x_mid);
+                if f_mid.is_nan() {
+                    return Err(RootError::NonFiniteEvaluation { x: x_mid });
+                }
+                if is_sign_change(f_a, f_mid) {
+                    window.b = x_mid;
+                    f_b = f_mid;
+                    replaced_b = true;
+                } else {
+                    window.a = x_mid;
+                    f_a = f_mid;
+                    replaced_b = false;
+                }
+            }
+            // false position step
+            Some(x_new) => {
+                let f_new = f.eval_f(x_new);
+                if !f_new.is_finite() {
+                    return Err(RootError::NonFiniteEvaluation { x: x_new });
+                }
+                if f_new == 0.0 {
+                    return Ok(x_new);
+                }
+
+                if is_sign_change(f_a, f_new) {
+                    if replaced_b {
+                        f_a *= f_b / (f_b + f_new);
+                    }
+                    window.b = x_new;
+                    f_b = f_new;
+                    replaced_b = true;
+                } else {
+                    if !replaced_b {
+                        f_b *= f_a / (f_a + f_new);
+                    }
+                    window.a = x_new;
+                    f_a = f_new;
+                    replaced_b = false;
+                }
+            }
+        }
+
+        // convergence criteria
+        if false_position_converged(&window) {
+            return Ok(window.middle());
+        }
+    }
+    Err(RootError::IterationLimit {
+        last_x: window.middle(),
+    })
+}
+
+/// Illinois false position seeded with an initial endpoint bias.
+///
+/// Illinois scales down the function value at the endpoint which has been
+/// retained too long, pulling the interpolant toward it.  This starts the
+/// iteration with such a scaling already in place.  A negative `initial_bias`
+/// scales f(a) by -initial_bias, favoring a, and a positive one scales f(b)
+/// by initial_bias, favoring b.  Zero is plain `false_position_illinios`.
+///
+/// Useful when one endpoint is known to be closer to the root but has the
+/// much larger |f|, as with steep exponentials.  A bias of about -|f(b)/f(a)|
+/// or |f(a)/f(b)| puts both endpoints on an equal footing.  A poorly chosen bias
+/// costs a few extra iterations before the Illinois updates wash it out.
+pub fn false_position_biased<F>(
+    f: &F,
+    bounds: &Bounds,
+    initial_bias: f64,
+    max_iter: usize,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval,
+{
+    check_degenerate(bounds)?;
+    assert!(initial_bias.is_finite());
+    let f_a = f.eval_f(bounds.a);
+    let f_b = f.eval_f(bounds.b);
+    assert!(f_a.is_finite());
+    assert!(f_b.is_finite());
+    check_sign_change(bounds, f_a, f_b)?;
+
+    false_position_illinios_from(
+        f,
+        bounds,
+        f_a,
+        f_b,
+        initial_bias,
+        1e-12,
+        1.0,
+        max_iter,
+        None,
+    )
+}
+
+/// Illinois false position given precomputed f(a) and f(b).
+///
+/// Saves the two endpoint evaluations when the caller already knows them.  The
+/// supplied values must be finite and show a sign change.
+pub fn false_position_illinios_with_endpoints<F>(
+    f: &F,
+    bounds: &Bounds,
+    f_a: f64,
+    f_b: f64,
+    max_iter: usize,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval,
+{
+    check_degenerate(bounds)?;
+    assert!(f_a.is_finite());
+    assert!(f_b.is_finite());
+    check_sign_change(bounds, f_a, f_b)?;
+    false_position_illinios_from(f, bounds, f_a, f_b, 0.0, 1e-12, 1.0, max_iter, None)
+}
+
+/// Illinois false position requiring the bracket to cross zero with a given
+/// orientation.
+///
+/// Returns `RootError::NoSignChange` if f(a) and f(b) do not change sign as
+/// required by `crossing`.
+pub fn false_position_illinios_crossing<F>(
+    f: &F,
+    bounds: &Bounds,
+    crossing: Crossing,
+    max_iter: usize,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval,
+{
+    check_degenerate(bounds)?;
+    let f_a = f.eval_f(bounds.a);
+    let f_b = f.eval_f(bounds.b);
+    assert!(f_a.is_finite());
+    assert!(f_b.is_finite());
+    if !crossing.matches(f_a, f_b) {
+        return Err(RootError::NoSignChange {
+            a: bounds.a,
+            b: bounds.b,
+        });
+    }
+    false_position_illinios_from(f, bounds, f_a, f_b, 0.0, 1e-12, 1.0, max_iter, None)
+}
+
+/// Illinois false position also returning the bracket midpoint after each
+/// iteration, ending with the root.
+///
+/// The trace is reserved up front for `max_iter` entries.
+pub fn false_position_illinios_trace<F>(
+    f: &F,
+    bounds: &Bounds,
+    max_iter: usize,
+) -> Result<(f64, Vec<f64>), RootError>
+where
+    F: RealFnEval,
+{
+    check_degenerate(bounds)?;
+    let f_a = f.eval_f(bounds.a);
+    let f_b = f.eval_f(bounds.b);
+    assert!(f_a.is_finite());
+    assert!(f_b.is_finite());
+    check_sign_change(bounds, f_a, f_b)?;
+
+    let mut trace = Vec::with_capacity(max_iter);
+    let root = false_position_illinios_from(
+        f,
+        bounds,
+        f_a,
+        f_b,
+        0.0,
+        1e-12,
+        1.0,
+        max_iter,
+        Some(&mut trace),
+    )?;
+    Ok((root, trace))
+}
+
+/// Illinois loop given a validated bracket with finite f(a) and f(b), starting
+/// from the given endpoint `bias`.
+///
+/// The interpolant counts as flat when |fga * fgb| < flat_eps * flat_scale^2.
+/// A `flat_scale` of one makes the threshold absolute.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn false_position_illinios_from<F>(
+    f: &F,
+    bounds: &Bounds,
+    f_a: f64,
+    f_b: f64,
+    bias: f64,
+    flat_eps: f64,
+    flat_scale: f64,
+    max_iter: usize,
+    mut trace: Option<&mut Vec<f64>>,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval,
+{
+    let mut window: Bounds = *bounds;
+    let mut f_a = f_a;
+    let mut f_b = f_b;
+
+    // already converged
+    if false_position_converged(&window) {
+        return Ok(window.middle());
+    }
+
+    let mut bias = bias;
+    for _ in 0..max_iter {
+        let fga = if bias < 0.0 { f_a * -bias } else { f_a };
+        let fgb = if bias > 0.0 { f_b * bias } else { f_b };
+
+        match false_position_step(&window, fga, fgb, flat_scale, flat_eps) {
+            // interpolant too flat, bisect instead
+            None => {
+                let x_mid = window.middle();
+                let f_mid = f.eval_f(x_mid);
+                if f_mid.is_nan() {
+                    return Err(RootError::NonFiniteEvaluation { x: x_mid });
+                }
+                if is_sign_change(f_a, f_mid) {
+                    window.b = x_mid;
+                    f_b = f_mid;
+                } else {
+                    window.a = x_mid;
+                    f_a = f_mid;
+                }
+                bias = 0.0;
+            }
+            // false position step
+            Some(x_new) => {
+                let f_new = f.eval_f(x_new);
+                if !f_new.is_finite() {
+                    return Err(RootError::NonFiniteEvaluation { x: x_new });
+                }
+
+                if is_sign_change(f_a, f_new) {
+                    window.b = x_new;
+                    f_b = f_new;
+
+                    if bias >= 0.0 {
+                        bias = -1.0;
+                    } else {
+                        bias *= 0.5;
+                    }
+                } else {
+                    window.a = x_new;
+                    f_a = f_new;
+
+                    if bias <= 0.0 {
+                        bias = 1.0;
+                    } else {
+                        bias *= 0.5;
+                    }
+                }
+            }
+        }
+
+        if let Some(ref mut t) = trace {
+            t.push(window.middle());
+        }
+
+        // convergence criteria
+        if false_position_converged(&window) {
+            return Ok(window.middle());
+        }
+    }
+    Err(RootError::IterationLimit {
+        last_x: window.middle(),
+    })
+}
+
+/// Stopping test of the false position solvers, which take no criteria.
+///
+/// Applied to the starting bracket as well as after every step, so a bracket
+/// that is already tight ends the solve exactly as the iteration would.
+fn false_position_converged(window: &Bounds) -> bool {
+    window.size() < 1e-9
+}
+
+/// Regula falsi interpolant through (a, fga) and (b, fgb), where fga and fgb
+/// are the possibly weighted endpoint values of opposite sign.
+///
+/// Computed as a + (b - a) * t with t = fga / (fga - fgb).  Opposite signs
+/// mean t lies in [0, 1] and the subtraction cannot cancel, so the result
+/// neither overflows nor leaves the bracket however large or small f is.
+///
+/// Returns None when the interpolant is too flat to trust, i.e. when
+/// |fga * fgb| < flat_eps * scale^2 with `scale` the typical magnitude of f,
+/// or when both values are zero.
+/// The comparison is done on scaled values, so is independent of the
+/// magnitude of f and safe from overflow.
+///
+/// Shared by the Illinois and Pegasus variants, the only false position
+/// methods in the crate; there is no Anderson-Björck variant.  Pegasus and
+/// `false_position_illinios_relative_eps` pass the larger starting |f| as
+/// `scale`, while the other Illinois entry points pass 1 for an absolute test.
+fn false_position_step(
+    window: &Bounds,
+    fga: f64,
+    fgb: f64,
+    scale: f64,
+    flat_eps: f64,
+) -> Option<f64> {
+    if (fga / scale).abs() * (fgb / scale).abs() < flat_eps {
+        return None;
+    }
+    let t = fga / (fga - fgb);
+    if !(0.0..=1.0).contains(&t) {
+        return None;
+    }
+    Some(window.a + (window.b - window.a) * t)
+}
+
+/// Root finding via Brent-Dekker method.
+///
+/// Combines bisection, the secant method, and inverse quadratic interpolation.
+/// Interpolation steps are taken when they land well inside the bracket and
+/// the steps are shrinking fast enough; otherwise a bisection step is forced.
+/// The result is superlinear convergence on well-behaved functions without
+/// ever doing much worse than bisection.  Like bisection, convergence is
+/// guaranteed given a valid bracket.
+///
+/// Iteration stops once the bracket is narrower than about 1e-9, matching
+/// `bisection`, or on hitting f(x) == 0 exactly.
+///
+/// *Brent, R. P. (1973). Algorithms for minimization without derivatives.
+/// Prentice-Hall, chapter 4.*
+///
+pub fn brent_dekker<F>(f: &F, bounds: &Bounds, max_iter: usize) -> Result<f64, RootError>
+where
+    F: RealFnEval,
+{
+    check_degenerate(bounds)?;
+    let (mut a, mut b) = (bounds.a, bounds.b);
+    let mut f_a = f.eval_f(a);
+    let mut f_b = f.eval_f(b);
+
+    // ensure we started with valid bracket
+    check_sign_change(bounds, f_a, f_b)?;
+
+    // b is the best estimate, c the contrapoint with f(c) of opposite sign, and
+    // a the previous b.  The d and e are the latest and previous step sizes.
+    let mut c = a;
+    let mut f_c = f_a;
+    let mut d = b - a;
+    let mut e = d;
+
+    for _ in 0..max_iter {
+        // keep the root bracketed between b and c
+        if !is_sign_change(f_b, f_c) {
+            c = a;
+            f_c = f_a;
+            d = b - a;
+            e = d;
+        }
+        if f_c.abs() < f_b.abs() {
+            a = b;
+            b = c;
+            c = a;
+            f_a = f_b;
+            f_b = f_c;
+            f_c = f_a;
+        }
+
+        // convergence criteria
+        let tol = 2.0 * f64::EPSILON * b.abs() + 0.5e-9;
+        let m = 0.5 * (c - b);
+        if m.abs() <= tol || f_b == 0.0 {
+            return Ok(b);
+        }
+
+        if e.abs() < tol || f_a.abs() <= f_b.abs() {
+            // previous step too small or not improving, so bisect
+            d = m;
+            e = m;
+        } else {
+            let s = f_b / f_a;
+            let (mut p, mut q) = if a == c {
+                // secant
+                (2.0 * m * s, 1.0 - s)
+            } else {
+                // inverse quadratic interpolation
+                let q = f_a / f_c;
+                let r = f_b / f_c;
+                (
+                    s * (2.0 * m * q * (q - r) - (b - a) * (r - 1.0)),
+                    (q - 1.0) * (r - 1.0) * (s - 1.0),
+                )
+            };
+            if p > 0.0 {
+                q = -q;
+            } else {
+                p = -p;
+            }
+
+            // accept interpolation only if well inside the bracket and the
+            // step is less than half the one before last
+            if 2.0 * p < (3.0 * m * q - (tol * q).abs()).min((e * q).abs()) {
+                e = d;
+                d = p / q;
+            } else {
+                d = m;
+                e = m;
+            }
+        }
+
+        a = b;
+        f_a = f_b;
+        b += if d.abs() > tol { d } else { tol.copysign(m) };
+        f_b = f.eval_f(b);
+        if f_b.is_nan() {
+            return Err(RootError::NonFiniteEvaluation { x: b });
+        }
+    }
+    Err(RootError::IterationLimit { last_x: b })
+}
+
+/// Root finding via Ridders' method.
+///
+/// Each iteration evaluates f at the bracket midpoint and fits an exponential
+/// through the three points, which removes the curvature before taking a
+/// false position step:
+///
+/// x_new = x_mid + (x_mid - a) * sign(f(a) - f(b)) * f(x_mid) / sqrt(f(x_mid)^2 - f(a)*f(b))
+///
+/// Convergence is quadratic using only f(x), at two evaluations per iteration.
+/// The bracket is updated to keep the sign change and at least halves each
+/// iteration, so convergence is guaranteed given a valid bracket.  Iteration
+/// stops once the bracket is narrower than 1e-9, matching `bisection`, or on
+/// hitting f(x) == 0 exactly.
+///
+/// *Ridders, C. (1979). A new algorithm for computing a single root of a real
+/// continuous function. IEEE Transactions on circuits and systems, 26(11),
+/// 979-980.*
+///
+pub fn ridders<F>(f: &F, bounds: &Bounds, max_iter: usize) -> Result<f64, RootError>
+where
+    F: RealFnEval,
+{
+    check_degenerate(bounds)?;
+    let mut window: Bounds = *bounds;
+    let mut f_a = f.eval_f(window.a);
+    let mut f_b = f.eval_f(window.b);
+
+    // ensure we started with valid bracket
+    check_sign_change(bounds, f_a, f_b)?;
+
+    for _ in 0..max_iter {
+        let mid = window.middle();
+        let f_mid = f.eval_f(mid);
+        if f_mid.is_nan() {
+            return Err(RootError::NonFiniteEvaluation { x: mid });
+        }
+
+        // fall back to bisection if the square root is not well defined
+        let disc = f_mid * f_mid - f_a * f_b;
+        let x_new = if disc > 0.0 {
+            mid + (mid - window.a) * (f_a - f_b).signum() * f_mid / disc.sqrt()
+        } else {
+            mid
+        };
+        if !x_new.is_finite() {
+            return Err(RootError::IteratedToNaN { x_new });
+        }
+        let f_new = f.eval_f(x_new);
+        if f_new.is_nan() {
+            return Err(RootError::NonFiniteEvaluation { x: x_new });
+        }
+        if f_new == 0.0 {
+            return Ok(x_new);
+        }
+
+        // keep the sign change, using the midpoint where possible
+        if is_sign_change(f_mid, f_new) {
+            if mid < x_new {
+                window = Bounds::new(mid, x_new);
+                f_a = f_mid;
+                f_b = f_new;
+            } else {
+                window = Bounds::new(x_new, mid);
+                f_a = f_new;
+                f_b = f_mid;
+            }
+        } else if is_sign_change(f_a, f_new) {
+            window.b = x_new;
+            f_b = f_new;
+        } else {
+            window.a = x_new;
+            f_a = f_new;
+        }
+
+        // convergence criteria
+        if window.size() < 1e-9 {
+            return Ok(x_new);
+        }
+    }
+    Err(RootError::IterationLimit {
+        last_x: window.middle(),
+    })
+}
+
+/// Root finding via the ITP (Interpolate, Truncate, Project) method.
+///
+/// Each iteration takes a regula falsi estimate, truncates it toward the
+/// bracket midpoint by `k1 * (b - a)^k2`, then projects it into a ball around
+/// the midpoint whose radius shrinks as the iteration budget is spent.  The
+/// result converges superlinearly on well-behaved functions yet never needs
+/// more than `n0` iterations beyond what bisection would.
+///
+/// `k1 > 0` and `1 <= k2 < 1 + φ` tune the truncation.  The authors suggest
+/// `k1 = 0.2 / (b - a)` and `k2 = 2`.  With `n0 = 0` the worst case matches
+/// bisection exactly, while a small positive `n0` (e.g. 1) lets interpolation
+/// steps run longer before the projection forces them back.
+///
+/// Iteration stops once the bracket is no wider than 1e-9, or on hitting
+/// f(x) == 0 exactly.  That takes at most `bisection_iterations_for(bounds,
+/// 1e-9) + n0` iterations.
+///
+/// *Oliveira, I. F. D., & Takahashi, R. H. C. (2020). An Enhancement of the
+/// Bisection Method Average Performance Preserving Minmax Optimality. ACM
+/// Transactions on Mathematical Software, 47(1), 1-24.*
+///
+pub fn itp<F>(
+    f: &F,
+    bounds: &Bounds,
+    k1: f64,
+    k2: f64,
+    n0: usize,
+    max_iter: usize,
+) -> Result<f64, RootError>
+where
+    F: RealFnEval,
+{
+    assert!(k1 > 0.0 && k1.is_finite());
+    assert!(
+        (1.0..2.618).contains(&k2),
+        "k2 must lie in [1, 1 + golden ratio)"
+    );
+
+    check_degenerate(bounds)?;
+    let mut window: Bounds = *bounds;
+    let mut f_a = f.eval_f(window.a);
+    let mut f_b = f.eval_f(window.b);
+
+    // ensure we started with valid bracket
+    check_sign_change(bounds, f_a, f_b)?;
+
+    // half of the 1e-9 final bracket width
+    let eps = 0.5e-9;
+    let n_max = bisection_iterations_for(bounds, 2.0 * eps) + n0;
+
+    // the projection spends the whole budget, so allow a few ulps of rounding
+    let tight = |w: &Bounds| w.size() <= 2.0 * eps + 4.0 * f64::EPSILON * w.a.abs().max(w.b.abs());
+
+    for j in 0..max_iter {
+        // convergence criteria
+        if tight(&window) {
+            return Ok(window.middle());
+        }
+
+        let (a, b) = (window.a, window.b);
+        let x_half = window.middle();
+        let r = eps * 2f64.powi((n_max as i32) - (j as i32)) - 0.5 * (b - a);
+        let delta = k1 * (b - a).powf(k2);
+
+        // interpolate
+        let mut x_f = (b * f_a - a * f_b) / (f_a - f_b);
+        if !x_f.is_finite() {
+            x_f = x_half;
+        }
+
+        // truncate
+        let sigma = (x_half - x_f).signum();
+        let x_t = if delta <= (x_half - x_f).abs() {
+            x_f + sigma * delta
+        } else {
+            x_half
+        };
+
+        // project
+        let x_itp = if (x_t - x_half).abs() <= r {
+            x_t
+        } else {
+            x_half - sigma * r
+        };
+
+        let f_itp = f.eval_f(x_itp);
+        if f_itp.is_nan() {
+            return Err(RootError::NonFiniteEvaluation { x: x_itp });
+        }
+        if f_itp == 0.0 {
+            return Ok(x_itp);
+        }
+
+        if is_sign_change(f_a, f_itp) {
+            window.b = x_itp;
+            f_b = f_itp;
+        } else {
+            window.a = x_itp;
+            f_a = f_itp;
+        }
+    }
+
+    if tight(&window) {
+        return Ok(window.middle());
+    }
+    Err(RootError::IterationLimit {
+        last_x: window.middle(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use convergence::{
+        AdaptiveResidual, BoxedCriteria, BracketAndResidual, ClosureCriteria, DeltaX, DualCriteria,
+        EitherCriteria, FnResidual, MinIterations, MultiCriteria, RecordingDeltaX,
+        SignificantDigits, Stagnation,
+    };
+    use std::cell::{Cell, RefCell};
+    use wrap::{
+        ComplexFn, ComplexFnAndFirst, CountingFn, RealFn, RealFnAndFirst, RealFnAndFirstSecond,
+        RealFnNumericalDiff, RealFnThroughThird,
+    };
+
+    struct RootTest {
+        name: String,
+        f: Box<dyn Fn(f64) -> f64>,
+        df: Box<dyn Fn(f64) -> f64>,
+        d2f: Box<dyn Fn(f64) -> f64>,
+        roots: Vec<f64>,
+        guesses: Vec<f64>,
+        brackets: Vec<Bounds>,
+    }
+
+    /// The Ford95 tests are from:
+    ///
+    /// *Ford, J. A. (1995). Improved algorithms of illinois-type for the numerical
+    /// solution of nonlinear equations. University of Essex, Department of Computer
+    /// Science.*
+    ///
+    fn make_root_tests_ford95() -> Vec<RootTest> {
+        vec![
+            RootTest {
+                name: "Ford95 Example One".to_owned(),
+                f: Box::new(|x| 4. * x.cos() - x.exp()),
+                df: Box::new(|x| -4. * x.sin() - x.exp()),
+                d2f: Box::new(|x| -4. * x.cos() - x.exp()),
+                roots: vec![0.90478821787302],
+                guesses: vec![5.0],
+                brackets: vec![Bounds::new(-1.5, 6.0)],
+            },
+            RootTest {
+                name: "Ford95 Example Three".to_owned(),
+                f: Box::new(|x| 2. * x * (-20f64).exp() + 1. - 2. * (-20. * x).exp()),
+                df: Box::new(|x| 40. * (-20. * x).exp() + 2. * (20f64).exp().recip()),
+                d2f: Box::new(|x| -800. * (-20. * x).exp()),
+                roots: vec![0.034657358821882],
+                guesses: vec![-2.5],
+                brackets: vec![Bounds::new(-1.0, 4.0)],
+            },
+            RootTest {
+                name: "Ford95 Example Four".to_owned(),
+                f: Box::new(|x| (x.recip() - 25.).exp() - 1.),
+                df: Box::new(|x| -(x.recip() - 25.).exp() * x.powi(-2)),
+                d2f: Box::new(|x| (x.recip() - 25.).exp() * (2. * x + 1.) * x.powi(-4)),
+                roots: vec![0.04],
+                guesses: vec![0.035],
+                brackets: vec![Bounds::new(0.02, 1.0)],
+            },
+            RootTest {
+                name: "Ford95 Example Six".to_owned(),
+                f: Box::new(|x| 10000000000. * x.powf(x.recip()) - 1.0),
+                df: Box::new(|x| -10000000000. * x.powf(x.recip() - 2.) * (x.ln() - 1.)),
+                d2f: Box::new(|x| {
+                    10000000000. * x.powf(x.recip() - 4.)
+                        * (-3. * x + x.ln().powi(2) + 2. * (x - 1.) * x.ln() + 1.)
+                }),
+                roots: vec![0.1],
+                guesses: vec![0.15],
+                brackets: vec![Bounds::new(0.05, 0.2)],
+            },
+            RootTest {
+                name: "Ford95 Example Seven".to_owned(),
+                f: Box::new(|x| x.powi(20) - 1.),
+                df: Box::new(|x| 20. * x.powi(19)),
+                d2f: Box::new(|x| 380. * x.powi(18)),
+                roots: vec![1.0],
+                guesses: vec![1.2],
+                brackets: vec![Bounds::new(-0.5, 5.0)],
+            },
+            RootTest {
+                name: "Ford95 Example Eight".to_owned(),
+                f: Box::new(|x| (21000. / x).exp() / (1.11 * 100000000000. * x * x) - 1.),
+                df: Box::new(|x| {
+                    (-1.8018e-11 * (21000. / x).exp() * (x + 10500.)) / (x * x * x * x)
+                }),
+                d2f: Box::new(|x| {
+                    (21000. / x).exp() * (5.40541e-11 * x * x + 1.13514e-6 * x + 0.00397297)
+                        / x.powi(6)
+                }),
+                roots: vec![551.77382493033],
+                guesses: vec![400.0],
+                brackets: vec![Bounds::new(350.0, 850.0)],
+            },
+            RootTest {
+                name: "Ford95 Example Nine".to_owned(),
+                f: Box::new(|x| x.recip() + x.ln() - 100.),
+                df: Box::new(|x| (x - 1.0) / (x * x)),
+                d2f: Box::new(|x| (2. - x) / (x * x * x)),
+                roots: vec![0.0095556044375379],
+                guesses: vec![0.01],
+                brackets: vec![Bounds::new(0.001, 100.0)],
+            },
+            RootTest {
+                name: "Ford95 Example Ten".to_owned(),
+                f: Box::new(|x| x.exp().exp() - (1.0f64).exp().exp()),
+                df: Box::new(|x| (x + x.exp()).exp()),
+                d2f: Box::new(|x| (x + x.exp()).exp() * (x.exp() + 1.)),
+                roots: vec![1.0],
+                guesses: vec![1.8],
+                brackets: vec![Bounds::new(0.5, 3.5)],
+            },
+            RootTest {
+                name: "Ford95 Example Eleven".to_owned(),
+                f: Box::new(|x| (0.01 / x).sin() - 0.01),
+                df: Box::new(|x| -0.01 * (0.01 / x).cos() / (x * x)),
+                d2f: Box::new(|x| {
+                    (0.02 * x * (0.01 / x).cos() - 0.0001 * (0.01 / x).sin()) / (x * x * x * x)
+                }),
+                roots: vec![0.99998333286109],
+                guesses: vec![0.55],
+                brackets: vec![Bounds::new(0.004, 200.0)],
+            },
+        ]
+    }
+
+    /// The Costabile06 tests are from:
+    ///
+    /// *Costabile, F., Gualtieri, M. I., & Luceri, R. (2006). A modification of
+    /// Muller’s method. Calcolo, 43(1), 39-50.*
+    ///
+    /// They include cases which are particularly tough for pure forms of
+    /// Newton-Raphson and Halley's Method.  See the code comments on examples
+    /// twenty two through twenty eight.  These all come from:
+    ///
+    // reference roots keep every published digit
+    #[allow(clippy::excessive_precision)]
+    fn make_root_tests_costabile06() -> Vec<RootTest> {
+        vec![
+            RootTest {
+                name: "Costabile06 Example One".to_owned(),
+                f: Box::new(|x| x * x * x - 1.),
+                df: Box::new(|x| 3. * x * x),
+                d2f: Box::new(|x| 6. * x),
+                roots: vec![1.0],
+                guesses: vec![0.1],
+                brackets: vec![Bounds::new(0.1, 1.3)],
+            },
+            RootTest {
+                name: "Costabile06 Example Two".to_owned(),
+                f: Box::new(|x| {
+                    x * x * (x * x / 3. + 2.0f64.sqrt() * x.sin()) - 3.0f64.sqrt() / 18.
+                }),
+                df: Box::new(|x| {
+                    4. * x * x * x / 3. + 2.0f64.sqrt() * x * x * x.cos()
+                        + 2. * 2.0f64.sqrt() * x * x.sin()
+                }),
+                d2f: Box::new(|x| {
+                    4. * x * x - 2.0f64.sqrt() * x * x * x.sin() + 2. * 2.0f64.sqrt() * x.sin()
+                        + 4. * 2.0f64.sqrt() * x * x.cos()
+                }),
+                roots: vec![0.39942229171096819451],
+                guesses: vec![1.0],
+                brackets: vec![Bounds::new(0.1, 1.0)],
+            },
+            RootTest {
+                name: "Costabile06 Example Three".to_owned(),
+                f: Box::new(|x| 2. * x * (-10.0f64).exp() + 1. - 2. * (-10. * x).exp()),
+                df: Box::new(|x| 20. * (-10. * x).exp() + 2. * (-10.0f64).exp()),
+                d2f: Box::new(|x| -200. * (-10. * x).exp()),
+                roots: vec![0.069314088687023473303],
+                guesses: vec![0.0],
+                brackets: vec![Bounds::new(0.0, 1.0)],
+            },
+            RootTest {
+                name: "Costabile06 Example Four".to_owned(),
+                f: Box::new(|x| 2. * x * (-20.0f64).exp() + 1. - 2. * (-20. * x).exp()),
+                df: Box::new(|x| 40. * (-20. * x).exp() + 2. * (-20.0f64).exp()),
+                d2f: Box::new(|x| -800. * (-20. * x).exp()),
+                roots: vec![0.034657359020853851362],
+                guesses: vec![0.2],
+                brackets: vec![Bounds::new(0.0, 1.0)],
+            },
+            RootTest {
+                name: "Costabile06 Example Five".to_owned(),
+                f: Box::new(|x| (1. + (1. - 5.0f64).powi(2)) * x * x - (1. - 5. * x).powi(2)),
+                df: Box::new(|x| 10. - 16. * x),
+                d2f: Box::new(|_| -16.),
+                roots: vec![0.109611796797792],
+                guesses: vec![0.4],
+                brackets: vec![Bounds::new(0.0, 1.0)],
+            },
+            RootTest {
+                name: "Costabile06 Example Six".to_owned(),
+                f: Box::new(|x| (1. + (1. - 10.0f64).powi(2)) * x * x - (1. - 10. * x).powi(2)),
+                df: Box::new(|x| 20. - 36. * x),
+                d2f: Box::new(|_| -36.),
+                roots: vec![0.0524786034368102],
+                guesses: vec![0.4],
+                brackets: vec![Bounds::new(0.0, 1.0)],
+            },
+            RootTest {
+                name: "Costabile06 Example Seven".to_owned(),
+                f: Box::new(|x| (1. + (1. - 20.0f64).powi(2)) * x * x - (1. - 20. * x).powi(2)),
+                df: Box::new(|x| 40. - 76. * x),
+                d2f: Box::new(|_| -76.),
+                roots: vec![0.0256237476199882],
+                guesses: vec![0.4],
+                brackets: vec![Bounds::new(0.0, 1.0)],
+            },
+            RootTest {
+                name: "Costabile06 Example Eight".to_owned(),
+                f: Box::new(|x| x * x - (1. - x).powi(5)),
+                df: Box::new(|x| 5. * (1. - x).powi(4) + 2. * x),
+                d2f: Box::new(|x| 20. * (1. - x).powi(3) + 2.),
+                roots: vec![0.34595481584824201796],
+                guesses: vec![1.0],
+                brackets: vec![Bounds::new(0.0, 1.0)],
+            },
+            RootTest {
+                name: "Costabile06 Example Nine".to_owned(),
+                f: Box::new(|x| (1. + (1. - 5.0f64).powi(4)) * x - (1. - 5. * x).powi(4)),
+                df: Box::new(|x| 20. * (1. - 5. * x).powi(3) + 257.),
+                d2f: Box::new(|x| -300. * (1. - 5. * x).powi(2)),
+                roots: vec![0.00361710817890406],
+                guesses: vec![0.5],
+                brackets: vec![Bounds::new(0.0, 1.0)],
+            },
+            RootTest {
+                name: "Costabile06 Example Ten".to_owned(),
+                f: Box::new(|x| (1. + (1. - 10.0f64).powi(4)) * x - (1. - 10. * x).powi(4)),
+                df: Box::new(|x| 40. * (1. - 10. * x).powi(3) + 6562.),
+                d2f: Box::new(|x| -1200. * (1. - 10. * x).powi(2)),
+                roots: vec![0.000151471],
+                guesses: vec![0.5],
+                brackets: vec![Bounds::new(0.0, 1.0)],
+            },
+            RootTest {
+                name: "Costabile06 Example Eleven".to_owned(),
+                f: Box::new(|x| (1. + (1. - 20.0f64).powi(4)) * x - (1. - 20. * x).powi(4)),
+                df: Box::new(|x| 80. * (1. - 20. * x).powi(3) + 130322.),
+                d2f: Box::new(|x| -4800. * (1. - 20. * x).powi(2)),
+                roots: vec![7.6686e-6],
+                guesses: vec![0.5],
+                brackets: vec![Bounds::new(0.0, 1.0)],
+            },
+            RootTest {
+                name: "Costabile06 Example Twelve".to_owned(),
+                f: Box::new(|x| x * x + (x / 5.).sin() - 0.25),
+                df: Box::new(|x| 2. * x + (1. / 5.) * (x / 5.).cos()),
+                d2f: Box::new(|x| 2. - (1. / 25.) * (x / 5.).sin()),
+                roots: vec![0.40999201798913713162125838],
+                guesses: vec![0.0],
+                brackets: vec![Bounds::new(0.0, 1.0)],
+            },
+            RootTest {
+                name: "Costabile06 Example Thirteen".to_owned(),
+                f: Box::new(|x| x * x + (x / 10.).sin() - 0.25),
+                df: Box::new(|x| 2. * x + (1. / 10.) * (x / 10.).cos()),
+                d2f: Box::new(|x| 2. - (1. / 100.) * (x / 100.).sin()),
+                roots: vec![0.45250914557764122545806719],
+                guesses: vec![0.0],
+                brackets: vec![Bounds::new(0.0, 1.0)],
+            },
+            RootTest {
+                name: "Costabile06 Example Fourteen".to_owned(),
+                f: Box::new(|x| x * x + (x / 20.).sin() - 0.25),
+                df: Box::new(|x| 2. * x + (1. / 20.) * (x / 20.).cos()),
+                d2f: Box::new(|x| 2. - (1. / 400.) * (x / 20.).sin()),
+                roots: vec![0.47562684859606241311984234],
+                guesses: vec![0.0],
+                brackets: vec![Bounds::new(0.0, 1.0)],
+            },
+            RootTest {
                 name: "Costabile06 Example Fifteen".to_owned(),
                 f: Box::new(|x| (5. * x - 1.) / (4. * x)),
                 df: Box::new(|x| 1. * (4. * x * x).recip()),
-                d2f: Box::new(|x| -1. * (2. * x * x * x).recip()),
+                d2f: Box::new(|x| -(2. * x * x * x).recip()),
                 roots: vec![0.2],
                 guesses: vec![0.375],
                 brackets: vec![Bounds::new(0.01, 1.0)],
@@ -591,429 +3566,2665 @@ mod tests {
                 ],
             },
             RootTest {
-                name: "Costabile06 Example Twenty Two, Twenty Eight".to_owned(),
-                f: Box::new(|x| x.powi(20) - 1.0),
-                df: Box::new(|x| 20. * x.powi(19)),
-                d2f: Box::new(|x| 380. * x.powi(18)),
-                roots: vec![1.0, -1.0],
-                guesses: vec![0.7, -0.7], // NR narrower converging region than Halley
-                brackets: vec![Bounds::new(0.5, 2.0), Bounds::new(-2.0, 0.5)],
+                name: "Costabile06 Example Twenty Two, Twenty Eight".to_owned(),
+                f: Box::new(|x| x.powi(20) - 1.0),
+                df: Box::new(|x| 20. * x.powi(19)),
+                d2f: Box::new(|x| 380. * x.powi(18)),
+                roots: vec![1.0, -1.0],
+                guesses: vec![0.7, -0.7], // NR narrower converging region than Halley
+                brackets: vec![Bounds::new(0.5, 2.0), Bounds::new(-2.0, 0.5)],
+            },
+            RootTest {
+                name: "Costabile06 Example Twenty Three".to_owned(),
+                f: Box::new(|x| (x - 1.).powi(3) * x.exp()),
+                df: Box::new(|x| (x - 1.).powi(2) * x.exp() * (x + 2.)),
+                d2f: Box::new(|x| x.exp() * (x * x * x + 3. * x * x - 3. * x - 1.)),
+                roots: vec![1.0],
+                guesses: vec![0.9999999995], // NR/Halley take tiny steps near root
+                brackets: vec![Bounds::new(0.5, 2.0)],
+            },
+            RootTest {
+                name: "Costabile06 Example Twenty Four".to_owned(),
+                f: Box::new(|x| (x - 1.).powi(5) * x.exp()),
+                df: Box::new(|x| (x - 1.).powi(4) * x.exp() * (x + 4.)),
+                d2f: Box::new(|x| x.exp() * (x - 1.).powi(3) * (x * x + 8. * x + 11.)),
+                roots: vec![1.0],
+                guesses: vec![1.0000000005], // NR/Halley take tiny steps near root
+                brackets: vec![Bounds::new(0.5, 2.0)],
+            },
+            RootTest {
+                name: "Costabile06 Example Twenty Five".to_owned(),
+                f: Box::new(|x| (10. * x - 1.) / (9. * x)),
+                df: Box::new(|x| (9. * x * x).recip()),
+                d2f: Box::new(|x| -2. * (9. * x * x * x).recip()),
+                roots: vec![0.1],
+                guesses: vec![0.01], // hard for iterative methods coming from right
+                brackets: vec![Bounds::new(0.01, 1.0)],
+            },
+            RootTest {
+                name: "Costabile06 Example Twenty Six".to_owned(),
+                f: Box::new(|x| (20. * x - 1.) / (19. * x)),
+                df: Box::new(|x| (19. * x * x).recip()),
+                d2f: Box::new(|x| -2. * (19. * x * x * x).recip()),
+                roots: vec![0.05],
+                guesses: vec![0.06], // hard for iterative methods coming from right
+                brackets: vec![Bounds::new(0.01, 1.0)],
+            },
+            RootTest {
+                name: "Costabile06 Example Twenty Seven".to_owned(),
+                f: Box::new(|x| (-x).exp() + x.cos()),
+                df: Box::new(|x| -(-x).exp() - x.sin()),
+                d2f: Box::new(|x| (-x).exp() - x.cos()),
+                roots: vec![1.74613953040801241765070309],
+                guesses: vec![1.746139531], // iterative methods suffer here
+                brackets: vec![Bounds::new(1.0, 2.0)],
+            },
+        ]
+    }
+
+    /// The Dowell71 tests are from:
+    ///
+    /// *Dowell, M., & Jarratt, P. (1971). A modified regula falsi method for
+    /// computing the root of an equation. BIT Numerical Mathematics, 11(2),
+    /// 168-174.*
+    ///
+    fn make_root_tests_dowell71() -> Vec<RootTest> {
+        let mut cases = Vec::new();
+
+        // Tbl 2 - no turning points or inflections in [0,1]
+        let roots = [
+            0.422477709641236,
+            0.138257155056824,
+            0.046209810152571,
+            0.034657359020853,
+        ];
+        for (i, ni) in [1, 5, 15, 20].iter().enumerate() {
+            let name = format!("Dowell71 Table 2 for n={}", ni);
+            let n = *ni as f64;
+            let f = move |x: f64| 2. * x * (-n).exp() + 1. - 2. * (-n * x).exp();
+            let df = move |x: f64| 2. * (n * (-n * x).exp() + (-n).exp());
+            let d2f = move |x: f64| -2. * n * n * (-n * x).exp();
+
+            cases.push(RootTest {
+                name,
+                f: Box::new(f),
+                df: Box::new(df),
+                d2f: Box::new(d2f),
+                roots: vec![roots[i]],
+                guesses: vec![0.1],
+                brackets: vec![Bounds::new(0., 1.)],
+            });
+        }
+
+        // Tbl 3 - one turning point on [0,1].
+        for ni in [2, 5, 15, 20].iter() {
+            let name = format!("Dowell71 Table 3 for n={}", ni);
+            let n = *ni as f64;
+            let f = move |x: f64| (1. + (1. - n).powi(2)) * x - (1. - n * x).powi(2);
+            let df = move |x: f64| n * n * (1. - 2. * x) + 2.;
+            let d2f = move |_| -2. * n * n;
+
+            let root = (-(n * n * n * n + 4.).sqrt() + n * n + 2.) / (2. * n * n);
+
+            cases.push(RootTest {
+                name,
+                f: Box::new(f),
+                df: Box::new(df),
+                d2f: Box::new(d2f),
+                roots: vec![root],
+                guesses: vec![0.1],
+                brackets: vec![Bounds::new(0., 1.)],
+            });
+        }
+
+        // Tbl 4 - one inflection on [0,1].
+        let roots = [0.5, 0.345954815848242, 0.195547623536565, 0.164920957276441];
+        for (i, ni) in [2, 5, 15, 20].iter().enumerate() {
+            let name = format!("Dowell71 Table 4 for n={}", ni);
+            let n = *ni as f64;
+            let f = move |x: f64| x * x - (1. - x).powi(n as i32);
+            let df = move |x: f64| n * (1. - x).powi((n as i32) - 1) + 2. * x;
+            let d2f = move |x: f64| 2. - (n - 1.) * n * (1. - x).powi((n as i32) - 2);
+
+            cases.push(RootTest {
+                name,
+                f: Box::new(f),
+                df: Box::new(df),
+                d2f: Box::new(d2f),
+                roots: vec![roots[i]],
+                guesses: vec![0.1],
+                brackets: vec![Bounds::new(0., 1.)],
+            });
+        }
+
+        // Tbl 5 - one turning point and one inflection on [0,1].
+        let roots = [
+            0.13775402050032426,
+            0.0036171081783322734,
+            2.598957598820562e-05,
+            7.668594662391115e-06,
+        ];
+        for (i, ni) in [2, 5, 15, 20].iter().enumerate() {
+            let name = format!("Dowell71 Table 5 for n={}", ni);
+            let n = *ni as f64;
+            let f = move |x: f64| (1. + (1. - n).powi(4)) * x - (1. - n * x).powi(4);
+            let df = move |x: f64| -4. * n * (n * x - 1.).powi(3) + (n - 1.).powi(4) + 1.;
+            let d2f = move |x: f64| -12. * n * n * (n * x - 1.).powi(2);
+
+            cases.push(RootTest {
+                name,
+                f: Box::new(f),
+                df: Box::new(df),
+                d2f: Box::new(d2f),
+                roots: vec![roots[i]],
+                guesses: vec![0.1],
+                brackets: vec![Bounds::new(0., 1.)],
+            });
+        }
+
+        // Tbl 6 - curves laying increasingly close to the x-axis for large n
+        let roots = [
+            0.4010581375414404,
+            0.5161535187571644,
+            0.5395222269080477,
+            0.5481822943411316,
+        ];
+        for (i, ni) in [1, 5, 10, 15].iter().enumerate() {
+            let name = format!("Dowell71 Table 6 for n={}", ni);
+            let n = *ni as f64;
+            let f = move |x: f64| (-n * x).exp() * (x - 1.) + x.powi(n as i32);
+            let df = move |x: f64| n * x.powi((n as i32) - 1) + (-n * x).exp() * (n * -x + n + 1.);
+            let d2f = move |x: f64| {
+                n * ((n - 1.) * x.powi((n as i32) - 2) + (-n * x).exp() * (n * (x - 1.) - 2.))
+            };
+
+            cases.push(RootTest {
+                name,
+                f: Box::new(f),
+                df: Box::new(df),
+                d2f: Box::new(d2f),
+                roots: vec![roots[i]],
+                guesses: vec![0.1],
+                brackets: vec![Bounds::new(0., 1.)],
+            });
+        }
+
+        // Tbl 7 - curves with y-axis asymptotic
+        for ni in [2, 5, 15, 20].iter() {
+            let name = format!("Dowell71 Table 7 for n={}", ni);
+            let n = *ni as f64;
+            let f = move |x: f64| (n * x - 1.) / ((n - 1.) * x);
+            let df = move |x: f64| 1. / ((n - 1.) * x * x);
+            let d2f = move |x: f64| -2. / ((n - 1.) * x * x * x);
+
+            let root = 1. / n;
+
+            cases.push(RootTest {
+                name,
+                f: Box::new(f),
+                df: Box::new(df),
+                d2f: Box::new(d2f),
+                roots: vec![root],
+                guesses: vec![0.01],
+                brackets: vec![Bounds::new(0.01, 1.0)],
+            });
+        }
+
+        cases
+    }
+
+    /// These tests have miscellaneous pedigrees.  Some are originals.
+    ///
+    // reference roots keep every published digit
+    #[allow(clippy::excessive_precision)]
+    fn make_root_tests_misc() -> Vec<RootTest> {
+        vec![
+            RootTest {
+                name: "Factored Parabola".to_owned(),
+                f: Box::new(|x| (x - 5.0) * (x - 4.0)),
+                df: Box::new(|x| 2.0 * x - 9.0),
+                d2f: Box::new(|_| 2.0),
+                roots: vec![5.0, 4.0],
+                guesses: vec![5.8, 3.8],
+                brackets: vec![Bounds::new(4.5, 100.0), Bounds::new(-100000.0, 4.01)],
+            },
+            RootTest {
+                name: "Wikipedia NR Parabola".to_owned(),
+                f: Box::new(|x| x * x - 612.0),
+                df: Box::new(|x| 2.0 * x),
+                d2f: Box::new(|_| 2.0),
+                roots: vec![-24.7386337537, 24.7386337537],
+                guesses: vec![-10.0, 10.0],
+                brackets: vec![Bounds::new(-30.0, 10.0), Bounds::new(10.0, 30.0)],
             },
             RootTest {
-                name: "Costabile06 Example Twenty Three".to_owned(),
-                f: Box::new(|x| (x - 1.).powi(3) * x.exp()),
-                df: Box::new(|x| (x - 1.).powi(2) * x.exp() * (x + 2.)),
-                d2f: Box::new(|x| x.exp() * (x * x * x + 3. * x * x - 3. * x - 1.)),
-                roots: vec![1.0],
-                guesses: vec![0.9999999995], // NR/Halley take tiny steps near root
-                brackets: vec![Bounds::new(0.5, 2.0)],
+                name: "Wikipedia NR Trigonometry".to_owned(),
+                f: Box::new(|x| x.cos() - x * x * x),
+                df: Box::new(|x| -x.sin() - 3. * x * x),
+                d2f: Box::new(|x| -x.cos() - 6. * x),
+                roots: vec![0.865474033102],
+                guesses: vec![0.5],
+                brackets: vec![Bounds::new(0.0, 1.0)],
             },
             RootTest {
-                name: "Costabile06 Example Twenty Four".to_owned(),
-                f: Box::new(|x| (x - 1.).powi(5) * x.exp()),
-                df: Box::new(|x| (x - 1.).powi(4) * x.exp() * (x + 4.)),
-                d2f: Box::new(|x| x.exp() * (x - 1.).powi(3) * (x * x + 8. * x + 11.)),
-                roots: vec![1.0],
-                guesses: vec![1.0000000005], // NR/Halley take tiny steps near root
-                brackets: vec![Bounds::new(0.5, 2.0)],
+                name: "Wikipedia Bisection Cubic".to_owned(),
+                f: Box::new(|x| x * x * x - x - 2.0),
+                df: Box::new(|x| 3.0 * x * x - 1.0),
+                d2f: Box::new(|x| 6.0 * x),
+                roots: vec![1.52137970680457],
+                guesses: vec![1.0],
+                brackets: vec![Bounds::new(1.0, 2.0)],
             },
             RootTest {
-                name: "Costabile06 Example Twenty Five".to_owned(),
-                f: Box::new(|x| (10. * x - 1.) / (9. * x)),
-                df: Box::new(|x| (9. * x * x).recip()),
-                d2f: Box::new(|x| -2. * (9. * x * x * x).recip()),
-                roots: vec![0.1],
-                guesses: vec![0.01], // hard for iterative methods coming from right
-                brackets: vec![Bounds::new(0.01, 1.0)],
+                name: "Isaac Newton's Secant Example".to_owned(),
+                f: Box::new(|x| x * x * x + 10.0 * x * x - 7.0 * x - 44.0),
+                df: Box::new(|x| 3.0 * x * x + 20.0 * x - 7.0),
+                d2f: Box::new(|x| 6.0 * x + 20.0),
+                roots: vec![2.20681731724844],
+                guesses: vec![2.2],
+                brackets: vec![Bounds::new(2.0, 2.3)],
             },
             RootTest {
-                name: "Costabile06 Example Twenty Six".to_owned(),
-                f: Box::new(|x| (20. * x - 1.) / (19. * x)),
-                df: Box::new(|x| (19. * x * x).recip()),
-                d2f: Box::new(|x| -2. * (19. * x * x * x).recip()),
-                roots: vec![0.05],
-                guesses: vec![0.06], // hard for iterative methods coming from right
-                brackets: vec![Bounds::new(0.01, 1.0)],
+                name: "Isaac Newton's NR Example".to_owned(),
+                f: Box::new(|x| x * x * x - 2.0 * x - 5.0),
+                df: Box::new(|x| 3.0 * x * x - 2.0),
+                d2f: Box::new(|x| 6.0 * x),
+                roots: vec![2.0945514815423265],
+                guesses: vec![2.0],
+                brackets: vec![Bounds::new(2.0, 3.0)],
             },
             RootTest {
-                name: "Costabile06 Example Twenty Seven".to_owned(),
-                f: Box::new(|x| (-x).exp() + x.cos()),
-                df: Box::new(|x| x.exp() - x.sin()),
-                d2f: Box::new(|x| (-x).exp() - x.cos()),
-                roots: vec![1.74613953040801241765070309],
-                guesses: vec![1.746139531], // iterative methods suffer here
-                brackets: vec![Bounds::new(1.0, 2.0)],
+                name: "Thomas Simpson NR Example".to_owned(),
+                f: Box::new(|x| {
+                    (1. - x).sqrt() + (1. - 2. * x * x).sqrt() + (1. - 3. * x * x * x).sqrt() - 2.
+                }),
+                df: Box::new(|x| {
+                    -2. * x * (1. - 2. * x * x).sqrt().recip()
+                        - 9. * x * x * (1. - 3. * x * x * x).sqrt().recip() / 2.
+                        - 1. * (1. - x).sqrt().recip() / 2.
+                }),
+                d2f: Box::new(|x| {
+                    -9. * x * (1. - 3. * x * x * x).sqrt().recip()
+                        - 4. * x * x * (1. - 2. * x * x).powf(1.5)
+                        - 2. * (1. - 2. * x * x).sqrt().recip()
+                        - 81. * x * x * x * x * (1. - 3. * x * x * x).powf(1.5) / 4.
+                        - 1. * (1. - x).powf(1.5) / 4.
+                }),
+                roots: vec![0.55158615249704711724768527],
+                guesses: vec![0.5],
+                brackets: vec![Bounds::new(0.0, 0.69)],
             },
         ]
     }
 
-    /// The Dowell71 tests are from:
-    ///
-    /// *Dowell, M., & Jarratt, P. (1971). A modified regula falsi method for
-    /// computing the root of an equation. BIT Numerical Mathematics, 11(2),
-    /// 168-174.*
-    ///
-    fn make_root_tests_dowell71() -> Vec<RootTest> {
-        let mut cases = Vec::new();
+    /// Table driven tests re-used for different methods.
+    fn make_root_tests() -> Vec<RootTest> {
+        let mut v = Vec::new();
+        v.extend(make_root_tests_ford95());
+        v.extend(make_root_tests_costabile06());
+        v.extend(make_root_tests_dowell71());
+        v.extend(make_root_tests_misc());
+        v
+    }
+
+    /*
+     * Table-driven tests
+     */
+    #[test]
+    fn test_table_bisection() {
+        for t in make_root_tests() {
+            for i in 0..t.roots.len() {
+                let f = RealFn::new(&*t.f);
+                let conv = DeltaX::new(1e-9);
+                let root = bisection(&f, &t.brackets[i], &conv, 100)
+                    .unwrap_or_else(|_| panic!("root for {}", t.name));
+
+                assert!(
+                    (root - t.roots[i]).abs() < 1e-8,
+                    "{} root wanted={}, got={}",
+                    t.name,
+                    t.roots[i],
+                    root
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_table_steffensen_bracketed() {
+        let mut iter_bisection = 0;
+        let mut iter_steffensen = 0;
+
+        for t in make_root_tests() {
+            for i in 0..t.roots.len() {
+                let f = RealFn::new(&*t.f);
+                let c1 = DeltaX::new(1e-10);
+                let conv = MinIterations::new(&c1, 0);
+                let root = steffensen_bracketed(&f, &t.brackets[i], &conv, 200)
+                    .unwrap_or_else(|_| panic!("root for {}", t.name));
+
+                assert!(
+                    (root - t.roots[i]).abs() < 1e-8,
+                    "{} root wanted={}, got={}",
+                    t.name,
+                    t.roots[i],
+                    root
+                );
+
+                iter_steffensen += conv.calls();
+                iter_bisection += bisection_trace(&f, &t.brackets[i], 100)
+                    .expect("root")
+                    .1
+                    .len();
+            }
+        }
+        assert!(
+            iter_steffensen < iter_bisection,
+            "steffensen={} bisection={}",
+            iter_steffensen,
+            iter_bisection
+        );
+    }
+
+    #[test]
+    fn test_table_newton() {
+        let c1 = DeltaX::new(1e-8);
+        let c2 = FnResidual::new(1e-9);
+        let conv = DualCriteria::new(&c1, &c2);
+
+        for t in make_root_tests() {
+            for i in 0..t.roots.len() {
+                let f = RealFnAndFirst::new(&*t.f, &*t.df);
+                let root = newton_raphson(&f, t.guesses[i], &conv, 100)
+                    .unwrap_or_else(|_| panic!("root for {}", t.name));
+
+                assert!(
+                    (root - t.roots[i]).abs() < 1e-9,
+                    "{} root wanted={}, got={}",
+                    t.name,
+                    t.roots[i],
+                    root
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_table_halley() {
+        let c1 = DeltaX::new(1e-8);
+        let c2 = FnResidual::new(1e-9);
+        let conv = DualCriteria::new(&c1, &c2);
+
+        for t in make_root_tests() {
+            for i in 0..t.roots.len() {
+                let f = RealFnAndFirstSecond::new(&*t.f, &*t.df, &*t.d2f);
+                let root = halley_method(&f, t.guesses[i], &conv, 100)
+                    .unwrap_or_else(|_| panic!("root for {}", t.name));
+
+                assert!(
+                    (root - t.roots[i]).abs() < 1e-9,
+                    "{} root wanted={}, got={}",
+                    t.name,
+                    t.roots[i],
+                    root
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_table_halley_numerical_second() {
+        let c1 = DeltaX::new(1e-8);
+        let c2 = FnResidual::new(1e-9);
+        let conv = DualCriteria::new(&c1, &c2);
+
+        for t in make_root_tests_costabile06() {
+            for i in 0..t.roots.len() {
+                let f = RealFnAndFirstNumericalSecond::new(&*t.f, &*t.df);
+                let root = halley_method(&f, t.guesses[i], &conv, 100)
+                    .unwrap_or_else(|_| panic!("root for {}", t.name));
+
+                assert!(
+                    (root - t.roots[i]).abs() < 1e-8,
+                    "{} root wanted={}, got={}",
+                    t.name,
+                    t.roots[i],
+                    root
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_householder3() {
+        // Wallis' cubic
+        let in_f = |x: f64| x * x * x - 2.0 * x - 5.0;
+        let in_df = |x: f64| 3.0 * x * x - 2.0;
+        let in_d2f = |x: f64| 6.0 * x;
+        let in_d3f = |_: f64| 6.0;
+        let f = RealFnThroughThird::new(&in_f, &in_df, &in_d2f, &in_d3f);
+        let expected = 2.0945514815423265;
+
+        let c = DeltaX::new(1e-12);
+        for &start in &[2.0, 5.0, 20.0] {
+            let conv_h3 = MinIterations::new(&c, 0);
+            let root = householder3(&f, start, &conv_h3, 100).expect("householder3 root");
+            assert!((root - expected).abs() < 1e-12);
+
+            let conv_h = MinIterations::new(&c, 0);
+            let _ = halley_method(&f, start, &conv_h, 100).expect("halley root");
+            assert!(
+                conv_h3.calls() < conv_h.calls(),
+                "start={} householder3={} halley={}",
+                start,
+                conv_h3.calls(),
+                conv_h.calls()
+            );
+        }
+    }
+
+    #[test]
+    fn test_householder3_zero_denominator() {
+        let in_f = |x: f64| x * x + 1.0;
+        let in_df = |x: f64| 2.0 * x;
+        let in_d2f = |_: f64| 2.0;
+        let in_d3f = |_: f64| 0.0;
+        let f = RealFnThroughThird::new(&in_f, &in_df, &in_d2f, &in_d3f);
+
+        // 6 df^3 - 6 f df d2f + f^2 d3f vanishes at x = 0
+        let conv = DeltaX::new(1e-9);
+        match householder3(&f, 0.0, &conv, 100) {
+            Err(RootError::ZeroDerivative { .. }) => {}
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+    }
+
+    #[test]
+    fn test_schroder_multiple_roots() {
+        let c = DeltaX::new(1e-10);
+
+        for t in make_root_tests_costabile06() {
+            if !t.name.ends_with("Twenty Three") && !t.name.ends_with("Twenty Four") {
+                continue;
+            }
+            let f = RealFnAndFirstSecond::new(&*t.f, &*t.df, &*t.d2f);
+
+            for &start in &[0.5, 2.0] {
+                let conv_s = MinIterations::new(&c, 0);
+                let root = schroder(&f, start, &conv_s, 100).expect("schroder root");
+                assert!((root - t.roots[0]).abs() < 1e-9, "{} got={}", t.name, root);
+
+                // halley only converges linearly on multiple roots
+                let conv_h = MinIterations::new(&c, 0);
+                let _ = halley_method(&f, start, &conv_h, 100).expect("halley root");
+                assert!(
+                    2 * conv_s.calls() < conv_h.calls(),
+                    "{} schroder={} halley={}",
+                    t.name,
+                    conv_s.calls(),
+                    conv_h.calls()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_schroder_simple_root() {
+        let in_f = |x: f64| x * x - 2.0;
+        let in_df = |x: f64| 2.0 * x;
+        let in_d2f = |_: f64| 2.0;
+        let f = RealFnAndFirstSecond::new(&in_f, &in_df, &in_d2f);
+
+        let conv = DeltaX::new(1e-12);
+        let root = schroder(&f, 1.0, &conv, 100).expect("root");
+        assert!((root - 2f64.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_schroder_zero_denominator() {
+        // df^2 - f*d2f = 1 - 1 = 0 everywhere
+        let in_f = |x: f64| x.exp();
+        let in_df = |x: f64| x.exp();
+        let in_d2f = |x: f64| x.exp();
+        let f = RealFnAndFirstSecond::new(&in_f, &in_df, &in_d2f);
+
+        let conv = DeltaX::new(1e-9);
+        match schroder(&f, 0.0, &conv, 100) {
+            Err(RootError::ZeroDerivative { .. }) => {}
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+    }
+
+    #[test]
+    fn test_laguerre_cube() {
+        let poly = Polynomial::new(vec![-1.0, 0.0, 0.0, 1.0]);
+        let f = RealFnAndFirst::new(&|x: f64| x * x * x - 1.0, &|x: f64| 3.0 * x * x);
+        let conv = DeltaX::new(1e-12);
+
+        // newton overshoots to ~3333 and crawls back
+        match newton_raphson(&f, 0.01, &conv, 20) {
+            Err(RootError::IterationLimit { .. }) => {}
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+        let root = laguerre(&poly, 0.01, 1e-12, 20).expect("laguerre root");
+        assert!((root - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_laguerre_twentieth_power() {
+        let mut coeffs = vec![0.0; 21];
+        coeffs[0] = -1.0;
+        coeffs[20] = 1.0;
+        let poly = Polynomial::new(coeffs);
+        let conv = DeltaX::new(1e-12);
+
+        for &(start, expected) in &[(0.8, 1.0), (-0.8, -1.0), (1.7, 1.0), (-1.7, -1.0)] {
+            match newton_raphson(&poly, start, &conv, 10) {
+                Err(RootError::IterationLimit { .. }) => {}
+                _ => {
+                    panic!("incorrect error type");
+                }
+            }
+            let root = laguerre(&poly, start, 1e-12, 10).expect("laguerre root");
+            assert!(
+                (root - expected).abs() < 1e-12,
+                "start={} got={}",
+                start,
+                root
+            );
+        }
+    }
+
+    #[test]
+    fn test_laguerre_exact_and_degenerate() {
+        let poly = Polynomial::new(vec![-1.0, 0.0, 0.0, 1.0]);
+        assert_eq!(laguerre(&poly, 1.0, 1e-12, 10).expect("root"), 1.0);
+
+        // p' and p'' both vanish at zero
+        match laguerre(&poly, 0.0, 1e-12, 10) {
+            Err(RootError::ZeroDerivative { .. }) => {}
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+        match laguerre(&poly, 0.01, 1e-12, 2) {
+            Err(RootError::IterationLimit { .. }) => {}
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+    }
+
+    #[test]
+    fn test_halley_from_first() {
+        for t in make_root_tests_costabile06() {
+            for i in 0..t.roots.len() {
+                let c1 = DeltaX::new(1e-8);
+                let c2 = FnResidual::new(1e-9);
+                let c = DualCriteria::new(&c1, &c2);
+
+                let f = RealFnAndFirstSecond::new(&*t.f, &*t.df, &*t.d2f);
+                let conv = MinIterations::new(&c, 0);
+                let root = halley_method(&f, t.guesses[i], &conv, 100)
+                    .unwrap_or_else(|_| panic!("root for {}", t.name));
+                let iter_analytic = conv.calls();
+
+                let conv = MinIterations::new(&c, 0);
+                let root_numerical = halley_from_first(&*t.f, &*t.df, t.guesses[i], &conv, 100)
+                    .unwrap_or_else(|_| panic!("root for {}", t.name));
+                let iter_numerical = conv.calls();
+
+                assert!(
+                    (root_numerical - root).abs() < 1e-8,
+                    "{} analytic={}, numerical={}",
+                    t.name,
+                    root,
+                    root_numerical
+                );
+                assert!(
+                    iter_numerical <= iter_analytic + 1,
+                    "{} iterations analytic={}, numerical={}",
+                    t.name,
+                    iter_analytic,
+                    iter_numerical
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_table_illinois() {
+        for t in make_root_tests() {
+            for i in 0..t.roots.len() {
+                let f = RealFn::new(&*t.f);
+                let root = false_position_illinios(&f, &t.brackets[i], 100)
+                    .unwrap_or_else(|_| panic!("root for {}", t.name));
+
+                assert!(
+                    (root - t.roots[i]).abs() < 1e-8,
+                    "{} root wanted={}, got={}",
+                    t.name,
+                    t.roots[i],
+                    root
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_table_pegasus() {
+        for t in make_root_tests() {
+            for i in 0..t.roots.len() {
+                let f = RealFn::new(&*t.f);
+                let root = false_position_pegasus(&f, &t.brackets[i], 100)
+                    .unwrap_or_else(|_| panic!("root for {}", t.name));
+
+                assert!(
+                    (root - t.roots[i]).abs() < 1e-8,
+                    "{} root wanted={}, got={}",
+                    t.name,
+                    t.roots[i],
+                    root
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_pegasus_endpoint_root() {
+        let in_f = |x: f64| x - 1.0;
+        let f = RealFn::new(&in_f);
+        let root = false_position_pegasus(&f, &Bounds::new(0.0, 1.0), 100).expect("root");
+        assert!((root - 1.0).abs() < 1e-9);
+
+        // interpolation lands exactly on the root
+        let root = false_position_pegasus(&f, &Bounds::new(0.0, 3.0), 100).expect("root");
+        assert_eq!(root, 1.0);
+    }
+
+    #[test]
+    fn test_pegasus_vs_illinois_ford95() {
+        // pegasus loses on a few examples but wins overall
+        let mut pegasus_evals = 0;
+        let mut illinois_evals = 0;
+        for t in make_root_tests_ford95() {
+            for i in 0..t.roots.len() {
+                let inner = RealFn::new(&*t.f);
+                let f = CountingFn::new(&inner);
+                false_position_pegasus(&f, &t.brackets[i], 100).expect("pegasus root");
+                pegasus_evals += f.f_evals();
+
+                let f = CountingFn::new(&inner);
+                false_position_illinios(&f, &t.brackets[i], 100).expect("illinois root");
+                illinois_evals += f.f_evals();
+            }
+        }
+        assert!(
+            pegasus_evals < illinois_evals,
+            "pegasus={} illinois={}",
+            pegasus_evals,
+            illinois_evals
+        );
+    }
+
+    #[test]
+    fn test_table_brent_dekker() {
+        for t in make_root_tests() {
+            for i in 0..t.roots.len() {
+                let f = RealFn::new(&*t.f);
+                let root = brent_dekker(&f, &t.brackets[i], 100)
+                    .unwrap_or_else(|_| panic!("root for {}", t.name));
+
+                assert!(
+                    (root - t.roots[i]).abs() < 1e-8,
+                    "{} root wanted={}, got={}",
+                    t.name,
+                    t.roots[i],
+                    root
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_brent_dekker_beats_bisection() {
+        for t in make_root_tests_ford95() {
+            for i in 0..t.roots.len() {
+                let inner = RealFn::new(&*t.f);
+
+                let f = CountingFn::new(&inner);
+                bisection(&f, &t.brackets[i], &DeltaX::new(1e-9), 100).expect("root");
+                let evals_bisection = f.f_evals();
+
+                let f = CountingFn::new(&inner);
+                brent_dekker(&f, &t.brackets[i], 100).expect("root");
+                let evals_brent = f.f_evals();
+
+                assert!(
+                    evals_brent < evals_bisection,
+                    "{} brent={} bisection={}",
+                    t.name,
+                    evals_brent,
+                    evals_bisection
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_brent_dekker_corner_cases() {
+        // root on an endpoint
+        let in_f = |x: f64| 1.0 - x;
+        let f = RealFn::new(&in_f);
+        assert_eq!(
+            brent_dekker(&f, &Bounds::new(1.0, 3.0), 100).expect("root"),
+            1.0
+        );
+
+        match brent_dekker(&f, &Bounds::new(0.0, 3.0), 1) {
+            Err(RootError::IterationLimit { .. }) => {}
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+
+        match brent_dekker(&f, &Bounds::new(2.0, 2.0), 100) {
+            Err(RootError::DegenerateBracket { .. }) => {}
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+    }
+
+    #[test]
+    fn test_table_itp() {
+        for t in make_root_tests() {
+            for i in 0..t.roots.len() {
+                let bracket = &t.brackets[i];
+                let inner = RealFn::new(&*t.f);
+                let f = CountingFn::new(&inner);
+                let k1 = 0.2 / bracket.size();
+                let root = itp(&f, bracket, k1, 2.0, 0, 100)
+                    .unwrap_or_else(|_| panic!("root for {}", t.name));
+
+                assert!(
+                    (root - t.roots[i]).abs() < 1e-8,
+                    "{} root wanted={}, got={}",
+                    t.name,
+                    t.roots[i],
+                    root
+                );
+
+                // two evaluations go to the endpoints
+                let iters = f.f_evals() - 2;
+                let bisect_iters = bisection_iterations_for(bracket, 1e-9);
+                assert!(
+                    iters <= bisect_iters,
+                    "{} itp={} bisection={}",
+                    t.name,
+                    iters,
+                    bisect_iters
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_itp_faster_than_bisection() {
+        let in_f = |x: f64| x * x * x - 2.0 * x - 5.0;
+        let bracket = Bounds::new(2.0, 3.0);
+
+        let inner = RealFn::new(&in_f);
+        let f = CountingFn::new(&inner);
+        let root = itp(&f, &bracket, 0.2, 2.0, 1, 100).expect("root");
+        assert!((root - 2.0945514815423265).abs() < 1e-9);
+        assert!(f.f_evals() * 2 < bisection_iterations_for(&bracket, 1e-9));
+    }
+
+    #[test]
+    fn test_itp_no_sign_change() {
+        let in_f = |x: f64| x * x + 1.0;
+        let f = RealFn::new(&in_f);
+        match itp(&f, &Bounds::new(-1.0, 1.0), 0.1, 2.0, 1, 100) {
+            Err(RootError::NoSignChange { .. }) => {}
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+    }
+
+    #[test]
+    fn test_table_ridders() {
+        for t in make_root_tests() {
+            for i in 0..t.roots.len() {
+                let f = RealFn::new(&*t.f);
+                let root = ridders(&f, &t.brackets[i], 100)
+                    .unwrap_or_else(|_| panic!("root for {}", t.name));
+
+                assert!(
+                    (root - t.roots[i]).abs() < 1e-8,
+                    "{} root wanted={}, got={}",
+                    t.name,
+                    t.roots[i],
+                    root
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_ridders_corner_cases() {
+        // exact root hit by the exponential fit
+        let in_f = |x: f64| x - 0.25;
+        let f = RealFn::new(&in_f);
+        assert_eq!(
+            ridders(&f, &Bounds::new(0.0, 1.0), 100).expect("root"),
+            0.25
+        );
+
+        match ridders(&f, &Bounds::new(0.5, 0.5), 100) {
+            Err(RootError::DegenerateBracket { .. }) => {}
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+
+        // non-finite update
+        let in_f = |x: f64| if x < 0.3 { -1.0 } else { f64::INFINITY };
+        let f = RealFn::new(&in_f);
+        match ridders(&f, &Bounds::new(0.0, 1.0), 100) {
+            Err(RootError::IteratedToNaN { .. }) => {}
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+    }
+
+    /*
+     * Wegstein's method.
+     */
+    #[test]
+    fn test_fixed_point_cos() {
+        let conv = FnResidual::new(1e-12);
+        let root = fixed_point(&|x: f64| x.cos(), 1.0, &conv, 200).expect("fixed point");
+        assert!((root - 0.7390851332151607).abs() < 1e-11);
+    }
+
+    #[test]
+    fn test_fixed_point_diverged() {
+        // fixed point at -1 is repelling since |g'| = 2
+        let conv = FnResidual::new(1e-12);
+        match fixed_point(&|x: f64| 2.0 * x + 1.0, 0.0, &conv, 100) {
+            Err(RootError::Diverged { .. }) => {}
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+
+        // oscillating divergence
+        match fixed_point(&|x: f64| 1.0 - 3.0 * x, 0.0, &conv, 100) {
+            Err(RootError::Diverged { .. }) => {}
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+    }
+
+    #[test]
+    fn test_aitken_accelerate() {
+        // slowly contracting map, |g'| is about 0.96 at the fixed point
+        let evals = Cell::new(0);
+        let g = |x: f64| {
+            evals.set(evals.get() + 1);
+            0.9 * x + 0.1 * x.cos()
+        };
+        let expected = 0.7390851332151607;
+        let conv = FnResidual::new(1e-12);
+
+        let root = fixed_point(&g, 1.0, &conv, 10000).expect("fixed point");
+        assert!((root - expected).abs() < 1e-9);
+        let plain_evals = evals.get();
+
+        evals.set(0);
+        let root = aitken_accelerate(&g, 1.0, &conv, 100).expect("fixed point");
+        assert!((root - expected).abs() < 1e-12);
+        assert!(
+            evals.get() * 10 < plain_evals,
+            "{} vs {}",
+            evals.get(),
+            plain_evals
+        );
+    }
+
+    #[test]
+    fn test_aitken_linear_map() {
+        // exact after a single extrapolation
+        let conv = FnResidual::new(1e-12);
+        let root = aitken_accelerate(&|x: f64| 0.5 * x + 1.0, 0.0, &conv, 10).expect("root");
+        assert!((root - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_table_inverse_quadratic() {
+        let c1 = DeltaX::new(1e-8);
+        let c2 = FnResidual::new(1e-9);
+        let c = DualCriteria::new(&c1, &c2);
+
+        // unsafeguarded, so these wander off to another root or overflow
+        let skip = [
+            "Costabile06 Example Four",
+            "Costabile06 Example Five",
+            "Costabile06 Example Twenty Two, Twenty Eight",
+            "Dowell71 Table 2 for n=20",
+            "Wikipedia NR Parabola",
+        ];
+
+        for t in make_root_tests()
+            .into_iter()
+            .filter(|t| !skip.contains(&t.name.as_str()))
+        {
+            let f = RealFn::new(&*t.f);
+            for i in 0..t.roots.len() {
+                let g = t.guesses[i];
+                let root = inverse_quadratic(&f, g - 1e-3, g + 1e-3, g, &c, 100)
+                    .unwrap_or_else(|e| panic!("{} from {}: {:?}", t.name, g, e));
+                assert!(
+                    (root - t.roots[i]).abs() < 1e-8,
+                    "{} expected={} got={}",
+                    t.name,
+                    t.roots[i],
+                    root
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_inverse_quadratic_degenerate() {
+        let in_f = |x: f64| x * x - 4.0;
+        let f = RealFn::new(&in_f);
+        let c = DeltaX::new(1e-12);
+
+        // f(-1) == f(1) for the oldest pair falls back to a secant step
+        let root = inverse_quadratic(&f, -1.0, 1.0, 3.0, &c, 100).expect("root");
+        assert!((root - 2.0).abs() < 1e-12);
+
+        // newest pair coincides
+        match inverse_quadratic(&f, 3.0, -1.0, 1.0, &c, 100) {
+            Err(RootError::ZeroDerivative { .. }) => {}
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+    }
+
+    #[test]
+    fn test_muller_complex_roots() {
+        let in_f = |z: Complex<f64>| z * z + 1.0;
+        let f = ComplexFn::new(&in_f);
+        let i = Complex::new(0.0, 1.0);
+
+        for &(x0, x1, x2) in &[(0.5, 1.0, 1.5), (-3.0, -2.0, -1.0), (2.0, 0.0, 5.0)] {
+            let root = muller(&f, x0, x1, x2, 1e-12, 50).expect("complex root");
+            assert!(
+                (root - i).norm() < 1e-12 || (root + i).norm() < 1e-12,
+                "seeds=({}, {}, {}) got={}",
+                x0,
+                x1,
+                x2,
+                root
+            );
+        }
+    }
+
+    #[test]
+    fn test_muller_quartic() {
+        // roots are the primitive eighth roots of unity, none real
+        let in_f = |z: Complex<f64>| z * z * z * z + 1.0;
+        let f = ComplexFn::new(&in_f);
+        let root = muller(&f, 0.0, 0.5, 1.0, 1e-12, 50).expect("complex root");
+        assert!((root.norm() - 1.0).abs() < 1e-12);
+        assert!((root.re.abs() - 0.5f64.sqrt()).abs() < 1e-12);
+        assert!(in_f(root).norm() < 1e-12);
+    }
+
+    #[test]
+    fn test_muller_real_root() {
+        let in_f = |z: Complex<f64>| z * z * z - z * 2.0 - 5.0;
+        let f = ComplexFn::new(&in_f);
+        let root = muller(&f, 1.0, 2.0, 3.0, 1e-12, 50).expect("root");
+        assert!((root.re - 2.0945514815423265).abs() < 1e-12);
+        assert!(root.im.abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_complex_newton_quadratic() {
+        let in_f = |z: Complex<f64>| z * z + 1.0;
+        let in_df = |z: Complex<f64>| z * 2.0;
+        let f = ComplexFnAndFirst::new(&in_f, &in_df);
+        let conv = DeltaX::new(1e-12);
+        let i = Complex::new(0.0, 1.0);
+
+        let root = complex_newton(&f, Complex::new(0.5, 0.5), &conv, 50).expect("root");
+        assert!((root - i).norm() < 1e-12);
+        let root = complex_newton(&f, Complex::new(0.5, -0.5), &conv, 50).expect("root");
+        assert!((root + i).norm() < 1e-12);
+
+        // exact root at the start
+        assert_eq!(complex_newton(&f, i, &conv, 50).expect("root"), i);
+
+        // a real seed never leaves the real line, and hits df=0 at the origin
+        match complex_newton(&f, Complex::new(0.0, 0.0), &conv, 50) {
+            Err(RootError::ZeroDerivative { .. }) => {}
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+    }
+
+    #[test]
+    fn test_complex_newton_cube_roots_of_unity() {
+        let in_f = |z: Complex<f64>| z * z * z - 1.0;
+        let in_df = |z: Complex<f64>| z * z * 3.0;
+        let f = ComplexFnAndFirst::new(&in_f, &in_df);
+        let c1 = DeltaX::new(1e-12);
+        let c2 = FnResidual::new(1e-12);
+        let conv = DualCriteria::new(&c1, &c2);
+
+        // seeds near each cube root of unity
+        for k in 0..3 {
+            let want = Complex::from_polar(1.0, 2.0 * f64::consts::PI * k as f64 / 3.0);
+            let seed = want * 1.2 + Complex::new(0.05, -0.05);
+            let root = complex_newton(&f, seed, &conv, 50).expect("root");
+            assert!((root - want).norm() < 1e-12, "k={} got={}", k, root);
+            assert!(in_f(root).norm() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_muller_constant() {
+        let in_f = |_: Complex<f64>| Complex::new(1.0, 0.0);
+        let f = ComplexFn::new(&in_f);
+        match muller(&f, 0.0, 1.0, 2.0, 1e-12, 50) {
+            Err(RootError::ZeroDerivative { .. }) => {}
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+    }
+
+    #[test]
+    fn test_wegstein_contraction() {
+        // slowly converging contraction with fixed point at x = cos(x)
+        let evals = Cell::new(0);
+        let in_g = |x: f64| {
+            evals.set(evals.get() + 1);
+            0.9 * x + 0.1 * x.cos()
+        };
+        let g = RealFn::new(&in_g);
+        let expected = 0.7390851332151607;
+
+        // plain fixed-point iteration
+        let mut x = 1.0;
+        let mut plain_iter = 0;
+        while (in_g(x) - x).abs() > 1e-12 {
+            x = in_g(x);
+            plain_iter += 1;
+        }
+        assert!((x - expected).abs() < 1e-9);
+
+        evals.set(0);
+        let conv = FnResidual::new(1e-12);
+        let root = wegstein(&g, 1.0, &conv, 100).expect("fixed point");
+        assert!((root - expected).abs() < 1e-12);
+        assert!(
+            evals.get() * 10 < plain_iter,
+            "{} vs {}",
+            evals.get(),
+            plain_iter
+        );
+    }
+
+    #[test]
+    fn test_wegstein_linear() {
+        // exact after a single secant step
+        let in_g = |x: f64| 3.0 * x - 4.0;
+        let g = RealFn::new(&in_g);
+        let conv = FnResidual::new(1e-12);
+        let root = wegstein(&g, 0.0, &conv, 5).expect("fixed point");
+        assert!((root - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_wegstein_unit_slope() {
+        // g(x) = x + 1 has slope one and no fixed point
+        let in_g = |x: f64| x + 1.0;
+        let g = RealFn::new(&in_g);
+        let conv = FnResidual::new(1e-12);
+        match wegstein(&g, 0.0, &conv, 10).expect_err("no fixed point") {
+            RootError::IterationLimit { .. } => {}
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+    }
+
+    #[test]
+    fn test_illinois_flat_eps_scaling() {
+        // same root, function scaled by powers of two so scaling is exact
+        let evals = Cell::new(0);
+        let make = |scale: f64| {
+            let evals = &evals;
+            move |x: f64| {
+                evals.set(evals.get() + 1);
+                scale * (x * x - 2.0)
+            }
+        };
+        let bounds = Bounds::new(0.0, 2.0);
+        let solve = |scale: f64, flat_eps: f64| {
+            evals.set(0);
+            let in_f = make(scale);
+            let f = RealFn::new(&in_f);
+            let root = false_position_illinios_eps(&f, &bounds, flat_eps, 100).expect("root");
+            assert!((root - 2.0f64.sqrt()).abs() < 1e-9);
+            (root, evals.get())
+        };
+
+        let (root, count) = solve(1.0, 1e-12);
+        let tiny = 2.0f64.powi(-30);
+        let huge = 2.0f64.powi(30);
+
+        // fixed threshold forces needless bisection on the small function
+        assert!(solve(tiny, 1e-12).1 > count);
+
+        // threshold scaled by s^2 reproduces the unscaled iteration exactly
+        assert_eq!((root, count), solve(tiny, 1e-12 * tiny * tiny));
+        assert_eq!((root, count), solve(huge, 1e-12 * huge * huge));
+    }
+
+    #[test]
+    fn test_illinois_relative_eps_scaling() {
+        let bounds = Bounds::new(0.0, 2.0);
+        let solve = |scale: f64, flat_eps: f64| {
+            let in_f = move |x: f64| scale * (x * x - 2.0);
+            let inner = RealFn::new(&in_f);
+            let f = CountingFn::new(&inner);
+            let root =
+                false_position_illinios_relative_eps(&f, &bounds, flat_eps, 100).expect("root");
+            (root, f.f_evals())
+        };
+
+        // relative threshold reproduces the unscaled iteration exactly
+        let (root, count) = solve(1.0, 1e-12);
+        assert_eq!((root, count), solve(2.0f64.powi(-30), 1e-12));
+        assert_eq!((root, count), solve(2.0f64.powi(30), 1e-12));
+
+        // a large threshold bisects more
+        assert!(solve(1.0, 1e-2).1 > count);
+
+        // 1e12 and 1e-12 are not powers of two, so allow rounding differences
+        for &scale in [1e12, 1e-12, 1e200, 1e-200].iter() {
+            let (root_scaled, count_scaled) = solve(scale, 1e-12);
+            assert!((root_scaled - root).abs() < 1e-12, "scale {}", scale);
+            assert_eq!(count, count_scaled, "scale {}", scale);
+        }
+    }
+
+    #[test]
+    fn test_bracketing_with_endpoints() {
+        let in_f = |x: f64| x * x - 2.0;
+        let inner = RealFn::new(&in_f);
+        let bounds = Bounds::new(0.0, 2.0);
+        let (f_a, f_b) = (in_f(bounds.a), in_f(bounds.b));
+
+        let f = CountingFn::new(&inner);
+        let root = bisection(&f, &bounds, &DeltaX::new(1e-9), 100).expect("root");
+        let evals = f.f_evals();
+
+        let f = CountingFn::new(&inner);
+        assert_eq!(
+            root,
+            bisection_with_endpoints(&f, &bounds, f_a, f_b, 100).expect("root")
+        );
+        assert_eq!(evals - 2, f.f_evals());
+
+        let f = CountingFn::new(&inner);
+        let root = false_position_illinios(&f, &bounds, 100).expect("root");
+        let evals = f.f_evals();
+
+        let f = CountingFn::new(&inner);
+        let root_ep =
+            false_position_illinios_with_endpoints(&f, &bounds, f_a, f_b, 100).expect("root");
+        assert_eq!(root, root_ep);
+        assert_eq!(evals - 2, f.f_evals());
+    }
+
+    #[test]
+    fn test_bisection_bracket() {
+        let in_f = |x: f64| x * x - 2.0;
+        let f = RealFn::new(&in_f);
+        let bounds = Bounds::new(0.0, 2.0);
+        let sqrt2 = 2.0f64.sqrt();
+
+        for &tol in [1e-3, 1e-9, 1e-12].iter() {
+            let conv = DeltaX::new(tol);
+            let bracket = bisection_bracket(&f, &bounds, &conv, 100).expect("bracket");
+            assert!(bracket.a <= sqrt2 && sqrt2 <= bracket.b);
+            assert!(is_sign_change(in_f(bracket.a), in_f(bracket.b)));
+            assert!(bracket.size() <= tol);
+
+            let root = bisection(&f, &bounds, &conv, 100).expect("root");
+            assert!(root == bracket.a || root == bracket.b);
+        }
+
+        match bisection_bracket(&f, &Bounds::new(2.0, 3.0), &DeltaX::new(1e-9), 100) {
+            Err(RootError::NoSignChange { .. }) => {}
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+    }
+
+    #[test]
+    fn test_bisection_evaluation_count() {
+        let in_f = |x: f64| x * x - 2.0;
+        let inner = RealFn::new(&in_f);
+        let bounds = Bounds::new(0.0, 2.0);
+
+        // two endpoints, then exactly one evaluation per midpoint
+        for max_iter in 1..20 {
+            let f = CountingFn::new(&inner);
+            match bisection(&f, &bounds, &DeltaX::new(1e-9), max_iter) {
+                Err(RootError::IterationLimit { .. }) => {}
+                _ => {
+                    panic!("incorrect error type");
+                }
+            }
+            assert_eq!(f.f_evals(), max_iter + 2);
+        }
+    }
+
+    #[test]
+    fn test_bracketing_already_converged() {
+        let in_f = |x: f64| x * x - 2.0;
+        let inner = RealFn::new(&in_f);
+        let sqrt2 = 2.0f64.sqrt();
+        let bounds = Bounds::new(sqrt2 - 5e-13, sqrt2 + 5e-13);
+
+        // only the endpoints are evaluated, even with no iterations allowed
+        let f = CountingFn::new(&inner);
+        let root = bisection(&f, &bounds, &DeltaX::new(1e-9), 0).expect("root");
+        assert_eq!(root, bounds.middle());
+        assert_eq!(f.f_evals(), 2);
+
+        let f = CountingFn::new(&inner);
+        let root = false_position_illinios(&f, &bounds, 0).expect("root");
+        assert_eq!(root, bounds.middle());
+        assert_eq!(f.f_evals(), 2);
+    }
+
+    #[test]
+    fn test_bisection_tight_start_honors_criteria() {
+        let in_f = |x: f64| x - 1.0;
+        let inner = RealFn::new(&in_f);
+        let bounds = Bounds::new(1.0 - 3e-10, 1.0 + 1e-10);
+
+        // narrower than 1e-9 to begin with, but not narrow enough
+        let f = CountingFn::new(&inner);
+        let conv = DeltaX::new(1e-15);
+        let root = bisection(&f, &bounds, &conv, 100).expect("root");
+        assert!((root - 1.0).abs() < 1e-15);
+        assert!(f.f_evals() > 2);
+
+        let bracket = bisection_bracket(&inner, &bounds, &conv, 100).expect("bracket");
+        assert!(bracket.size() < 1e-15);
+        assert!(bracket.a <= 1.0 && 1.0 <= bracket.b);
+    }
+
+    #[test]
+    fn test_bisection_tight_start_stateful_criteria() {
+        let in_f = |x: f64| x - 1.0;
+        let inner = RealFn::new(&in_f);
+        let bounds = Bounds::new(1.0 - 1e-12, 1.0 + 1e-12);
+
+        // the pre-check is the first of the three calls
+        let f = CountingFn::new(&inner);
+        let delta = DeltaX::new(1e-9);
+        let conv = MinIterations::new(&delta, 3);
+        bisection(&f, &bounds, &conv, 100).expect("root");
+        assert_eq!(conv.calls(), 3);
+        assert_eq!(f.f_evals(), 4);
+
+        // b is the first sample in the window, so one split fills it
+        let f = CountingFn::new(&inner);
+        let conv = Stagnation::new(2, 1e-9);
+        bisection(&f, &bounds, &conv, 100).expect("root");
+        assert!(conv.stagnated());
+        assert_eq!(f.f_evals(), 3);
+    }
+
+    #[test]
+    fn test_tighten_bracket() {
+        let in_f = |x: f64| x * x - 2.0;
+        let f = RealFn::new(&in_f);
+        let bounds = Bounds::new(0.0, 2.0);
+
+        for &width in [0.5, 1e-3, 1e-10].iter() {
+            let tight = tighten_bracket(&f, &bounds, width, 100).expect("bracket");
+            assert!(tight.size() <= width);
+            assert!(is_sign_change(in_f(tight.a), in_f(tight.b)));
+            assert!(tight.contains(2.0f64.sqrt()));
+        }
+
+        // already tight enough
+        assert_eq!(
+            tighten_bracket(&f, &bounds, 2.0, 0).expect("bracket"),
+            bounds
+        );
+
+        let n = bisection_iterations_for(&bounds, 1e-6);
+        assert!(tighten_bracket(&f, &bounds, 1e-6, n).is_ok());
+        match tighten_bracket(&f, &bounds, 1e-6, n - 1) {
+            Err(RootError::IterationLimit { .. }) => {}
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+    }
+
+    #[test]
+    fn test_bisection_eps() {
+        let in_f = |x: f64| 1e-20 * (x - 1.0 / 3.0);
+        let inner = RealFn::new(&in_f);
+        let bounds = Bounds::new(0.0, 1.0);
+
+        let f = CountingFn::new(&inner);
+        let root = bisection(&f, &bounds, &DeltaX::new(1e-9), 100).expect("root");
+        let evals = f.f_evals();
+
+        // stops as soon as |f| drops below 1e-26, i.e. within 1e-6 of the root
+        let f = CountingFn::new(&inner);
+        let root_eps = bisection_eps(&f, &bounds, 1e-26, 100).expect("root");
+        assert!(f.f_evals() < evals);
+        assert!((root_eps - root).abs() < 1e-6);
+        assert!(in_f(root_eps).abs() < 1e-26);
+
+        // zero_eps of zero behaves like plain bisection
+        let root_zero = bisection_eps(&inner, &bounds, 0.0, 100).expect("root");
+        assert!((root_zero - root).abs() < 1e-9);
+
+        // endpoint already a root
+        let root_end = bisection_eps(&inner, &Bounds::new(1.0 / 3.0, 1.0), 1e-26, 100);
+        assert_eq!(root_end.expect("root"), 1.0 / 3.0);
+
+        // exact zeros have no sign, so even with zero_eps of zero they stop
+        // the solve rather than being kept as a bracket end
+        let in_g = |x: f64| x - 0.5;
+        let inner = RealFn::new(&in_g);
+        let f = CountingFn::new(&inner);
+        assert_eq!(bisection_eps(&f, &bounds, 0.0, 100).expect("root"), 0.5);
+        assert_eq!(f.f_evals(), 3);
+        let root_end = bisection_eps(&inner, &Bounds::new(0.5, 1.0), 0.0, 100);
+        assert_eq!(root_end.expect("root"), 0.5);
+    }
+
+    #[test]
+    fn test_bisection_iterations_for() {
+        assert_eq!(bisection_iterations_for(&Bounds::new(0.0, 1.0), 0.25), 2);
+        assert_eq!(bisection_iterations_for(&Bounds::new(0.0, 1.0), 0.2), 3);
+        assert_eq!(bisection_iterations_for(&Bounds::new(-4.0, 4.0), 1e-3), 13);
+        assert_eq!(bisection_iterations_for(&Bounds::new(2.0, 3.0), 1e-9), 30);
+        assert_eq!(bisection_iterations_for(&Bounds::new(0.0, 1.0), 2.0), 0);
+        assert_eq!(bisection_iterations_for(&Bounds::new(1.0, 1.0), 1e-9), 0);
+
+        // bisection stops once the bracket is narrower than 1e-9
+        let in_f = |x: f64| x * x - 5.0;
+        let f = RealFn::new(&in_f);
+        let bounds = Bounds::new(2.0, 3.0);
+        let n = bisection_iterations_for(&bounds, 1e-9);
+        assert!(bisection(&f, &bounds, &DeltaX::new(1e-9), n).is_ok());
+        assert!(bisection(&f, &bounds, &DeltaX::new(1e-9), n - 1).is_err());
+    }
+
+    #[test]
+    fn test_bisection_bracket_and_residual() {
+        // shallow slope, so |f| is small well away from the root at 3
+        let in_f = |x: f64| 1e-6 * (x - 3.0);
+        let f = RealFn::new(&in_f);
+        let bounds = Bounds::new(0.0, 10.0);
+
+        // residual alone stops early and far from the root
+        let conv = FnResidual::new(1e-6);
+        let root = bisection(&f, &bounds, &conv, 100).expect("root");
+        assert!((root - 3.0).abs() > 0.1);
+
+        // width alone stops once the bracket is narrow
+        let conv = DeltaX::new(1e-3);
+        let root = bisection(&f, &bounds, &conv, 100).expect("root");
+        assert!((root - 3.0).abs() < 1e-3);
+
+        // both must hold
+        let c = BracketAndResidual::new(1e-3, 1e-12);
+        let conv = MinIterations::new(&c, 0);
+        let root = bisection(&f, &bounds, &conv, 100).expect("root");
+        assert!(in_f(root).abs() < 1e-12);
+
+        let c = DeltaX::new(1e-3);
+        let width_only = MinIterations::new(&c, 0);
+        let _ = bisection(&f, &bounds, &width_only, 100);
+        assert!(conv.calls() > width_only.calls());
+    }
+
+    #[test]
+    fn test_false_position_biased() {
+        // Ford95 Example Eight: f(a) is huge yet the root is nearer a
+        let in_f = |x: f64| (21000. / x).exp() / (1.11 * 100000000000. * x * x) - 1.;
+        let inner = RealFn::new(&in_f);
+        let bounds = Bounds::new(350.0, 850.0);
+
+        let f = CountingFn::new(&inner);
+        let root = false_position_illinios(&f, &bounds, 100).expect("root");
+        assert!((root - 551.77382493033).abs() < 1e-8);
+        let evals = f.f_evals();
+
+        let f = CountingFn::new(&inner);
+        let root_biased = false_position_biased(&f, &bounds, 0.0, 100).expect("root");
+        assert_eq!(root, root_biased);
+        assert_eq!(evals, f.f_evals());
+
+        let f = CountingFn::new(&inner);
+        let root_biased = false_position_biased(&f, &bounds, -1e-9, 100).expect("root");
+        assert!((root_biased - 551.77382493033).abs() < 1e-8);
+        assert!(f.f_evals() < evals, "{} vs {}", f.f_evals(), evals);
+    }
+
+    #[test]
+    fn test_bisection_with_endpoints_no_sign_change() {
+        let in_f = |x: f64| x * x - 2.0;
+        let f = RealFn::new(&in_f);
+        match bisection_with_endpoints(&f, &Bounds::new(0.0, 2.0), 1.0, 2.0, 100) {
+            Err(RootError::NoSignChange { .. }) => {}
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+    }
+
+    #[test]
+    fn test_bracketing_degenerate() {
+        let in_f = |x: f64| x * x - 2.0;
+        let f = RealFn::new(&in_f);
+        let bounds = Bounds::new(2.0, 2.0);
+
+        match bisection(&f, &bounds, &DeltaX::new(1e-9), 100) {
+            Err(RootError::DegenerateBracket { x }) => {
+                assert_eq!(x, 2.0);
+            }
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+        match false_position_illinios(&f, &bounds, 100) {
+            Err(RootError::DegenerateBracket { .. }) => {}
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+        match steffensen_bracketed(&f, &bounds, &DeltaX::new(1e-9), 100) {
+            Err(RootError::DegenerateBracket { .. }) => {}
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+    }
+
+    #[test]
+    fn test_bisection_log() {
+        let evals = Cell::new(0);
+        let in_f = |x: f64| {
+            evals.set(evals.get() + 1);
+            x - 0.01
+        };
+        let f = RealFn::new(&in_f);
+        let bounds = Bounds::new(0.004, 200.0);
+
+        let root = bisection(&f, &bounds, &DeltaX::new(1e-9), 100).expect("root");
+        assert!((root - 0.01).abs() < 1e-9);
+        let evals_arithmetic = evals.replace(0);
+
+        let root = bisection_log(&f, &bounds, 100).expect("root");
+        assert!((root - 0.01).abs() < 1e-9);
+        let evals_geometric = evals.get();
+
+        assert!(
+            evals_geometric + 10 < evals_arithmetic,
+            "geometric={} arithmetic={}",
+            evals_geometric,
+            evals_arithmetic
+        );
+
+        // non-positive brackets still work
+        let in_g = |x: f64| x - 0.5;
+        let g = RealFn::new(&in_g);
+        let root = bisection_log(&g, &Bounds::new(-1.0, 2.0), 100).expect("root");
+        assert!((root - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_bracketing_crossing() {
+        // increasing through the root at sqrt(2)
+        let in_f = |x: f64| x * x - 2.0;
+        let f = RealFn::new(&in_f);
+        let bounds = Bounds::new(0.0, 2.0);
+
+        for &crossing in [Crossing::NegToPos, Crossing::Either].iter() {
+            let root = bisection_crossing(&f, &bounds, crossing, 100).expect("root");
+            assert!((root - 2.0f64.sqrt()).abs() < 1e-9);
+            let root = false_position_illinios_crossing(&f, &bounds, crossing, 100).expect("root");
+            assert!((root - 2.0f64.sqrt()).abs() < 1e-9);
+        }
+
+        match bisection_crossing(&f, &bounds, Crossing::PosToNeg, 100) {
+            Err(RootError::NoSignChange { a, b }) => {
+                assert_eq!((0.0, 2.0), (a, b));
+            }
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+        match false_position_illinios_crossing(&f, &bounds, Crossing::PosToNeg, 100) {
+            Err(RootError::NoSignChange { .. }) => {}
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+    }
+
+    /*
+     * Numerical derivatives.
+     */
+    #[test]
+    fn test_table_numerical_diff() {
+        for t in make_root_tests() {
+            let numerical = RealFnNumericalDiff::new(&*t.f);
+            for x in t.roots.iter() {
+                let exact = (t.df)(*x);
+                let err = (numerical.eval_df(*x) - exact).abs() / exact.abs().max(1.0);
+                assert!(err < 1e-5, "{} error {} at x={}", t.name, err, x);
+            }
+        }
+
+        // fixed step, too large for good accuracy
+        let in_f = |x: f64| x * x * x;
+        let coarse = RealFnNumericalDiff::new(&in_f).with_step(0.1);
+        assert!((coarse.eval_df(1.0) - 3.01).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_table_newton_numerical_diff() {
+        // looser than test_table_newton, since df is only approximate
+        let c1 = DeltaX::new(1e-7);
+        let c2 = FnResidual::new(1e-8);
+        let conv = DualCriteria::new(&c1, &c2);
+
+        for t in make_root_tests() {
+            for i in 0..t.roots.len() {
+                let f = RealFnNumericalDiff::new(&*t.f);
+                let root = newton_raphson(&f, t.guesses[i], &conv, 100)
+                    .unwrap_or_else(|_| panic!("root for {}", t.name));
+
+                assert!(
+                    (root - t.roots[i]).abs() < 1e-7,
+                    "{} root wanted={}, got={}",
+                    t.name,
+                    t.roots[i],
+                    root
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_table_numerical_diff_richardson() {
+        // entries smooth well beyond the extrapolation's base step
+        let smooth = [
+            "Ford95 Example Seven",
+            "Ford95 Example Ten",
+            "Ford95 Example Eleven",
+            "Costabile06 Example Two",
+            "Costabile06 Example Seventeen",
+            "Factored Parabola",
+            "Wikipedia NR Parabola",
+            "Wikipedia NR Trigonometry",
+            "Wikipedia Bisection Cubic",
+            "Isaac Newton's NR Example",
+        ];
+
+        for t in make_root_tests()
+            .into_iter()
+            .filter(|t| smooth.contains(&t.name.as_str()))
+        {
+            let central = RealFnNumericalDiff::new(&*t.f);
+            let extrapolated = RealFnNumericalDiff::new(&*t.f).richardson(3);
+
+            for x in t.roots.iter().chain(t.guesses.iter()) {
+                let exact = (t.df)(*x);
+                let scale = exact.abs().max(1.0);
+                let err_central = (central.eval_df(*x) - exact).abs() / scale;
+                let err_extrapolated = (extrapolated.eval_df(*x) - exact).abs() / scale;
+
+                assert!(
+                    err_central < 1e-8,
+                    "{} central error {} at x={}",
+                    t.name,
+                    err_central,
+                    x
+                );
+                assert!(
+                    err_extrapolated < 1e-12,
+                    "{} extrapolated error {} at x={}",
+                    t.name,
+                    err_extrapolated,
+                    x
+                );
+            }
+        }
+    }
+
+    /*
+     * Bisection corner cases
+     */
+    #[test]
+    fn test_bisection_no_straddle() {
+        let f = |x| x * x;
+        let bounds = Bounds::new(-10.0, -5.0);
+        match bisection(&RealFn::new(&f), &bounds, &DeltaX::new(1e-9), 100) {
+            Err(RootError::NoSignChange { a, b }) => {
+                assert_eq!(a, -10.0);
+                assert_eq!(b, -5.0);
+            }
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+
+        // other bracketing solvers too
+        let f = RealFn::new(&f);
+        match false_position_illinios(&f, &bounds, 100) {
+            Err(RootError::NoSignChange { .. }) => {}
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+        match brent_dekker(&f, &bounds, 100) {
+            Err(RootError::NoSignChange { .. }) => {}
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+        match ridders(&f, &bounds, 100) {
+            Err(RootError::NoSignChange { .. }) => {}
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+    }
+
+    #[test]
+    fn test_bisection_centered_root() {
+        let f = |x| x;
+        let bounds = Bounds::new(-1000000.0, 1000000.0);
+        let root =
+            bisection(&RealFn::new(&f), &bounds, &DeltaX::new(1e-9), 100).expect("found root");
+        assert!(root.abs() < 1e-9, "wanted root x=0");
+    }
+
+    #[test]
+    fn test_bisection_ulps() {
+        let in_f = |x: f64| x;
+        let inner = RealFn::new(&in_f);
+        let f = CountingFn::new(&inner);
+        let root = bisection_ulps(&f, &Bounds::new(-1e18, 1e18), 1, 100).expect("found root");
+        assert_eq!(root, 0.0);
+        assert!(f.f_evals() <= 66);
+
+        // root far from zero, where 1e-9 absolute width is unreachable
+        let in_f = |x: f64| x - 12345678.9e10;
+        let inner = RealFn::new(&in_f);
+        let f = CountingFn::new(&inner);
+        let root = bisection_ulps(&f, &Bounds::new(-1e300, 1e300), 1, 100).expect("found root");
+        assert!((root - 12345678.9e10).abs() <= 12345678.9e10 * f64::EPSILON);
+        assert!(f.f_evals() <= 66);
+
+        // decreasing f, bracket not spanning zero
+        let in_f = |x: f64| 3.0 - x * x;
+        let root = bisection_ulps(&RealFn::new(&in_f), &Bounds::new(1.0, 1e150), 4, 100);
+        assert!((root.expect("found root") - 3f64.sqrt()).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_ordered_bits() {
+        let xs = [f64::MIN, -1.0, -1e-310, -0.0, 0.0, 1e-310, 1.0, f64::MAX];
+        for w in xs.windows(2) {
+            assert!(ordered_bits(w[0]) <= ordered_bits(w[1]));
+        }
+        for &x in xs.iter() {
+            assert_eq!(from_ordered_bits(ordered_bits(x)), x);
+        }
+        assert_eq!(ordered_bits(-0.0), 0);
+        assert_eq!(ordered_bits(1.0) + 1, ordered_bits(1.0 + f64::EPSILON));
+        assert_eq!(ordered_bits(-1.0) - 1, ordered_bits(-1.0 - f64::EPSILON));
+    }
+
+    #[test]
+    fn test_bisection_criteria() {
+        let in_f = |x: f64| x * x - 2.0;
+        let f = RealFn::new(&in_f);
+        let bounds = Bounds::new(0.0, 2.0);
+
+        // loose tolerance stops early
+        let c1 = DeltaX::new(1e-3);
+        let loose = MinIterations::new(&c1, 0);
+        let root = bisection(&f, &bounds, &loose, 100).expect("root");
+        assert!((root - 2f64.sqrt()).abs() < 1e-3);
+
+        let c2 = DeltaX::new(1e-9);
+        let tight = MinIterations::new(&c2, 0);
+        bisection(&f, &bounds, &tight, 100).expect("root");
+        assert!(loose.calls() < tight.calls());
+
+        // residual based criteria work too
+        let finish = FnResidual::new(1e-12);
+        let root = bisection(&f, &bounds, &finish, 100).expect("root");
+        assert!(in_f(root).abs() < 1e-12);
+
+        // deprecated wrapper matches an explicit DeltaX
+        #[allow(deprecated)]
+        let root_default = bisection_default_tol(&f, &bounds, 100).expect("root");
+        assert_eq!(
+            root_default,
+            bisection(&f, &bounds, &c2, 100).expect("root")
+        );
+    }
+
+    #[test]
+    fn test_bisection_singularity() {
+        let in_f = |x: f64| 1.0 / x;
+        let f = RealFn::new(&in_f);
+        match bisection(&f, &Bounds::new(-1.0, 1.0), &DeltaX::new(1e-9), 100) {
+            Err(RootError::ConvergedToSingularity { x, residual }) => {
+                assert!(x.abs() < 1e-9);
+                assert!(residual > 1e8);
+            }
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+
+        // off-center pole, approached from the left endpoint only
+        let in_f = |x: f64| 1.0 / (x - 0.7);
+        let f = RealFn::new(&in_f);
+        match bisection(&f, &Bounds::new(-1.0, 1.0), &DeltaX::new(1e-9), 100) {
+            Err(RootError::ConvergedToSingularity { .. }) => {}
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+
+        // a root where the residual only just shrinks is still a root
+        let in_f = |x: f64| x - 1e-6;
+        let f = RealFn::new(&in_f);
+        let finish = DeltaX::new(1e-9);
+        let root = bisection(&f, &Bounds::new(0.0, 1.0), &finish, 100).expect("found root");
+        assert!((root - 1e-6).abs() < 1e-9);
+
+        // tiny |f(a)| at the start must not make a simple root look like a pole
+        let in_f = |x: f64| x * (x - 2.0) - 1e-12;
+        let f = RealFn::new(&in_f);
+        let root = bisection(&f, &Bounds::new(0.0, 3.0), &finish, 100).expect("found root");
+        assert!((root - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_find_roots() {
+        let roots = find_roots(|x: f64| x.sin(), -0.1, 6.3, 1e-9);
+        assert_eq!(roots.len(), 3);
+        for (i, root) in roots.iter().enumerate() {
+            let expected = (i as f64) * f64::consts::PI;
+            assert!(
+                (root - expected).abs() < 1e-9,
+                "got={}, wanted={}",
+                root,
+                expected
+            );
+        }
+
+        // unreachable tolerance still gives the best available
+        let roots = find_roots(|x: f64| x - 1e6, 0.0, 2e6, 1e-300);
+        assert_eq!(roots.len(), 1);
+        assert!((roots[0] - 1e6).abs() < 1e-9);
+
+        // no roots, empty range
+        assert!(find_roots(|x: f64| x * x + 1.0, -5.0, 5.0, 1e-9).is_empty());
+        assert!(find_roots(|x: f64| x, 1.0, 1.0, 1e-9).is_empty());
+    }
+
+    #[test]
+    fn test_find_all_roots() {
+        let in_f = |x: f64| x.sin();
+        let f = RealFn::new(&in_f);
+        let bounds = Bounds::new(-0.1, 6.3);
+        let finish = DeltaX::new(1e-9);
+
+        for &(name, method) in &[
+            ("bisection", BracketMethod::Bisection(&finish)),
+            ("brent", BracketMethod::Brent),
+            ("illinois", BracketMethod::Illinois),
+        ] {
+            let roots = find_all_roots(&f, &bounds, 0.1, method, 100);
+            assert_eq!(roots.len(), 3, "{}", name);
+            for (i, root) in roots.iter().enumerate() {
+                let expected = (i as f64) * f64::consts::PI;
+                assert!(
+                    (root - expected).abs() < 1e-8,
+                    "{} got={}, wanted={}",
+                    name,
+                    root,
+                    expected
+                );
+            }
+        }
+
+        // no roots
+        let in_g = |x: f64| x * x + 1.0;
+        let g = RealFn::new(&in_g);
+        assert!(find_all_roots(&g, &bounds, 0.1, BracketMethod::Brent, 100).is_empty());
+
+        // bisection honors the caller's criteria
+        let loose = DeltaX::new(1e-3);
+        let method = BracketMethod::Bisection(&loose);
+        let roots = find_all_roots(&f, &bounds, 0.1, method, 100);
+        assert_eq!(roots.len(), 3);
+        assert!(roots.iter().any(|&r| r.sin().abs() > 1e-9));
+        assert!(roots.iter().all(|&r| r.sin().abs() < 1e-3));
+    }
+
+    /*
+     * Newton-Raphson corner cases.
+     */
+    #[test]
+    #[should_panic]
+    fn test_newton_nonfinite_start() {
+        let in_f = |x| (x - 5.0) * (x - 4.0);
+        let in_df = |x| 2.0 * x - 9.0;
+        let f = RealFnAndFirst::new(&in_f, &in_df);
+
+        let conv = DeltaX::new(1e-9);
+        let _ = newton_raphson(&f, f64::NAN, &conv, 100);
+    }
+
+    #[test]
+    fn test_newton_zero_derivative() {
+        let in_f = |_| 2.0;
+        let in_df = |_| 0.0;
+        let f = RealFnAndFirst::new(&in_f, &in_df);
+
+        let conv = DeltaX::new(1e-9);
+        match newton_raphson(&f, 5.8, &conv, 100).expect_err("zero derivative not ok") {
+            RootError::ZeroDerivative { .. } => {}
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+    }
+
+    #[test]
+    fn test_table_safe_newton() {
+        let c1 = DeltaX::new(1e-10);
+        let c2 = FnResidual::new(1e-9);
+        let conv = DualCriteria::new(&c1, &c2);
+
+        for t in make_root_tests() {
+            for i in 0..t.roots.len() {
+                let f = RealFnAndFirst::new(&*t.f, &*t.df);
+                let root = safe_newton(&f, &t.brackets[i], &conv, 100)
+                    .unwrap_or_else(|_| panic!("root for {}", t.name));
+
+                assert!(
+                    (root - t.roots[i]).abs() < 1e-9,
+                    "{} root wanted={}, got={}",
+                    t.name,
+                    t.roots[i],
+                    root
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_safe_newton_where_newton_diverges() {
+        struct DivergeTest {
+            name: &'static str,
+            f: Box<dyn Fn(f64) -> f64>,
+            df: Box<dyn Fn(f64) -> f64>,
+            root: f64,
+            guess: f64,
+            bracket: Bounds,
+        }
 
-        // Tbl 2 - no turning points or inflections in [0,1]
-        let roots = [
-            0.422477709641236,
-            0.138257155056824,
-            0.046209810152571,
-            0.034657359020853,
+        let tests = vec![
+            DivergeTest {
+                // Costabile06 Example Twenty Two, outside the converging region
+                name: "x^20 - 1",
+                f: Box::new(|x| x.powi(20) - 1.0),
+                df: Box::new(|x| 20. * x.powi(19)),
+                root: 1.0,
+                guess: 0.1,
+                bracket: Bounds::new(0.1, 5.0),
+            },
+            DivergeTest {
+                name: "atan(x - 1)",
+                f: Box::new(|x| (x - 1.).atan()),
+                df: Box::new(|x| 1. / (1. + (x - 1.) * (x - 1.))),
+                root: 1.0,
+                guess: -30.0,
+                bracket: Bounds::new(-30.0, 40.0),
+            },
+            DivergeTest {
+                name: "x * exp(-x)",
+                f: Box::new(|x| x * (-x).exp()),
+                df: Box::new(|x| (1. - x) * (-x).exp()),
+                root: 0.0,
+                guess: 2.0,
+                bracket: Bounds::new(-0.5, 2.0),
+            },
+            DivergeTest {
+                // newton cycles 0, 1, 0, 1, ...
+                name: "x^3 - 2x + 2",
+                f: Box::new(|x| x * x * x - 2. * x + 2.),
+                df: Box::new(|x| 3. * x * x - 2.),
+                root: -1.769292354238631,
+                guess: 0.0,
+                bracket: Bounds::new(-3.0, 0.0),
+            },
         ];
-        for (i, ni) in vec![1, 5, 15, 20].iter().enumerate() {
-            let name = format!("Dowell71 Table 2 for n={}", ni);
-            let n = *ni as f64;
-            let f = move |x: f64| 2. * x * (-n).exp() + 1. - 2. * (-n * x).exp();
-            let df = move |x: f64| 2. * (n * (-n * x).exp() + (-n).exp());
-            let d2f = move |x: f64| -2. * n * n * (-n * x).exp();
 
-            cases.push(RootTest {
-                name: name,
-                f: Box::new(f),
-                df: Box::new(df),
-                d2f: Box::new(d2f),
-                roots: vec![roots[i]],
-                guesses: vec![0.1],
-                brackets: vec![Bounds::new(0., 1.)],
-            });
-        }
+        let c1 = DeltaX::new(1e-10);
+        let c2 = FnResidual::new(1e-9);
+        let conv = DualCriteria::new(&c1, &c2);
 
-        // Tbl 3 - one turning point on [0,1].
-        for ni in vec![2, 5, 15, 20].iter() {
-            let name = format!("Dowell71 Table 3 for n={}", ni);
-            let n = *ni as f64;
-            let f = move |x: f64| (1. + (1. - n).powi(2)) * x - (1. - n * x).powi(2);
-            let df = move |x: f64| n * n * (1. - 2. * x) + 2.;
-            let d2f = move |_| -2. * n * n;
+        for t in tests {
+            let f = RealFnAndFirst::new(&*t.f, &*t.df);
 
-            let root = (-(n * n * n * n + 4.).sqrt() + n * n + 2.) / (2. * n * n);
+            // plain newton fails, or at best wanders off to the wrong place
+            if let Ok(root) = newton_raphson(&f, t.guess, &conv, 100) {
+                assert!((root - t.root).abs() > 1e-3, "{} newton converged", t.name);
+            }
 
-            cases.push(RootTest {
-                name: name,
-                f: Box::new(f),
-                df: Box::new(df),
-                d2f: Box::new(d2f),
-                roots: vec![root],
-                guesses: vec![0.1],
-                brackets: vec![Bounds::new(0., 1.)],
-            });
+            let root = safe_newton_with_bisections(&f, &t.bracket, 0, &conv, 100)
+                .unwrap_or_else(|_| panic!("root for {}", t.name));
+            assert!(
+                (root - t.root).abs() < 1e-9,
+                "{} root wanted={}, got={}",
+                t.name,
+                t.root,
+                root
+            );
         }
+    }
 
-        // Tbl 4 - one inflection on [0,1].
-        let roots = [0.5, 0.345954815848242, 0.195547623536565, 0.164920957276441];
-        for (i, ni) in vec![2, 5, 15, 20].iter().enumerate() {
-            let name = format!("Dowell71 Table 4 for n={}", ni);
-            let n = *ni as f64;
-            let f = move |x: f64| x * x - (1. - x).powi(n as i32);
-            let df = move |x: f64| n * (1. - x).powi((n as i32) - 1) + 2. * x;
-            let d2f = move |x: f64| 2. - (n - 1.) * n * (1. - x).powi((n as i32) - 2);
+    #[test]
+    fn test_safe_newton_no_sign_change() {
+        let in_f = |x: f64| x * x + 1.0;
+        let in_df = |x: f64| 2.0 * x;
+        let f = RealFnAndFirst::new(&in_f, &in_df);
 
-            cases.push(RootTest {
-                name: name,
-                f: Box::new(f),
-                df: Box::new(df),
-                d2f: Box::new(d2f),
-                roots: vec![roots[i]],
-                guesses: vec![0.1],
-                brackets: vec![Bounds::new(0., 1.)],
-            });
+        let conv = DeltaX::new(1e-9);
+        match safe_newton(&f, &Bounds::new(-1.0, 1.0), &conv, 100) {
+            Err(RootError::NoSignChange { .. }) => {}
+            _ => {
+                panic!("incorrect error type");
+            }
         }
+    }
 
-        // Tbl 5 - one turning point and one inflection on [0,1].
-        let roots = [
-            0.13775402050032426,
-            0.0036171081783322734,
-            2.598957598820562e-05,
-            7.668594662391115e-06,
-        ];
-        for (i, ni) in vec![2, 5, 15, 20].iter().enumerate() {
-            let name = format!("Dowell71 Table 5 for n={}", ni);
-            let n = *ni as f64;
-            let f = move |x: f64| (1. + (1. - n).powi(4)) * x - (1. - n * x).powi(4);
-            let df = move |x: f64| -4. * n * (n * x - 1.).powi(3) + (n - 1.).powi(4) + 1.;
-            let d2f = move |x: f64| -12. * n * n * (n * x - 1.).powi(2);
+    #[test]
+    fn test_safe_newton_initial_bisections() {
+        // newton on atan diverges when started more than ~1.39 from the root
+        let steps = RefCell::new(Vec::new());
+        let in_f = |x: f64| (x - 1.0).atan();
+        let in_df = |x: f64| {
+            steps.borrow_mut().push(x);
+            1.0 / (1.0 + (x - 1.0) * (x - 1.0))
+        };
+        let f = RealFnAndFirst::new(&in_f, &in_df);
+        let b = Bounds::new(-30.0, 40.0);
+        let conv = DeltaX::new(1e-12);
 
-            cases.push(RootTest {
-                name: name,
-                f: Box::new(f),
-                df: Box::new(df),
-                d2f: Box::new(d2f),
-                roots: vec![roots[i]],
-                guesses: vec![0.1],
-                brackets: vec![Bounds::new(0., 1.)],
-            });
+        // a newton step landing farther from the root than it started
+        let diverged = |steps: &[f64]| {
+            steps
+                .windows(2)
+                .any(|w| (w[1] - 1.0).abs() > (w[0] - 1.0).abs())
+        };
+
+        let root = safe_newton(&f, &b, &conv, 100).expect("root");
+        assert!((root - 1.0).abs() < 1e-12);
+        assert!(diverged(&steps.borrow()));
+
+        steps.borrow_mut().clear();
+        let root = safe_newton_with_bisections(&f, &b, 4, &conv, 100).expect("root");
+        assert!((root - 1.0).abs() < 1e-12);
+        assert!(!diverged(&steps.borrow()));
+    }
+
+    #[test]
+    fn test_estimate_multiplicity() {
+        let in_f = |x: f64| x.exp() * (x - 1.0).powi(3);
+        let in_df = |x: f64| x.exp() * ((x - 1.0).powi(3) + 3.0 * (x - 1.0).powi(2));
+        let f = RealFnAndFirst::new(&in_f, &in_df);
+        assert_eq!(estimate_multiplicity(&f, 1.0), 3);
+
+        let in_f = |x: f64| x.exp() * (x - 1.0).powi(5);
+        let in_df = |x: f64| x.exp() * ((x - 1.0).powi(5) + 5.0 * (x - 1.0).powi(4));
+        let f = RealFnAndFirst::new(&in_f, &in_df);
+        assert_eq!(estimate_multiplicity(&f, 1.0), 5);
+
+        // root only known approximately
+        assert_eq!(estimate_multiplicity(&f, 1.0 + 1e-5), 5);
+    }
+
+    #[test]
+    fn test_estimate_multiplicity_simple() {
+        let in_f = |x: f64| x.sin();
+        let in_df = |x: f64| x.cos();
+        let f = RealFnAndFirst::new(&in_f, &in_df);
+        assert_eq!(estimate_multiplicity(&f, f64::consts::PI), 1);
+    }
+
+    #[test]
+    fn test_newton_trace_scratch() {
+        let in_f = |x: f64| -x * x + 2.0 * x + 1.0;
+        let in_df = |x: f64| -2.0 * x + 2.0;
+        let f = RealFnAndFirst::new(&in_f, &in_df);
+        let conv = DeltaX::new(1e-9);
+
+        let (root, trace) = newton_raphson_trace(&f, 3.0, &conv, 100).expect("root");
+        let mut scratch = SolverScratch::new();
+        for _ in 0..3 {
+            let root_scratch =
+                newton_raphson_trace_scratch(&f, 3.0, &conv, 100, &mut scratch).expect("root");
+            assert_eq!(root, root_scratch);
+            assert_eq!(trace, scratch.trace());
         }
+    }
 
-        // Tbl 6 - curves laying increasingly close to the x-axis for large n
-        let roots = [
-            0.4010581375414404,
-            0.5161535187571644,
-            0.5395222269080477,
-            0.5481822943411316,
-        ];
-        for (i, ni) in vec![1, 5, 10, 15].iter().enumerate() {
-            let name = format!("Dowell71 Table 6 for n={}", ni);
-            let n = *ni as f64;
-            let f = move |x: f64| (-n * x).exp() * (x - 1.) + x.powi(n as i32);
-            let df = move |x: f64| n * x.powi((n as i32) - 1) + (-n * x).exp() * (n * -x + n + 1.);
-            let d2f = move |x: f64| {
-                n * ((n - 1.) * x.powi((n as i32) - 2) + (-n * x).exp() * (n * (x - 1.) - 2.))
-            };
+    #[test]
+    fn test_newton_builder() {
+        let in_f = |x: f64| x * x - 2.0;
+        let in_df = |x: f64| 2.0 * x;
+        let f = RealFnAndFirst::new(&in_f, &in_df);
 
-            cases.push(RootTest {
-                name: name,
-                f: Box::new(f),
-                df: Box::new(df),
-                d2f: Box::new(d2f),
-                roots: vec![roots[i]],
-                guesses: vec![0.1],
-                brackets: vec![Bounds::new(0., 1.)],
-            });
+        // defaults match the free function with DeltaX(1e-9) and 100 iterations
+        let root = NewtonBuilder::new(&f).start(1.0).solve().expect("root");
+        let conv = DeltaX::new(1e-9);
+        assert_eq!(root, newton_raphson(&f, 1.0, &conv, 100).expect("root"));
+
+        let conv = FnResidual::new(1e-15);
+        let root = NewtonBuilder::new(&f)
+            .convergence(&conv)
+            .start(1.0)
+            .solve()
+            .expect("root");
+        assert_eq!(root, newton_raphson(&f, 1.0, &conv, 100).expect("root"));
+
+        match NewtonBuilder::new(&f).start(1.0).max_iter(1).solve() {
+            Err(RootError::IterationLimit { .. }) => {}
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+        match NewtonBuilder::new(&f).solve() {
+            Err(RootError::InvalidParameter { name: "start", .. }) => {}
+            _ => {
+                panic!("incorrect error type");
+            }
         }
+    }
 
-        // Tbl 7 - curves with y-axis asymptotic
-        for ni in vec![2, 5, 15, 20].iter() {
-            let name = format!("Dowell71 Table 7 for n={}", ni);
-            let n = *ni as f64;
-            let f = move |x: f64| (n * x - 1.) / ((n - 1.) * x);
-            let df = move |x: f64| 1. / ((n - 1.) * x * x);
-            let d2f = move |x: f64| -2. / ((n - 1.) * x * x * x);
+    #[test]
+    fn test_newton_traced() {
+        let in_f = |x: f64| x * x - 2.0;
+        let in_df = |x: f64| 2.0 * x;
+        let f = RealFnAndFirst::new(&in_f, &in_df);
+        let conv = DeltaX::new(1e-12);
 
-            let root = 1. / n;
+        let mut steps = Vec::new();
+        let root = newton_raphson_traced(&f, 3.0, &conv, 100, |i, x, fx| steps.push((i, x, fx)))
+            .expect("root");
+        assert_eq!(root, steps.last().unwrap().1);
 
-            cases.push(RootTest {
-                name: name,
-                f: Box::new(f),
-                df: Box::new(df),
-                d2f: Box::new(d2f),
-                roots: vec![root],
-                guesses: vec![0.01],
-                brackets: vec![Bounds::new(0.01, 1.0)],
-            });
+        // from the right on a convex function, iterates approach sqrt(2) from above
+        let sqrt2 = 2f64.sqrt();
+        let mut err_pre = 3.0 - sqrt2;
+        for (n, &(i, x, fx)) in steps.iter().enumerate() {
+            assert_eq!(i, n + 1);
+            assert_eq!(fx, in_f(x));
+            assert!(x - sqrt2 > -1e-15);
+            assert!((x - sqrt2).abs() <= err_pre);
+            err_pre = (x - sqrt2).abs();
         }
+    }
 
-        cases
+    #[test]
+    fn test_estimate_convergence_order() {
+        // errors square each step, ending below rounding noise
+        let iterates = [2.1, 2.01, 2.0001, 2.00000001, 2.0 + 1e-16];
+        let q = estimate_convergence_order(&iterates, 2.0);
+        assert!((q - 2.0).abs() < 1e-6, "order {}", q);
+
+        // linear convergence
+        let iterates: Vec<f64> = (0..10).map(|i| 0.5f64.powi(i)).collect();
+        let q = estimate_convergence_order(&iterates, 0.0);
+        assert!((q - 1.0).abs() < 1e-12, "order {}", q);
+
+        assert!(estimate_convergence_order(&[2.1, 2.01], 2.0).is_nan());
     }
 
-    /// These tests have miscellaneous pedigrees.  Some are originals.
-    ///
-    fn make_root_tests_misc() -> Vec<RootTest> {
-        vec![
-            RootTest {
-                name: "Factored Parabola".to_owned(),
-                f: Box::new(|x| (x - 5.0) * (x - 4.0)),
-                df: Box::new(|x| 2.0 * x - 9.0),
-                d2f: Box::new(|_| 2.0),
-                roots: vec![5.0, 4.0],
-                guesses: vec![5.8, 3.8],
-                brackets: vec![Bounds::new(4.5, 100.0), Bounds::new(-100000.0, 4.01)],
-            },
-            RootTest {
-                name: "Wikipedia NR Parabola".to_owned(),
-                f: Box::new(|x| x * x - 612.0),
-                df: Box::new(|x| 2.0 * x),
-                d2f: Box::new(|_| 2.0),
-                roots: vec![-24.7386337537, 24.7386337537],
-                guesses: vec![-10.0, 10.0],
-                brackets: vec![Bounds::new(-30.0, 10.0), Bounds::new(10.0, 30.0)],
-            },
-            RootTest {
-                name: "Wikipedia NR Trigonometry".to_owned(),
-                f: Box::new(|x| x.cos() - x * x * x),
-                df: Box::new(|x| -x.sin() - 3. * x * x),
-                d2f: Box::new(|x| -x.cos() - 6. * x),
-                roots: vec![0.865474033102],
-                guesses: vec![0.5],
-                brackets: vec![Bounds::new(0.0, 1.0)],
-            },
-            RootTest {
-                name: "Wikipedia Bisection Cubic".to_owned(),
-                f: Box::new(|x| x * x * x - x - 2.0),
-                df: Box::new(|x| 3.0 * x * x - 1.0),
-                d2f: Box::new(|x| 6.0 * x),
-                roots: vec![1.52137970680457],
-                guesses: vec![1.0],
-                brackets: vec![Bounds::new(1.0, 2.0)],
-            },
-            RootTest {
-                name: "Isaac Newton's Secant Example".to_owned(),
-                f: Box::new(|x| x * x * x + 10.0 * x * x - 7.0 * x - 44.0),
-                df: Box::new(|x| 3.0 * x * x + 20.0 * x - 7.0),
-                d2f: Box::new(|x| 6.0 * x + 20.0),
-                roots: vec![2.20681731724844],
-                guesses: vec![2.2],
-                brackets: vec![Bounds::new(2.0, 2.3)],
-            },
-            RootTest {
-                name: "Isaac Newton's NR Example".to_owned(),
-                f: Box::new(|x| x * x * x - 2.0 * x - 5.0),
-                df: Box::new(|x| 3.0 * x * x - 2.0),
-                d2f: Box::new(|x| 6.0 * x),
-                roots: vec![2.0945514815423265],
-                guesses: vec![2.0],
-                brackets: vec![Bounds::new(2.0, 3.0)],
-            },
-            RootTest {
-                name: "Thomas Simpson NR Example".to_owned(),
-                f: Box::new(|x| {
-                    (1. - x).sqrt() + (1. - 2. * x * x).sqrt() + (1. - 3. * x * x * x).sqrt() - 2.
-                }),
-                df: Box::new(|x| {
-                    -2. * x * (1. - 2. * x * x).sqrt().recip()
-                        - 9. * x * x * (1. - 3. * x * x * x).sqrt().recip() / 2.
-                        - 1. * (1. - x).sqrt().recip() / 2.
-                }),
-                d2f: Box::new(|x| {
-                    -9. * x * (1. - 3. * x * x * x).sqrt().recip()
-                        - 4. * x * x * (1. - 2. * x * x).powf(1.5)
-                        - 2. * (1. - 2. * x * x).sqrt().recip()
-                        - 81. * x * x * x * x * (1. - 3. * x * x * x).powf(1.5) / 4.
-                        - 1. * (1. - x).powf(1.5) / 4.
-                }),
-                roots: vec![0.55158615249704711724768527],
-                guesses: vec![0.5],
-                brackets: vec![Bounds::new(0.0, 0.69)],
-            },
-        ]
+    #[test]
+    fn test_estimate_convergence_order_newton() {
+        let in_f = |x: f64| x * x - 2.0;
+        let in_df = |x: f64| 2.0 * x;
+        let f = RealFnAndFirst::new(&in_f, &in_df);
+        let conv = DeltaX::new(1e-15);
+
+        let (root, trace) = newton_raphson_trace(&f, 3.0, &conv, 100).expect("root");
+        let q = estimate_convergence_order(&trace, root);
+        assert!((q - 2.0).abs() < 0.2, "order {}", q);
     }
 
-    /// Table driven tests re-used for different methods.
-    fn make_root_tests() -> Vec<RootTest> {
-        let mut v = Vec::new();
-        v.extend(make_root_tests_ford95());
-        v.extend(make_root_tests_costabile06());
-        v.extend(make_root_tests_dowell71());
-        v.extend(make_root_tests_misc());
-        v
+    #[test]
+    fn test_newton_robust_zero_derivative() {
+        // from 2.0 the first newton step lands exactly on the local max at 1.0
+        let in_f = |x: f64| x * x * x - 3.0 * x + 7.0;
+        let in_df = |x: f64| 3.0 * x * x - 3.0;
+        let f = RealFnAndFirst::new(&in_f, &in_df);
+        let conv = DeltaX::new(1e-12);
+
+        match newton_raphson(&f, 2.0, &conv, 100) {
+            Err(RootError::ZeroDerivative { x_cur }) => assert_eq!(x_cur, 1.0),
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+
+        let root = newton_raphson_robust(&f, 2.0, &conv, 100, 3).expect("root");
+        assert!(in_f(root).abs() < 1e-12);
+
+        match newton_raphson_robust(&f, 2.0, &conv, 100, 0) {
+            Err(RootError::ZeroDerivative { .. }) => {}
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
     }
 
-    /*
-     * Table-driven tests
-     */
     #[test]
-    fn test_table_bisection() {
-        for t in make_root_tests() {
-            for i in 0..t.roots.len() {
-                let f = RealFn::new(&*t.f);
-                let root =
-                    bisection(&f, &t.brackets[i], 100).expect(&format!("root for {}", t.name));
+    fn test_solve_to_x_tol() {
+        let in_f = |x: f64| x * x * x - 2.0 * x - 5.0;
+        let in_df = |x: f64| 3.0 * x * x - 2.0;
+        let f = RealFnAndFirst::new(&in_f, &in_df);
+        let expected = 2.0945514815423265;
+        for &x_tol in [1e-2, 1e-4, 1e-8].iter() {
+            let root = solve_to_x_tol(&f, &Bounds::new(2.0, 3.0), x_tol, 100).expect("root");
+            assert!(
+                (root - expected).abs() < x_tol,
+                "x_tol {} root {}",
+                x_tol,
+                root
+            );
+        }
 
-                assert!(
-                    (root - t.roots[i]).abs() < 1e-8,
-                    format!("{} root wanted={}, got={}", t.name, t.roots[i], root)
-                );
+        // shallow slope, where a fixed residual tolerance would stop far away
+        let in_f = |x: f64| 1e-6 * (x.exp() - 10.0);
+        let in_df = |x: f64| 1e-6 * x.exp();
+        let f = RealFnAndFirst::new(&in_f, &in_df);
+        let root = solve_to_x_tol(&f, &Bounds::new(0.0, 5.0), 1e-4, 100).expect("root");
+        assert!((root - 10.0f64.ln()).abs() < 1e-4);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_newton_deadline() {
+        use std::thread;
+
+        // from 0.7 newton overshoots to ~44.5 then creeps back to 1 slowly
+        let in_f = |x: f64| {
+            thread::sleep(Duration::from_millis(2));
+            x.powi(20) - 1.0
+        };
+        let in_df = |x: f64| 20.0 * x.powi(19);
+        let f = RealFnAndFirst::new(&in_f, &in_df);
+        let conv = DeltaX::new(1e-12);
+
+        let deadline = Deadline::new(Duration::from_millis(20));
+        match newton_raphson_deadline(&f, 0.7, &conv, 1000, &deadline) {
+            Err(RootError::Timeout { last_x, elapsed }) => {
+                assert!((0.7..45.0).contains(&last_x), "last_x {}", last_x);
+                assert!(elapsed >= Duration::from_millis(20));
+            }
+            _ => {
+                panic!("incorrect error type");
             }
         }
+
+        // generous deadline changes nothing
+        let deadline = Deadline::new(Duration::from_secs(3600));
+        let in_f = |x: f64| x.powi(20) - 1.0;
+        let f = RealFnAndFirst::new(&in_f, &in_df);
+        let root = newton_raphson_deadline(&f, 0.7, &conv, 1000, &deadline).expect("root");
+        assert_eq!(root, newton_raphson(&f, 0.7, &conv, 1000).expect("root"));
     }
 
     #[test]
-    fn test_table_newton() {
-        let c1 = DeltaX::new(1e-8);
-        let c2 = FnResidual::new(1e-9);
-        let conv = DualCriteria::new(&c1, &c2);
+    #[cfg(feature = "std")]
+    fn test_bisection_deadline() {
+        use std::thread;
 
-        for t in make_root_tests() {
-            for i in 0..t.roots.len() {
-                let f = RealFnAndFirst::new(&*t.f, &*t.df);
-                let root = newton_raphson(&f, t.guesses[i], &conv, 100)
-                    .expect(&format!("root for {}", t.name));
+        let in_f = |x: f64| {
+            thread::sleep(Duration::from_millis(2));
+            x * x - 2.0
+        };
+        let f = RealFn::new(&in_f);
+        let bounds = Bounds::new(0.0, 2.0);
 
-                assert!(
-                    (root - t.roots[i]).abs() < 1e-9,
-                    format!("{} root wanted={}, got={}", t.name, t.roots[i], root)
-                );
+        let deadline = Deadline::new(Duration::from_millis(10));
+        match bisection_deadline(&f, &bounds, 100, &deadline) {
+            Err(RootError::Timeout { last_x, .. }) => {
+                assert!(bounds.contains(last_x));
+                assert!((last_x - 2.0f64.sqrt()).abs() < 0.5);
+            }
+            _ => {
+                panic!("incorrect error type");
             }
         }
     }
 
     #[test]
-    fn test_table_halley() {
+    fn test_race_bisection_wins() {
+        // newton on atan diverges from 4
+        let in_f = |x: f64| (x - 1.0).atan();
+        let in_df = |x: f64| 1.0 / (1.0 + (x - 1.0) * (x - 1.0));
+        let f = RealFnAndFirst::new(&in_f, &in_df);
+        let conv = DeltaX::new(1e-9);
+        assert!(newton_raphson(&f, 4.0, &conv, 100).is_err());
+
+        let (root, method) = race(&f, &Bounds::new(-2.0, 10.0), 4.0, &conv, 100).expect("root");
+        assert_eq!(method, Method::Bisection);
+        assert!((root - 1.0).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_race_newton_wins() {
+        let in_f = |x: f64| x * x - 2.0;
+        let in_df = |x: f64| 2.0 * x;
+        let f = RealFnAndFirst::new(&in_f, &in_df);
+        let conv = DeltaX::new(1e-9);
+
+        let (root, method) = race(&f, &Bounds::new(0.0, 2.0), 1.5, &conv, 100).expect("root");
+        assert_eq!(method, Method::Newton);
+        assert!((root - 2.0f64.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_newton_final_step() {
+        let in_f = |x: f64| -x * x + 2.0 * x + 1.0;
+        let in_df = |x: f64| -2.0 * x + 2.0;
+        let f = RealFnAndFirst::new(&in_f, &in_df);
+
+        let conv = RecordingDeltaX::new(1e-6);
+        let result = newton_raphson_verbose(&f, 3.0, &conv, 100).expect("root");
+
+        assert!((result.root - 2.41421356237).abs() < 1e-9);
+        assert!(result.final_step < 1e-6);
+        assert_eq!(Some(result.final_step), conv.last_delta());
+        assert_eq!(
+            result.root,
+            newton_raphson(&f, 3.0, &conv, 100).expect("root")
+        );
+
+        // Halley reports the same
+        let in_d2f = |_: f64| -2.0;
+        let f = RealFnAndFirstSecond::new(&in_f, &in_df, &in_d2f);
+        let result = halley_method_verbose(&f, 3.0, &conv, 100).expect("root");
+        assert!(result.final_step < 1e-6);
+        assert!(result.iterations > 0);
+    }
+
+    #[test]
+    fn test_verbose_iterations_and_residual() {
         let c1 = DeltaX::new(1e-8);
         let c2 = FnResidual::new(1e-9);
         let conv = DualCriteria::new(&c1, &c2);
 
-        for t in make_root_tests() {
+        let mut iter_newton = 0;
+        let mut iter_halley = 0;
+        for t in make_root_tests_ford95() {
             for i in 0..t.roots.len() {
                 let f = RealFnAndFirstSecond::new(&*t.f, &*t.df, &*t.d2f);
-                let root = halley_method(&f, t.guesses[i], &conv, 100)
-                    .expect(&format!("root for {}", t.name));
+                let nr = newton_raphson_verbose(&f, t.guesses[i], &conv, 100)
+                    .unwrap_or_else(|_| panic!("root for {}", t.name));
+                let hm = halley_method_verbose(&f, t.guesses[i], &conv, 100)
+                    .unwrap_or_else(|_| panic!("root for {}", t.name));
 
-                assert!(
-                    (root - t.roots[i]).abs() < 1e-9,
-                    format!("{} root wanted={}, got={}", t.name, t.roots[i], root)
-                );
+                assert_eq!(nr.residual, (t.f)(nr.root));
+                assert_eq!(hm.residual, (t.f)(hm.root));
+                assert!(nr.residual.abs() < 1e-9);
+                assert!(hm.residual.abs() < 1e-9);
+
+                iter_newton += nr.iterations;
+                iter_halley += hm.iterations;
             }
         }
+        assert!(
+            iter_halley < iter_newton,
+            "halley={} newton={}",
+            iter_halley,
+            iter_newton
+        );
+
+        // iterations matches what the criteria saw
+        let in_f = |x: f64| x * x - 2.0;
+        let in_df = |x: f64| 2.0 * x;
+        let f = RealFnAndFirst::new(&in_f, &in_df);
+        let c = DeltaX::new(1e-12);
+        let counted = MinIterations::new(&c, 0);
+        let result = newton_raphson_verbose(&f, 1.0, &counted, 100).expect("root");
+        assert_eq!(result.iterations, counted.calls());
     }
 
     #[test]
-    fn test_table_illinois() {
-        for t in make_root_tests() {
-            for i in 0..t.roots.len() {
-                let f = RealFn::new(&*t.f);
-                let root = false_position_illinios(&f, &t.brackets[i], 100)
-                    .expect(&format!("root for {}", t.name));
+    fn test_newton_jumped_basin() {
+        // from 0.7 the first step overshoots to ~44.5 before creeping back to 1
+        let in_f = |x: f64| x.powi(20) - 1.0;
+        let in_df = |x: f64| 20.0 * x.powi(19);
+        let f = RealFnAndFirst::new(&in_f, &in_df);
+        let conv = DeltaX::new(1e-12);
 
-                assert!(
-                    (root - t.roots[i]).abs() < 1e-8,
-                    format!("{} root wanted={}, got={}", t.name, t.roots[i], root)
-                );
-            }
+        let result = newton_raphson_verbose(&f, 0.7, &conv, 200).expect("root");
+        assert_eq!(result.start, 0.7);
+        assert!((result.net_displacement - 0.3).abs() < 1e-9);
+        assert!(!result.jumped_basin(1.0));
+
+        // positive starts never leave the positive axis for x^20-1, so use sin
+        // from near its maximum, where the tangent is nearly flat
+        let in_f = |x: f64| x.sin();
+        let in_df = |x: f64| x.cos();
+        let f = RealFnAndFirst::new(&in_f, &in_df);
+
+        let result = newton_raphson_verbose(&f, 1.0, &conv, 100).expect("root");
+        assert!(!result.jumped_basin(0.0));
+
+        let result = newton_raphson_verbose(&f, 1.5, &conv, 100).expect("root");
+        assert!(result.jumped_basin(0.0));
+        assert!((result.root + 4.0 * f64::consts::PI).abs() < 1e-12);
+        assert!(result.net_displacement < -12.0);
+    }
+
+    #[test]
+    fn test_traces() {
+        let in_f = |x: f64| x * x - 2.0;
+        let in_df = |x: f64| 2.0 * x;
+        let f = RealFnAndFirst::new(&in_f, &in_df);
+        let expected = 2.0f64.sqrt();
+
+        // newton from the convex side approaches monotonically
+        let conv = DeltaX::new(1e-12);
+        let (root, trace) = newton_raphson_trace(&f, 3.0, &conv, 50).expect("root");
+        assert_eq!(Some(&root), trace.last());
+        assert!(trace.capacity() >= 50);
+        for w in trace.windows(2) {
+            assert!((w[1] - expected).abs() <= (w[0] - expected).abs());
         }
+
+        let bounds = Bounds::new(0.0, 3.0);
+        let (root, trace) = bisection_trace(&f, &bounds, 100).expect("root");
+        assert_eq!(
+            bisection(&f, &bounds, &DeltaX::new(1e-9), 100).expect("root"),
+            root
+        );
+        assert_eq!(bounds.middle(), trace[0]);
+        assert!((trace.last().unwrap() - root).abs() < 1e-9);
+
+        let (root, trace) = false_position_illinios_trace(&f, &bounds, 100).expect("root");
+        assert_eq!(
+            false_position_illinios(&f, &bounds, 100).expect("root"),
+            root
+        );
+        assert_eq!(Some(&root), trace.last());
+    }
+
+    #[test]
+    fn test_armijo_backtrack() {
+        // full newton step on atan overshoots badly from x=2
+        let in_f = |x: f64| x.atan();
+        let f = RealFn::new(&in_f);
+        let x = 2.0;
+        let f_x = in_f(x);
+        let direction = -f_x * (1.0 + x * x);
+        assert!(in_f(x + direction).abs() > f_x.abs());
+
+        let t = armijo_backtrack(&f, x, f_x, direction, 1e-4, 30);
+        assert!(t < 1.0);
+        assert!(in_f(x + t * direction).abs() <= (1.0 - 1e-4 * t) * f_x.abs());
+
+        // a good full step is accepted as is
+        let x = 0.1;
+        let f_x = in_f(x);
+        let direction = -f_x * (1.0 + x * x);
+        assert_eq!(1.0, armijo_backtrack(&f, x, f_x, direction, 1e-4, 30));
     }
 
-    /*
-     * Bisection corner cases
-     */
     #[test]
     #[should_panic]
-    fn test_bisection_no_straddle() {
-        let f = |x| x * x;
-        let _ = bisection(&RealFn::new(&f), &Bounds::new(-10.0, -5.0), 100);
+    fn test_armijo_backtrack_bad_c() {
+        let in_f = |x: f64| x;
+        let f = RealFn::new(&in_f);
+        let _ = armijo_backtrack(&f, 1.0, 1.0, -1.0, 1.5, 10);
     }
 
     #[test]
-    fn test_bisection_centered_root() {
-        let f = |x| x;
-        let root = bisection(&RealFn::new(&f), &Bounds::new(-1000000.0, 1000000.0), 100)
-            .expect("found root");
-        assert!(root.abs() < 1e-9, "wanted root x=0");
+    fn test_halley_adaptive() {
+        let in_f = |x: f64| x * x * x - 1.0;
+        let in_df = |x: f64| 3.0 * x * x;
+        let in_d2f = |x: f64| 6.0 * x;
+        let f = RealFnAndFirstSecond::new(&in_f, &in_df, &in_d2f);
+
+        let residuals = RefCell::new(vec![in_f(-1.5).abs()]);
+        let c1 = DeltaX::new(1e-12);
+        let c2 = ClosureCriteria::new(|x_pre: f64, x_cur: f64, f_cur: f64| {
+            residuals.borrow_mut().push(f_cur.abs());
+            c1.is_converged(x_pre, x_cur, f_cur)
+        });
+
+        // pure halley overshoots from the far side of the inflection point
+        let _ = halley_method(&f, -1.5, &c2, 10);
+        assert!(residuals.borrow().windows(2).any(|w| w[1] > w[0]));
+
+        residuals.replace(vec![in_f(-1.5).abs()]);
+        let root = halley_method_adaptive(&f, -1.5, &c2, 100).expect("root");
+        assert!((root - 1.0).abs() < 1e-12);
+        assert!(residuals.borrow().windows(2).all(|w| w[1] <= w[0]));
+
+        // derivative with the wrong sign, so no step reduces the residual
+        let in_f = |x: f64| x - 1.0;
+        let in_df = |_: f64| -1.0;
+        let in_d2f = |_: f64| 0.0;
+        let f = RealFnAndFirstSecond::new(&in_f, &in_df, &in_d2f);
+        match halley_method_adaptive(&f, 2.0, &c1, 100) {
+            Err(RootError::LineSearchFailed { x_cur }) => assert_eq!(x_cur, 2.0),
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
     }
 
-    /*
-     * Newton-Raphson corner cases.
-     */
     #[test]
-    #[should_panic]
-    fn test_newton_nonfinite_start() {
-        let in_f = |x| (x - 5.0) * (x - 4.0);
-        let in_df = |x| 2.0 * x - 9.0;
-        let f = RealFnAndFirst::new(&in_f, &in_df);
+    fn test_newton_adaptive_residual_scaling() {
+        // same root at x=ln(3), function scaled over twenty decades
+        let solve = |scale: f64| {
+            let in_f = move |x: f64| scale * (x.exp() - 3.0);
+            let in_df = move |x: f64| scale * x.exp();
+            let f = RealFnAndFirst::new(&in_f, &in_df);
+            let c1 = AdaptiveResidual::new(1e-12, 1);
+            let conv = MinIterations::new(&c1, 0);
+            let root = newton_raphson(&f, 0.0, &conv, 100).expect("root");
+            (root, conv.calls())
+        };
 
-        let conv = DeltaX::new(1e-9);
-        let _ = newton_raphson(&f, f64::NAN, &conv, 100);
+        let expected = 3.0f64.ln();
+        let (root, iterations) = solve(1.0);
+        assert!((root - expected).abs() < 1e-12);
+        for &scale in [1e10, 1e-10].iter() {
+            let (root_scaled, iterations_scaled) = solve(scale);
+            assert!((root_scaled - expected).abs() < 1e-12);
+            assert_eq!(iterations, iterations_scaled);
+        }
     }
 
     #[test]
-    fn test_newton_zero_derivative() {
-        let in_f = |_| 2.0;
-        let in_df = |_| 0.0;
+    fn test_polish_newton() {
+        let in_f = |x: f64| x * x - 2.0;
+        let in_df = |x: f64| 2.0 * x;
         let f = RealFnAndFirst::new(&in_f, &in_df);
+        let expected = 2.0f64.sqrt();
 
-        let conv = DeltaX::new(1e-9);
-        match newton_raphson(&f, 5.8, &conv, 100).expect_err("zero derivative not ok") {
-            RootError::ZeroDerivative { .. } => {
-                return;
-            }
+        // loose bisection result
+        let approx = match bisection(&f, &Bounds::new(0.0, 2.0), &DeltaX::new(1e-9), 21) {
+            Err(RootError::IterationLimit { last_x }) => last_x,
             _ => {
-                assert!(false, "incorrect error type");
+                panic!("incorrect error type");
+            }
+        };
+        assert!((approx - expected).abs() < 1e-6);
+        assert!((approx - expected).abs() > 1e-9);
+
+        let root = polish_newton(&f, approx, 2).expect("root");
+        assert!((root - expected).abs() <= 2.0 * f64::EPSILON);
+
+        // zero derivative leaves input unchanged
+        assert_eq!(0.0, polish_newton(&f, 0.0, 2).expect("root"));
+    }
+
+    #[test]
+    fn test_basin_map() {
+        // three roots
+        let in_f = |x: f64| x.exp() - 4.0 * x * x;
+        let in_df = |x: f64| x.exp() - 8.0 * x;
+        let in_d2f = |x: f64| x.exp() - 8.0;
+        let roots = [-0.40777670940448035, 0.7148059123627778, 4.3065847282207];
+
+        let f = RealFnAndFirst::new(&in_f, &in_df);
+        let conv = DeltaX::new(1e-12);
+        let starts = [-1.0, 0.0, 0.5, 2.0, 5.0];
+        let map = basin_map(&f, &starts, &conv, 100);
+
+        let expected = [roots[0], roots[0], roots[1], roots[1], roots[2]];
+        for (got, want) in map.iter().zip(expected.iter()) {
+            assert!((got.expect("root") - want).abs() < 1e-9);
+        }
+        assert_eq!(3, count_basins(&map, 1e-6));
+
+        // failed solves are reported as None
+        let map = basin_map(&f, &[5.0], &conv, 1);
+        assert_eq!(vec![None], map);
+        assert_eq!(0, count_basins(&map, 1e-6));
+
+        let f = RealFnAndFirstSecond::new(&in_f, &in_df, &in_d2f);
+        let map = halley_basin_map(&f, &[-1.0, 0.5, 5.0], &conv, 100);
+        assert_eq!(3, count_basins(&map, 1e-6));
+    }
+
+    #[test]
+    fn test_newton_min_iterations() {
+        // inner criterion would fire right after the first step
+        let evals = Cell::new(0);
+        let in_f = |x: f64| {
+            evals.set(evals.get() + 1);
+            x * x - 612.0
+        };
+        let in_df = |x: f64| 2.0 * x;
+        let f = RealFnAndFirst::new(&in_f, &in_df);
+
+        let inner = FnResidual::new(1e6);
+        let conv = MinIterations::new(&inner, 7);
+        let root = newton_raphson(&f, 10.0, &conv, 100).expect("root");
+
+        // one evaluation for the start plus one per iteration
+        assert_eq!(conv.calls(), 7);
+        assert_eq!(evals.get(), 8);
+        assert!((root - 24.7386337537).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_newton_significant_digits() {
+        // roots of x^2 - c at wildly different magnitudes
+        for c in [2e-16f64, 2.0, 2e16].iter() {
+            let expected = c.sqrt();
+            let in_f = |x: f64| x * x - c;
+            let in_df = |x: f64| 2.0 * x;
+            let f = RealFnAndFirst::new(&in_f, &in_df);
+
+            for digits in [6, 12].iter() {
+                let conv = SignificantDigits::new(*digits);
+                let root = newton_raphson(&f, 3.0 * expected, &conv, 100).expect("root");
+
+                let rel_err = (root - expected).abs() / expected;
+                assert!(
+                    rel_err < 10f64.powi(-(*digits as i32)),
+                    "digits={} root wanted={}, got={}",
+                    digits,
+                    expected,
+                    root
+                );
             }
         }
     }
 
+    #[test]
+    fn test_newton_runtime_criteria() {
+        // stand-in for criteria read from a config file
+        enum Config {
+            DeltaX(f64),
+            FnResidual(f64),
+        }
+        let config = [Config::DeltaX(1e-8), Config::FnResidual(1e-9)];
+
+        let criteria: Vec<Box<dyn IsConverged>> = config
+            .iter()
+            .map(|c| -> Box<dyn IsConverged> {
+                match *c {
+                    Config::DeltaX(eps) => Box::new(DeltaX::new(eps)),
+                    Config::FnResidual(eps) => Box::new(FnResidual::new(eps)),
+                }
+            })
+            .collect();
+        let conv = BoxedCriteria(Box::new(MultiCriteria::new(criteria)));
+
+        let in_f = |x: f64| x * x - 612.0;
+        let in_df = |x: f64| 2.0 * x;
+        let f = RealFnAndFirst::new(&in_f, &in_df);
+
+        let root = newton_raphson(&f, 10.0, &conv, 100).expect("root");
+        assert!((root - 24.7386337537).abs() < 1e-9);
+    }
+
     /*
      * Halley's Method corner cases.
      */
@@ -1047,6 +6258,42 @@ mod tests {
         assert!((root - 0.144765).abs() > 0.14);
     }
 
+    #[test]
+    fn test_pathology_microstep_verified() {
+        let in_f = |x: f64| 0.001 * (1.0 / x).exp() - 1.0;
+        let in_df = |x: f64| -0.001 * (1.0 / x).exp() / (x * x);
+        let f = RealFnAndFirst::new(&in_f, &in_df);
+
+        // false root is rejected
+        match solve_verified(&f, 0.00142, 1e-9, 1e-6, 100).expect_err("suspect") {
+            RootError::SuspectConvergence { x, residual } => {
+                assert!((x - 0.144765).abs() > 0.14);
+                assert!(residual.abs() > 1e-6);
+            }
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+
+        // good start still works
+        let root = solve_verified(&f, 0.1, 1e-9, 1e-6, 100).expect("root");
+        assert!((root - 0.144765).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_solve_verified_retry() {
+        // loose x_tol leaves residual above threshold on the first pass
+        let in_f = |x: f64| x * x - 612.0;
+        let in_df = |x: f64| 2.0 * x;
+        let f = RealFnAndFirst::new(&in_f, &in_df);
+
+        let root = newton_raphson(&f, 10.0, &DeltaX::new(1e-2), 100).expect("root");
+        assert!(in_f(root).abs() > 1e-12);
+
+        let root = solve_verified(&f, 10.0, 1e-2, 1e-12, 100).expect("root");
+        assert!(in_f(root).abs() <= 1e-12);
+    }
+
     #[test]
     fn test_pathology_flatlining() {
         // f(x)=1/e^(x^100) - 0.5, roots approx -0.996342 and 0.996342
@@ -1060,4 +6307,142 @@ mod tests {
         let conv = DeltaX::new(1e-9);
         let _ = newton_raphson(&f, 0.99999, &conv, 100).expect_err("no convergence");
     }
+
+    #[test]
+    fn test_newton_raphson_clamped() {
+        // atan overshoots further each step from beyond |x| ~ 1.39
+        let in_f = |x: f64| x.atan();
+        let in_df = |x: f64| 1.0 / (1.0 + x * x);
+        let f = RealFnAndFirst::new(&in_f, &in_df);
+        let conv = DeltaX::new(1e-9);
+
+        let _ = newton_raphson(&f, 2.0, &conv, 100).expect_err("diverges");
+        let root = newton_raphson_clamped(&f, 2.0, 1.0, &conv, 100).expect("root");
+        assert!(root.abs() < 1e-9);
+
+        // short steps are untouched
+        let unclamped = newton_raphson(&f, 0.5, &conv, 100).expect("root");
+        assert_eq!(
+            unclamped,
+            newton_raphson_clamped(&f, 0.5, 1.0, &conv, 100).expect("root")
+        );
+    }
+
+    #[test]
+    fn test_newton_raphson_damped() {
+        let t = make_root_tests_costabile06()
+            .into_iter()
+            .find(|t| t.name == "Costabile06 Example Twenty Two, Twenty Eight")
+            .expect("table entry");
+        let f = RealFnAndFirst::new(&*t.f, &*t.df);
+
+        let c1 = DeltaX::new(1e-10);
+        let c2 = FnResidual::new(1e-9);
+        let conv = DualCriteria::new(&c1, &c2);
+
+        // plain newton is flung out to ~5e17 and crawls back
+        let _ = newton_raphson(&f, 0.1, &conv, 100).expect_err("no convergence");
+
+        let root = newton_raphson_damped(&f, 0.1, &conv, 100, 100).expect("root");
+        assert!((root - 1.0).abs() < 1e-9);
+
+        // not enough halvings to pull the first step back in
+        match newton_raphson_damped(&f, 0.1, &conv, 100, 10) {
+            Err(RootError::LineSearchFailed { x_cur }) => assert_eq!(x_cur, 0.1),
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+    }
+
+    #[test]
+    fn test_pathology_flatlining_history() {
+        let in_f = |x: f64| x.powi(-100).exp() - 0.5;
+        let in_df = |x: f64| -100.0 * (-x.powi(100)).exp() * x.powi(99);
+        let f = RealFnAndFirst::new(&in_f, &in_df);
+
+        // f stalls at 0.5 while x is flung far from the root
+        let conv = DeltaX::new(1e-9);
+        match newton_raphson_with_history(&f, 0.99999, &conv, 100) {
+            Err(RootError::NotConverged { history, cause }) => {
+                assert!(history.len() >= 2);
+                let &(x_last, f_last) = history.last().unwrap();
+                assert!(x_last > 1e100);
+                assert!((f_last - 0.5).abs() < 1e-12);
+                assert!(history.iter().all(|&(_, fx)| (fx - 0.5).abs() < 1e-2));
+                match *cause {
+                    RootError::IteratedToNaN { .. } => {}
+                    _ => {
+                        panic!("incorrect error type");
+                    }
+                }
+            }
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+    }
+
+    #[test]
+    fn test_newton_raphson_with_history() {
+        let in_f = |x: f64| x * x - 2.0;
+        let in_df = |x: f64| 2.0 * x;
+        let f = RealFnAndFirst::new(&in_f, &in_df);
+        let conv = DeltaX::new(1e-9);
+
+        let (root, history) = newton_raphson_with_history(&f, 1.0, &conv, 100).expect("root");
+        let (trace_root, trace) = newton_raphson_trace(&f, 1.0, &conv, 100).expect("root");
+        assert_eq!(root, trace_root);
+        assert_eq!(history.len(), trace.len());
+        for (&(x, fx), &t) in history.iter().zip(trace.iter()) {
+            assert_eq!(x, t);
+            assert_eq!(fx, in_f(x));
+        }
+        assert_eq!(history.last().unwrap().0, root);
+
+        // iteration limit keeps the partial path
+        match newton_raphson_with_history(&f, 1.0, &conv, 3) {
+            Err(RootError::NotConverged { history, cause }) => {
+                assert_eq!(history.len(), 3);
+                assert_eq!(
+                    &trace[..3],
+                    &history.iter().map(|h| h.0).collect::<Vec<_>>()[..]
+                );
+                match *cause {
+                    RootError::IterationLimit { .. } => {}
+                    _ => {
+                        panic!("incorrect error type");
+                    }
+                }
+            }
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+    }
+
+    #[test]
+    fn test_pathology_stagnation() {
+        // residual tolerance finer than f64 can resolve near sqrt(2)
+        let in_f = |x: f64| x * x - 2.0;
+        let in_df = |x: f64| 2.0 * x;
+        let f = RealFnAndFirst::new(&in_f, &in_df);
+
+        let conv = FnResidual::new(1e-30);
+        let err = newton_raphson(&f, 1.0, &conv, 100).expect_err("no convergence");
+        match err {
+            RootError::IterationLimit { .. } => {}
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+
+        // stagnation bails out early, and says so
+        let stuck = Stagnation::new(4, 1e-12);
+        let finish = EitherCriteria::new(&conv, &stuck);
+        let r = newton_raphson_verbose(&f, 1.0, &finish, 100).expect("stagnation stop");
+        assert!(stuck.stagnated());
+        assert!(r.iterations < 15);
+        assert!((r.root - 2f64.sqrt()).abs() < 1e-15);
+    }
 }
@@ -0,0 +1,178 @@
+//! Root finding for systems of N equations in N unknowns.
+//!
+//! This generalizes `system2` to F: R<sup>N</sup> ⟶ R<sup>N</sup> with a dense
+//! Jacobian.  Each Newton step solves the linear system by Gaussian elimination
+//! with partial pivoting, costing O(N<sup>3</sup>).  It is intended for small
+//! systems; large or sparse problems call for a dedicated linear algebra crate.
+//!
+//! # Examples
+//! ```
+//! use rootfind::solver::system::newton_nd;
+//!
+//! // intersection of the unit circle with the line y = x
+//! let f = |x: &[f64]| vec![x[0] * x[0] + x[1] * x[1] - 1.0, x[1] - x[0]];
+//! let jac = |x: &[f64]| vec![vec![2.0 * x[0], 2.0 * x[1]], vec![-1.0, 1.0]];
+//!
+//! let root = newton_nd(f, jac, &[1.0, 0.5], 1e-12, 50).expect("root");
+//! assert!((root[0] - 0.5f64.sqrt()).abs() < 1e-9);
+//! assert!((root[1] - 0.5f64.sqrt()).abs() < 1e-9);
+//! ```
+use alloc::vec::Vec;
+use core::f64;
+
+use super::RootError;
+
+/// Newton's method for a system of N equations.
+///
+/// The `jac` returns the Jacobian as rows, i.e. `jac(x)[i][j]` is
+/// ∂f<sub>i</sub>/∂x<sub>j</sub>.  Both `f` and `jac` must return results
+/// matching the dimension of `start`.
+///
+/// Iteration stops once the residual's infinity-norm, max<sub>i</sub>
+/// |f<sub>i</sub>(x)|, drops below `tol`.  A Jacobian which is singular to
+/// working precision gives `RootError::SingularJacobian`.
+pub fn newton_nd<F, J>(
+    f: F,
+    jac: J,
+    start: &[f64],
+    tol: f64,
+    max_iter: usize,
+) -> Result<Vec<f64>, RootError>
+where
+    F: Fn(&[f64]) -> Vec<f64>,
+    J: Fn(&[f64]) -> Vec<Vec<f64>>,
+{
+    assert!(start.iter().all(|x| x.is_finite()));
+    assert!(tol > 0.0);
+
+    let n = start.len();
+    let mut x = start.to_vec();
+    let mut fx = f(&x);
+    assert_eq!(fx.len(), n);
+
+    for _ in 0..max_iter {
+        let j = jac(&x);
+        assert_eq!(j.len(), n);
+        assert!(j.iter().all(|row| row.len() == n));
+
+        let dx = match solve_dense(j, fx) {
+            Some(dx) => dx,
+            None => return Err(RootError::SingularJacobian { x_cur: x }),
+        };
+        for (x_i, dx_i) in x.iter_mut().zip(dx.iter()) {
+            *x_i -= dx_i;
+            if !x_i.is_finite() {
+                return Err(RootError::IteratedToNaN { x_new: *x_i });
+            }
+        }
+
+        fx = f(&x);
+        assert_eq!(fx.len(), n);
+        if fx.iter().fold(0.0f64, |m, v| m.max(v.abs())) < tol {
+            return Ok(x);
+        }
+    }
+    Err(RootError::SystemIterationLimit { last_x: x })
+}
+
+/// Solve the dense system a * x = b by Gaussian elimination with partial
+/// pivoting.
+///
+/// Returns None when a pivot is non-finite or negligible relative to the
+/// largest entry of `a`.
+fn solve_dense(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Option<Vec<f64>> {
+    let n = b.len();
+    let scale = a
+        .iter()
+        .flat_map(|row| row.iter())
+        .fold(0.0f64, |m, v| m.max(v.abs()));
+    let tiny = f64::EPSILON * scale;
+
+    for k in 0..n {
+        // partial pivot on largest magnitude in column k
+        let p = (k..n).max_by(|&i, &j| a[i][k].abs().total_cmp(&a[j][k].abs()))?;
+        let pivot = a[p][k];
+        if !pivot.is_finite() || pivot.abs() <= tiny {
+            return None;
+        }
+        a.swap(k, p);
+        b.swap(k, p);
+
+        let (upper, lower) = a.split_at_mut(k + 1);
+        let pivot_row = &upper[k];
+        let b_k = b[k];
+        for (row, b_i) in lower.iter_mut().zip(b.iter_mut().skip(k + 1)) {
+            let m = row[k] / pivot;
+            for (r, p) in row[k..].iter_mut().zip(pivot_row[k..].iter()) {
+                *r -= m * p;
+            }
+            *b_i -= m * b_k;
+        }
+    }
+
+    // back substitution
+    let mut x = vec![0.0; n];
+    for k in (0..n).rev() {
+        let sum: f64 = ((k + 1)..n).map(|j| a[k][j] * x[j]).sum();
+        x[k] = (b[k] - sum) / a[k][k];
+    }
+    Some(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Elementary symmetric polynomials of (1, 2, 3).
+    fn vieta(x: &[f64]) -> Vec<f64> {
+        vec![
+            x[0] + x[1] + x[2] - 6.0,
+            x[0] * x[1] + x[1] * x[2] + x[2] * x[0] - 11.0,
+            x[0] * x[1] * x[2] - 6.0,
+        ]
+    }
+
+    fn vieta_jac(x: &[f64]) -> Vec<Vec<f64>> {
+        vec![
+            vec![1.0, 1.0, 1.0],
+            vec![x[1] + x[2], x[0] + x[2], x[0] + x[1]],
+            vec![x[1] * x[2], x[0] * x[2], x[0] * x[1]],
+        ]
+    }
+
+    #[test]
+    fn test_newton_nd_polynomial_system() {
+        let root = newton_nd(vieta, vieta_jac, &[0.5, 2.2, 3.5], 1e-12, 50).expect("root");
+        for (got, want) in root.iter().zip([1.0, 2.0, 3.0].iter()) {
+            assert!((got - want).abs() < 1e-10, "got={:?}", root);
+        }
+
+        // a different start picks a different ordering of the same solution
+        let root = newton_nd(vieta, vieta_jac, &[3.2, 0.9, 1.8], 1e-12, 50).expect("root");
+        for (got, want) in root.iter().zip([3.0, 1.0, 2.0].iter()) {
+            assert!((got - want).abs() < 1e-10, "got={:?}", root);
+        }
+    }
+
+    #[test]
+    fn test_newton_nd_singular_jacobian() {
+        // jacobian has repeated columns whenever two unknowns coincide
+        match newton_nd(vieta, vieta_jac, &[1.0, 1.0, 1.0], 1e-12, 50) {
+            Err(RootError::SingularJacobian { x_cur }) => {
+                assert_eq!(x_cur, vec![1.0, 1.0, 1.0]);
+            }
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+    }
+
+    #[test]
+    fn test_solve_dense_pivoting() {
+        // zero leading entry requires a row swap
+        let a = vec![vec![0.0, 2.0], vec![3.0, 1.0]];
+        let x = solve_dense(a, vec![4.0, 5.0]).expect("solution");
+        assert!((x[0] - 1.0).abs() < 1e-15);
+        assert!((x[1] - 2.0).abs() < 1e-15);
+    }
+}
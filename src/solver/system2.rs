@@ -0,0 +1,122 @@
+//! Root finding for systems of two equations in two unknowns.
+//!
+//! Many practical problems, like finding the intersection of two curves, are
+//! small nonlinear systems F(x) = 0 with F: R<sup>2</sup> ⟶ R<sup>2</sup>.  The
+//! 2x2 linear system of each Newton step is solved directly by Cramer's rule.
+//! Larger systems are handled by `system::newton_nd`.
+//!
+//! # Examples
+//! ```
+//! use rootfind::solver::system2::newton2;
+//!
+//! // intersection of the unit circle with the line y = x
+//! let f = |x: [f64; 2]| [x[0] * x[0] + x[1] * x[1] - 1.0, x[1] - x[0]];
+//! let jac = |x: [f64; 2]| [[2.0 * x[0], 2.0 * x[1]], [-1.0, 1.0]];
+//!
+//! let root = newton2(f, jac, [1.0, 0.5], 1e-12, 50).expect("root");
+//! assert!((root[0] - 0.5f64.sqrt()).abs() < 1e-9);
+//! assert!((root[1] - 0.5f64.sqrt()).abs() < 1e-9);
+//! ```
+use super::RootError;
+
+/// Newton's method for a system of two equations.
+///
+/// The `jac` returns the Jacobian with rows holding the gradients of each
+/// component, i.e. `jac(x)[i][j]` is ∂f<sub>i</sub>/∂x<sub>j</sub>.
+///
+/// Iteration stops once the residual's infinity-norm, max(|f<sub>0</sub>|,
+/// |f<sub>1</sub>|), drops below `tol`.  A singular Jacobian gives
+/// `RootError::ZeroDeterminant`.
+pub fn newton2<F, J>(
+    f: F,
+    jac: J,
+    start: [f64; 2],
+    tol: f64,
+    max_iter: usize,
+) -> Result<[f64; 2], RootError>
+where
+    F: Fn([f64; 2]) -> [f64; 2],
+    J: Fn([f64; 2]) -> [[f64; 2]; 2],
+{
+    assert!(start[0].is_finite() && start[1].is_finite());
+    assert!(tol > 0.0);
+
+    let mut x = start;
+    let mut fx = f(x);
+
+    for _ in 0..max_iter {
+        let [[a, b], [c, d]] = jac(x);
+        let det = a * d - b * c;
+        if det == 0.0 || !det.is_finite() {
+            return Err(RootError::ZeroDeterminant { x_cur: x });
+        }
+
+        // Cramer's rule for J * dx = f(x)
+        let dx = [(d * fx[0] - b * fx[1]) / det, (a * fx[1] - c * fx[0]) / det];
+        x = [x[0] - dx[0], x[1] - dx[1]];
+        for &x_new in x.iter() {
+            if !x_new.is_finite() {
+                return Err(RootError::IteratedToNaN { x_new });
+            }
+        }
+
+        fx = f(x);
+        if fx[0].abs().max(fx[1].abs()) < tol {
+            return Ok(x);
+        }
+    }
+    Err(RootError::SystemIterationLimit { last_x: x.to_vec() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_newton2_circle_line() {
+        // x^2 + y^2 = 4 intersected with y = x + 1
+        let f = |x: [f64; 2]| [x[0] * x[0] + x[1] * x[1] - 4.0, x[1] - x[0] - 1.0];
+        let jac = |x: [f64; 2]| [[2.0 * x[0], 2.0 * x[1]], [-1.0, 1.0]];
+
+        let expected = (7.0f64.sqrt() - 1.0) / 2.0;
+        let root = newton2(f, jac, [1.0, 1.0], 1e-12, 50).expect("root");
+        assert!((root[0] - expected).abs() < 1e-12);
+        assert!((root[1] - (expected + 1.0)).abs() < 1e-12);
+
+        // other intersection
+        let expected = (-7.0f64.sqrt() - 1.0) / 2.0;
+        let root = newton2(f, jac, [-2.0, -1.0], 1e-12, 50).expect("root");
+        assert!((root[0] - expected).abs() < 1e-12);
+        assert!((root[1] - (expected + 1.0)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_newton2_zero_determinant() {
+        let f = |x: [f64; 2]| [x[0] * x[0] + x[1] * x[1] - 4.0, x[1] - x[0] - 1.0];
+        let jac = |x: [f64; 2]| [[2.0 * x[0], 2.0 * x[1]], [-1.0, 1.0]];
+
+        match newton2(f, jac, [0.0, 0.0], 1e-12, 50) {
+            Err(RootError::ZeroDeterminant { x_cur }) => {
+                assert_eq!(x_cur, [0.0, 0.0]);
+            }
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+    }
+
+    #[test]
+    fn test_newton2_iteration_limit() {
+        let f = |x: [f64; 2]| [x[0] * x[0] + x[1] * x[1] - 4.0, x[1] - x[0] - 1.0];
+        let jac = |x: [f64; 2]| [[2.0 * x[0], 2.0 * x[1]], [-1.0, 1.0]];
+
+        match newton2(f, jac, [10.0, 10.0], 1e-12, 2) {
+            Err(RootError::SystemIterationLimit { last_x }) => {
+                assert_eq!(last_x.len(), 2);
+            }
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+    }
+}
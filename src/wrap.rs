@@ -5,8 +5,10 @@
 //! * RealFnEval
 //! * RealDfEval
 //! * RealD2fEval
+//! * RealD3fEval
 //!
-//! indicating that a functor computes f(x), df(x), and d2f(x) respectively.
+//! indicating that a functor computes f(x), df(x), d2f(x), and d3f(x)
+//! respectively.
 //! The rootfind library uses these type bounds to help implement generic
 //! driver routines.
 //!
@@ -16,6 +18,13 @@
 //! * RealFn - f(x)
 //! * RealFnAndFirst - f(x) and df(x)
 //! * RealFnAndFirstSecond - f(x), df(x), and d2f(x)
+//! * RealFnThroughThird - f(x), df(x), d2f(x), and d3f(x)
+//! * RealFnAndFirstNumericalSecond - f(x) and df(x), with d2f(x) approximated
+//!   from df(x)
+//!
+//! RealFn, RealFnAndFirst, and RealFnAndFirstSecond work for any
+//! `num_traits::Float` scalar, so closures over f32 can be passed to the
+//! generic solvers like `newton_raphson`, `halley_method`, and `bisection`.
 //!
 //! Just invoke the appropriate generic struct and you're ready to go:
 //!
@@ -44,45 +53,113 @@
 //!
 //! // f can now be used in bisection, Newton-Raphson, or Halley's method
 //! ```
+//!
+//! When no analytic derivative is available, RealFnNumericalDiff approximates
+//! df(x) and d2f(x) by finite differences.  `RealFnAndFirstSecond::numeric_from`
+//! is a shortcut for running Halley's method on a bare closure.  If f can be evaluated over complex inputs,
+//! ComplexStepDf instead yields df(x) to machine precision.
+//!
+//! ComplexFn wraps a `Fn(Complex<f64>) -> Complex<f64>` to implement
+//! ComplexFnEval, for solvers such as Muller's method that search the complex
+//! plane.  ComplexFnAndFirst adds the complex derivative for complex
+//! Newton-Raphson.
+//!
+//! KahanFn evaluates functions given as a list of terms using compensated
+//! summation, preserving residual accuracy where large terms cancel.
+//!
+//! CountingFn wraps an existing RealFnEval and tallies evaluations of f(x),
+//! df(x) and d2f(x), which is handy when comparing the cost of different methods.
+//!
+//! CachingFn wraps an existing RealFnEval and remembers recent evaluations,
+//! so asking again for f at exactly the same x is free.
+//!
+//! TrapNaNFn wraps an existing function and pinpoints where it first returned
+//! NaN or infinity.
+//!
+//! Polynomials have their own dedicated type, `Polynomial`.
+pub use self::polynomial::Polynomial;
+
+use alloc::vec::Vec;
+use core::cell::Cell;
+#[cfg(feature = "std")]
+use core::cell::RefCell;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+use num_complex::Complex;
+use num_traits::Float;
+
+use solver::RootError;
+
+pub mod polynomial;
 
 /// Trait evaluating f(x) with f: R<sup>1</sup> ⟶  R<sup>1</sup>.
-pub trait RealFnEval {
-    fn eval_f(&self, x: f64) -> f64;
+///
+/// The scalar type defaults to f64.  The wrappers RealFn, RealFnAndFirst, and
+/// RealFnAndFirstSecond also implement it for any `num_traits::Float`, e.g. f32.
+pub trait RealFnEval<T = f64> {
+    fn eval_f(&self, x: T) -> T;
+
+    /// First x where a non-finite evaluation was trapped, if any.
+    ///
+    /// Only `TrapNaNFn` traps, and wrappers around it pass this through.  The
+    /// iterative solvers check it after every evaluation.
+    fn trapped(&self) -> Option<f64> {
+        None
+    }
 }
 
 /// Trait evaluating the derivative df(x) with df: R<sup>1</sup> ⟶  R<sup>1</sup>.
-pub trait RealDfEval {
-    fn eval_df(&self, x: f64) -> f64;
+pub trait RealDfEval<T = f64> {
+    fn eval_df(&self, x: T) -> T;
 }
 
 /// Trait evaluating the second derivative d2f(x) with
 /// d2f: R<sup>1</sup> ⟶R<sup>1</sup>.
-pub trait RealD2fEval {
-    fn eval_d2f(&self, x: f64) -> f64;
+pub trait RealD2fEval<T = f64> {
+    fn eval_d2f(&self, x: T) -> T;
+}
+
+/// Trait evaluating f(z) with f: C<sup>1</sup> ⟶  C<sup>1</sup>.
+pub trait ComplexFnEval {
+    fn eval_complex(&self, z: Complex<f64>) -> Complex<f64>;
+}
+
+/// Trait evaluating the complex derivative df(z) with
+/// df: C<sup>1</sup> ⟶  C<sup>1</sup>.
+pub trait ComplexDfEval {
+    fn eval_complex_df(&self, z: Complex<f64>) -> Complex<f64>;
+}
+
+/// Trait evaluating the third derivative d3f(x) with
+/// d3f: R<sup>1</sup> ⟶R<sup>1</sup>.
+pub trait RealD3fEval<T = f64> {
+    fn eval_d3f(&self, x: T) -> T;
 }
 
 /// Wraps function to implement RealFnEval.
 pub struct RealFn<'a, F>
 where
-    F: 'a + ?Sized + Fn(f64) -> f64,
+    F: 'a + ?Sized,
 {
     pub f: &'a F,
 }
 
 impl<'a, F> RealFn<'a, F>
 where
-    F: 'a + ?Sized + Fn(f64) -> f64,
+    F: 'a + ?Sized,
 {
     pub fn new(f: &'a F) -> RealFn<'a, F> {
         RealFn { f }
     }
 }
 
-impl<'a, F> RealFnEval for RealFn<'a, F>
+impl<'a, T, F> RealFnEval<T> for RealFn<'a, F>
 where
-    F: 'a + ?Sized + Fn(f64) -> f64,
+    T: Float,
+    F: 'a + ?Sized + Fn(T) -> T,
 {
-    fn eval_f(&self, x: f64) -> f64 {
+    fn eval_f(&self, x: T) -> T {
         (self.f)(x)
     }
 }
@@ -90,8 +167,8 @@ where
 /// Wraps functions to implement RealFnEval and RealDfEval.
 pub struct RealFnAndFirst<'a, F1, F2>
 where
-    F1: 'a + ?Sized + Fn(f64) -> f64,
-    F2: 'a + ?Sized + Fn(f64) -> f64,
+    F1: 'a + ?Sized,
+    F2: 'a + ?Sized,
 {
     pub f: &'a F1,
     pub df: &'a F2,
@@ -99,30 +176,32 @@ where
 
 impl<'a, F1, F2> RealFnAndFirst<'a, F1, F2>
 where
-    F1: 'a + ?Sized + Fn(f64) -> f64,
-    F2: 'a + ?Sized + Fn(f64) -> f64,
+    F1: 'a + ?Sized,
+    F2: 'a + ?Sized,
 {
     pub fn new(f: &'a F1, df: &'a F2) -> RealFnAndFirst<'a, F1, F2> {
         RealFnAndFirst { f, df }
     }
 }
 
-impl<'a, F1, F2> RealFnEval for RealFnAndFirst<'a, F1, F2>
+impl<'a, T, F1, F2> RealFnEval<T> for RealFnAndFirst<'a, F1, F2>
 where
-    F1: 'a + ?Sized + Fn(f64) -> f64,
-    F2: 'a + ?Sized + Fn(f64) -> f64,
+    T: Float,
+    F1: 'a + ?Sized + Fn(T) -> T,
+    F2: 'a + ?Sized,
 {
-    fn eval_f(&self, x: f64) -> f64 {
+    fn eval_f(&self, x: T) -> T {
         (self.f)(x)
     }
 }
 
-impl<'a, F1, F2> RealDfEval for RealFnAndFirst<'a, F1, F2>
+impl<'a, T, F1, F2> RealDfEval<T> for RealFnAndFirst<'a, F1, F2>
 where
-    F1: 'a + ?Sized + Fn(f64) -> f64,
-    F2: 'a + ?Sized + Fn(f64) -> f64,
+    T: Float,
+    F1: 'a + ?Sized,
+    F2: 'a + ?Sized + Fn(T) -> T,
 {
-    fn eval_df(&self, x: f64) -> f64 {
+    fn eval_df(&self, x: T) -> T {
         (self.df)(x)
     }
 }
@@ -130,9 +209,9 @@ where
 /// Wraps functions to implement RealFnEval, RealDfEval, and RealD2fEval.
 pub struct RealFnAndFirstSecond<'a, F1, F2, F3>
 where
-    F1: 'a + ?Sized + Fn(f64) -> f64,
-    F2: 'a + ?Sized + Fn(f64) -> f64,
-    F3: 'a + ?Sized + Fn(f64) -> f64,
+    F1: 'a + ?Sized,
+    F2: 'a + ?Sized,
+    F3: 'a + ?Sized,
 {
     pub f: &'a F1,
     pub df: &'a F2,
@@ -141,44 +220,994 @@ where
 
 impl<'a, F1, F2, F3> RealFnAndFirstSecond<'a, F1, F2, F3>
 where
-    F1: 'a + ?Sized + Fn(f64) -> f64,
-    F2: 'a + ?Sized + Fn(f64) -> f64,
-    F3: 'a + ?Sized + Fn(f64) -> f64,
+    F1: 'a + ?Sized,
+    F2: 'a + ?Sized,
+    F3: 'a + ?Sized,
 {
     pub fn new(f: &'a F1, df: &'a F2, d2f: &'a F3) -> RealFnAndFirstSecond<'a, F1, F2, F3> {
         RealFnAndFirstSecond { f, df, d2f }
     }
 }
 
-impl<'a, F1, F2, F3> RealFnEval for RealFnAndFirstSecond<'a, F1, F2, F3>
+impl<'a, T, F1, F2, F3> RealFnEval<T> for RealFnAndFirstSecond<'a, F1, F2, F3>
+where
+    T: Float,
+    F1: 'a + ?Sized + Fn(T) -> T,
+    F2: 'a + ?Sized,
+    F3: 'a + ?Sized,
+{
+    fn eval_f(&self, x: T) -> T {
+        (self.f)(x)
+    }
+}
+
+impl<'a, T, F1, F2, F3> RealDfEval<T> for RealFnAndFirstSecond<'a, F1, F2, F3>
+where
+    T: Float,
+    F1: 'a + ?Sized,
+    F2: 'a + ?Sized + Fn(T) -> T,
+    F3: 'a + ?Sized,
+{
+    fn eval_df(&self, x: T) -> T {
+        (self.df)(x)
+    }
+}
+
+impl<'a, T, F1, F2, F3> RealD2fEval<T> for RealFnAndFirstSecond<'a, F1, F2, F3>
+where
+    T: Float,
+    F1: 'a + ?Sized,
+    F2: 'a + ?Sized,
+    F3: 'a + ?Sized + Fn(T) -> T,
+{
+    fn eval_d2f(&self, x: T) -> T {
+        (self.d2f)(x)
+    }
+}
+
+/// Wraps function to implement ComplexFnEval.
+pub struct ComplexFn<'a, F>
+where
+    F: 'a + ?Sized + Fn(Complex<f64>) -> Complex<f64>,
+{
+    pub f: &'a F,
+}
+
+impl<'a, F> ComplexFn<'a, F>
+where
+    F: 'a + ?Sized + Fn(Complex<f64>) -> Complex<f64>,
+{
+    pub fn new(f: &'a F) -> ComplexFn<'a, F> {
+        ComplexFn { f }
+    }
+}
+
+impl<'a, F> ComplexFnEval for ComplexFn<'a, F>
+where
+    F: 'a + ?Sized + Fn(Complex<f64>) -> Complex<f64>,
+{
+    fn eval_complex(&self, z: Complex<f64>) -> Complex<f64> {
+        (self.f)(z)
+    }
+}
+
+/// Wraps functions to implement ComplexFnEval and ComplexDfEval.
+pub struct ComplexFnAndFirst<'a, F1, F2>
+where
+    F1: 'a + ?Sized + Fn(Complex<f64>) -> Complex<f64>,
+    F2: 'a + ?Sized + Fn(Complex<f64>) -> Complex<f64>,
+{
+    pub f: &'a F1,
+    pub df: &'a F2,
+}
+
+impl<'a, F1, F2> ComplexFnAndFirst<'a, F1, F2>
+where
+    F1: 'a + ?Sized + Fn(Complex<f64>) -> Complex<f64>,
+    F2: 'a + ?Sized + Fn(Complex<f64>) -> Complex<f64>,
+{
+    pub fn new(f: &'a F1, df: &'a F2) -> ComplexFnAndFirst<'a, F1, F2> {
+        ComplexFnAndFirst { f, df }
+    }
+}
+
+impl<'a, F1, F2> ComplexFnEval for ComplexFnAndFirst<'a, F1, F2>
+where
+    F1: 'a + ?Sized + Fn(Complex<f64>) -> Complex<f64>,
+    F2: 'a + ?Sized + Fn(Complex<f64>) -> Complex<f64>,
+{
+    fn eval_complex(&self, z: Complex<f64>) -> Complex<f64> {
+        (self.f)(z)
+    }
+}
+
+impl<'a, F1, F2> ComplexDfEval for ComplexFnAndFirst<'a, F1, F2>
+where
+    F1: 'a + ?Sized + Fn(Complex<f64>) -> Complex<f64>,
+    F2: 'a + ?Sized + Fn(Complex<f64>) -> Complex<f64>,
+{
+    fn eval_complex_df(&self, z: Complex<f64>) -> Complex<f64> {
+        (self.df)(z)
+    }
+}
+
+/// Wraps functions to implement RealFnEval through RealD3fEval.
+pub struct RealFnThroughThird<'a, F1, F2, F3, F4>
+where
+    F1: 'a + ?Sized,
+    F2: 'a + ?Sized,
+    F3: 'a + ?Sized,
+    F4: 'a + ?Sized,
+{
+    pub f: &'a F1,
+    pub df: &'a F2,
+    pub d2f: &'a F3,
+    pub d3f: &'a F4,
+}
+
+impl<'a, F1, F2, F3, F4> RealFnThroughThird<'a, F1, F2, F3, F4>
+where
+    F1: 'a + ?Sized,
+    F2: 'a + ?Sized,
+    F3: 'a + ?Sized,
+    F4: 'a + ?Sized,
+{
+    pub fn new(
+        f: &'a F1,
+        df: &'a F2,
+        d2f: &'a F3,
+        d3f: &'a F4,
+    ) -> RealFnThroughThird<'a, F1, F2, F3, F4> {
+        RealFnThroughThird { f, df, d2f, d3f }
+    }
+}
+
+impl<'a, T, F1, F2, F3, F4> RealFnEval<T> for RealFnThroughThird<'a, F1, F2, F3, F4>
+where
+    T: Float,
+    F1: 'a + ?Sized + Fn(T) -> T,
+    F2: 'a + ?Sized,
+    F3: 'a + ?Sized,
+    F4: 'a + ?Sized,
+{
+    fn eval_f(&self, x: T) -> T {
+        (self.f)(x)
+    }
+}
+
+impl<'a, T, F1, F2, F3, F4> RealDfEval<T> for RealFnThroughThird<'a, F1, F2, F3, F4>
+where
+    T: Float,
+    F1: 'a + ?Sized,
+    F2: 'a + ?Sized + Fn(T) -> T,
+    F3: 'a + ?Sized,
+    F4: 'a + ?Sized,
+{
+    fn eval_df(&self, x: T) -> T {
+        (self.df)(x)
+    }
+}
+
+impl<'a, T, F1, F2, F3, F4> RealD2fEval<T> for RealFnThroughThird<'a, F1, F2, F3, F4>
+where
+    T: Float,
+    F1: 'a + ?Sized,
+    F2: 'a + ?Sized,
+    F3: 'a + ?Sized + Fn(T) -> T,
+    F4: 'a + ?Sized,
+{
+    fn eval_d2f(&self, x: T) -> T {
+        (self.d2f)(x)
+    }
+}
+
+impl<'a, T, F1, F2, F3, F4> RealD3fEval<T> for RealFnThroughThird<'a, F1, F2, F3, F4>
+where
+    T: Float,
+    F1: 'a + ?Sized,
+    F2: 'a + ?Sized,
+    F3: 'a + ?Sized,
+    F4: 'a + ?Sized + Fn(T) -> T,
+{
+    fn eval_d3f(&self, x: T) -> T {
+        (self.d3f)(x)
+    }
+}
+
+impl<'a, F> RealFnAndFirstSecond<'a, F, F, F>
+where
+    F: 'a + ?Sized + Fn(f64) -> f64,
+{
+    /// Wrap a bare f(x), approximating both derivatives by finite differences.
+    ///
+    /// This lets Halley's method run without deriving anything, but analytic
+    /// derivatives are preferred whenever available.  The numerical df is
+    /// accurate to roughly 1e-10 relative and d2f to roughly 1e-8, so the final
+    /// digits of the root are less trustworthy and tight convergence criteria
+    /// may never be met.  Each Halley iteration also costs five evaluations of f
+    /// rather than one.
+    ///
+    /// Returns a RealFnNumericalDiff, which supplies f, df, and d2f.
+    pub fn numeric_from(f: &'a F) -> RealFnNumericalDiff<'a, F> {
+        RealFnNumericalDiff::new(f)
+    }
+}
+
+/// Wraps f and df to implement RealFnEval, RealDfEval, and a numerical
+/// RealD2fEval.
+///
+/// This runs Halley's method with only first derivative knowledge.  The second
+/// derivative is approximated by the central difference of df
+///
+/// d2f(x) ≈ (df(x+h) - df(x-h)) / 2h
+///
+/// with a default step of cbrt(ε) * max(|x|, 1), balancing truncation and
+/// cancellation error.  Use `with_step(h)` to override it.  Each Halley
+/// iteration then costs two extra evaluations of df.  Since Halley's step only
+/// uses d2f as a correction, modest errors in it barely slow convergence.
+pub struct RealFnAndFirstNumericalSecond<'a, F1, F2>
+where
+    F1: 'a + ?Sized + Fn(f64) -> f64,
+    F2: 'a + ?Sized + Fn(f64) -> f64,
+{
+    pub f: &'a F1,
+    pub df: &'a F2,
+    step: Option<f64>,
+}
+
+impl<'a, F1, F2> RealFnAndFirstNumericalSecond<'a, F1, F2>
+where
+    F1: 'a + ?Sized + Fn(f64) -> f64,
+    F2: 'a + ?Sized + Fn(f64) -> f64,
+{
+    pub fn new(f: &'a F1, df: &'a F2) -> RealFnAndFirstNumericalSecond<'a, F1, F2> {
+        RealFnAndFirstNumericalSecond { f, df, step: None }
+    }
+
+    /// Use a fixed step `h` instead of one scaled to |x|.
+    pub fn with_step(self, h: f64) -> RealFnAndFirstNumericalSecond<'a, F1, F2> {
+        assert!(h > 0.0);
+        assert!(h.is_finite());
+        RealFnAndFirstNumericalSecond {
+            step: Some(h),
+            ..self
+        }
+    }
+}
+
+impl<'a, F1, F2> RealFnEval for RealFnAndFirstNumericalSecond<'a, F1, F2>
 where
     F1: 'a + ?Sized + Fn(f64) -> f64,
     F2: 'a + ?Sized + Fn(f64) -> f64,
-    F3: 'a + ?Sized + Fn(f64) -> f64,
 {
     fn eval_f(&self, x: f64) -> f64 {
         (self.f)(x)
     }
 }
 
-impl<'a, F1, F2, F3> RealDfEval for RealFnAndFirstSecond<'a, F1, F2, F3>
+impl<'a, F1, F2> RealDfEval for RealFnAndFirstNumericalSecond<'a, F1, F2>
 where
     F1: 'a + ?Sized + Fn(f64) -> f64,
     F2: 'a + ?Sized + Fn(f64) -> f64,
-    F3: 'a + ?Sized + Fn(f64) -> f64,
 {
     fn eval_df(&self, x: f64) -> f64 {
         (self.df)(x)
     }
 }
 
-impl<'a, F1, F2, F3> RealD2fEval for RealFnAndFirstSecond<'a, F1, F2, F3>
+impl<'a, F1, F2> RealD2fEval for RealFnAndFirstNumericalSecond<'a, F1, F2>
 where
     F1: 'a + ?Sized + Fn(f64) -> f64,
     F2: 'a + ?Sized + Fn(f64) -> f64,
-    F3: 'a + ?Sized + Fn(f64) -> f64,
 {
     fn eval_d2f(&self, x: f64) -> f64 {
-        (self.d2f)(x)
+        let h = self
+            .step
+            .unwrap_or_else(|| f64::EPSILON.cbrt() * x.abs().max(1.0));
+        ((self.df)(x + h) - (self.df)(x - h)) / (2.0 * h)
+    }
+}
+
+/// Wraps function to implement RealFnEval and a numerical RealDfEval and
+/// RealD2fEval.
+///
+/// The derivative is approximated by the central difference
+///
+/// df(x) ≈ (f(x+h) - f(x-h)) / 2h
+///
+/// which has O(h^2) truncation error but suffers cancellation as h shrinks:
+/// f(x+h) and f(x-h) agree in most of their digits, so the difference keeps
+/// only a few significant ones.  The default step of 1e-6 * max(|x|, 1)
+/// balances the two, giving roughly 8-10 correct digits for well scaled f.
+/// Use `with_step(h)` to override it, e.g. for functions varying on a much
+/// smaller scale than x itself.
+///
+/// Newton-Raphson with a numerical derivative still converges, but errors in
+/// the slope cost some of its quadratic convergence, so expect a few extra
+/// iterations.
+///
+/// The second derivative uses the second central difference
+///
+/// d2f(x) ≈ (f(x+h) - 2f(x) + f(x-h)) / h^2
+///
+/// with its own larger step of 1e-4 * max(|x|, 1), since cancellation is worse
+/// here.  Richardson extrapolation is not applied to it.
+///
+/// Richardson extrapolation can be enabled with `richardson(levels)`.  It
+/// evaluates central differences at h, h/2, h/4, ... and combines them to
+/// cancel successive error terms, reaching O(h^(2*levels+2)) truncation error.
+/// Each level costs two extra evaluations of f per derivative, so
+/// `2 * (levels + 1)` in total.  Since truncation error is eliminated, a larger
+/// base step is used to keep cancellation in check.
+pub struct RealFnNumericalDiff<'a, F>
+where
+    F: 'a + ?Sized + Fn(f64) -> f64,
+{
+    pub f: &'a F,
+    step: Option<f64>,
+    levels: usize,
+}
+
+impl<'a, F> RealFnNumericalDiff<'a, F>
+where
+    F: 'a + ?Sized + Fn(f64) -> f64,
+{
+    pub fn new(f: &'a F) -> RealFnNumericalDiff<'a, F> {
+        RealFnNumericalDiff {
+            f,
+            step: None,
+            levels: 0,
+        }
+    }
+
+    /// Use a fixed step `h` instead of one scaled to |x|.
+    ///
+    /// With `richardson` this is the base step, which is then halved at each
+    /// level.
+    pub fn with_step(self, h: f64) -> RealFnNumericalDiff<'a, F> {
+        assert!(h > 0.0);
+        assert!(h.is_finite());
+        RealFnNumericalDiff {
+            step: Some(h),
+            ..self
+        }
+    }
+
+    /// Use Richardson extrapolation with the given number of levels.
+    pub fn richardson(self, levels: usize) -> RealFnNumericalDiff<'a, F> {
+        RealFnNumericalDiff { levels, ..self }
+    }
+
+    /// Base step size used at x.
+    fn step_at(&self, x: f64) -> f64 {
+        match self.step {
+            Some(h) => h,
+            None if self.levels == 0 => 1e-6 * x.abs().max(1.0),
+            None => 1e-2 * x.abs().max(1.0),
+        }
+    }
+
+    fn central_diff(&self, x: f64, h: f64) -> f64 {
+        ((self.f)(x + h) - (self.f)(x - h)) / (2.0 * h)
+    }
+}
+
+impl<'a, F> RealFnEval for RealFnNumericalDiff<'a, F>
+where
+    F: 'a + ?Sized + Fn(f64) -> f64,
+{
+    fn eval_f(&self, x: f64) -> f64 {
+        (self.f)(x)
+    }
+}
+
+impl<'a, F> RealDfEval for RealFnNumericalDiff<'a, F>
+where
+    F: 'a + ?Sized + Fn(f64) -> f64,
+{
+    fn eval_df(&self, x: f64) -> f64 {
+        let mut h = self.step_at(x);
+        if self.levels == 0 {
+            return self.central_diff(x, h);
+        }
+
+        // tableau row for step h/2^i, extrapolated in place
+        let mut row = Vec::with_capacity(self.levels + 1);
+        for i in 0..(self.levels + 1) {
+            let mut d = self.central_diff(x, h);
+            let mut factor = 4.0;
+            for prev in row.iter_mut().take(i) {
+                let next = d + (d - *prev) / (factor - 1.0);
+                *prev = d;
+                d = next;
+                factor *= 4.0;
+            }
+            row.push(d);
+            h *= 0.5;
+        }
+        row[self.levels]
+    }
+}
+
+impl<'a, F> RealD2fEval for RealFnNumericalDiff<'a, F>
+where
+    F: 'a + ?Sized + Fn(f64) -> f64,
+{
+    fn eval_d2f(&self, x: f64) -> f64 {
+        let h = 1e-4 * x.abs().max(1.0);
+        ((self.f)(x + h) - 2.0 * (self.f)(x) + (self.f)(x - h)) / (h * h)
+    }
+}
+
+/// Wraps complex function to implement RealFnEval and RealDfEval via the
+/// complex step.
+///
+/// For f analytic and real-valued on the real line,
+///
+/// df(x) ≈ Im(f(x + ih)) / h
+///
+/// There is no subtraction, hence no cancellation, and so h can be taken tiny.
+/// With the default h = 1e-30 the truncation error is far below f64 precision.
+/// Each derivative costs a single complex evaluation of f.
+///
+/// The supplied function must only use operations that extend analytically to
+/// complex arguments.  In particular, abs() and comparisons destroy the
+/// imaginary part's meaning.
+pub struct ComplexStepDf<'a, F>
+where
+    F: 'a + ?Sized + Fn(Complex<f64>) -> Complex<f64>,
+{
+    pub f: &'a F,
+    pub h: f64,
+}
+
+impl<'a, F> ComplexStepDf<'a, F>
+where
+    F: 'a + ?Sized + Fn(Complex<f64>) -> Complex<f64>,
+{
+    pub fn new(f: &'a F) -> ComplexStepDf<'a, F> {
+        ComplexStepDf { f, h: 1e-30 }
+    }
+}
+
+impl<'a, F> RealFnEval for ComplexStepDf<'a, F>
+where
+    F: 'a + ?Sized + Fn(Complex<f64>) -> Complex<f64>,
+{
+    fn eval_f(&self, x: f64) -> f64 {
+        (self.f)(Complex::new(x, 0.0)).re
+    }
+}
+
+impl<'a, F> RealDfEval for ComplexStepDf<'a, F>
+where
+    F: 'a + ?Sized + Fn(Complex<f64>) -> Complex<f64>,
+{
+    fn eval_df(&self, x: f64) -> f64 {
+        (self.f)(Complex::new(x, self.h)).im / self.h
+    }
+}
+
+/// Wraps function returning the terms of a sum to implement RealFnEval with
+/// compensated summation.
+///
+/// Near a root, f(x) is often the small difference of large terms, e.g.
+/// Costabile06 Example Five, 17x^2 - (1 - 5x)^2.  Naive summation loses the
+/// low-order bits of each partial sum, so the residual near the root is mostly
+/// rounding noise and FnResidual becomes unreliable.  Here the terms are added
+/// with the Kahan-Babuška (Neumaier) algorithm, whose error does not grow with
+/// the size of the cancelling terms.
+///
+/// Compensation only removes error from the summation itself.  It helps when
+/// each term is computed accurately, so expand products into separate terms
+/// (17x^2, -1, 10x, -25x^2 rather than 17x^2 and -(1 - 5x)^2).
+///
+/// The function may return any slice-like type; a fixed-size array avoids
+/// allocation.
+pub struct KahanFn<'a, F>
+where
+    F: 'a + ?Sized,
+{
+    pub f: &'a F,
+}
+
+impl<'a, F, T> KahanFn<'a, F>
+where
+    F: 'a + ?Sized + Fn(f64) -> T,
+    T: AsRef<[f64]>,
+{
+    pub fn new(f: &'a F) -> KahanFn<'a, F> {
+        KahanFn { f }
+    }
+}
+
+impl<'a, F, T> RealFnEval for KahanFn<'a, F>
+where
+    F: 'a + ?Sized + Fn(f64) -> T,
+    T: AsRef<[f64]>,
+{
+    fn eval_f(&self, x: f64) -> f64 {
+        let terms = (self.f)(x);
+        let mut sum = 0.0;
+        let mut comp = 0.0;
+        for &term in terms.as_ref() {
+            let t = sum + term;
+            if sum.abs() >= term.abs() {
+                comp += (sum - t) + term;
+            } else {
+                comp += (term - t) + sum;
+            }
+            sum = t;
+        }
+        sum + comp
+    }
+}
+
+/// Wraps a RealFnEval implementor and counts evaluations of f(x).
+///
+/// If the wrapped function also provides df(x) or d2f(x), those evaluations
+/// are forwarded and counted separately.
+pub struct CountingFn<'a, F>
+where
+    F: 'a + RealFnEval,
+{
+    pub f: &'a F,
+    f_evals: Cell<usize>,
+    df_evals: Cell<usize>,
+    d2f_evals: Cell<usize>,
+}
+
+impl<'a, F> CountingFn<'a, F>
+where
+    F: 'a + RealFnEval,
+{
+    pub fn new(f: &'a F) -> CountingFn<'a, F> {
+        CountingFn {
+            f,
+            f_evals: Cell::new(0),
+            df_evals: Cell::new(0),
+            d2f_evals: Cell::new(0),
+        }
+    }
+
+    /// Number of f(x) evaluations so far.
+    pub fn f_evals(&self) -> usize {
+        self.f_evals.get()
+    }
+
+    /// Number of df(x) evaluations so far.
+    pub fn df_evals(&self) -> usize {
+        self.df_evals.get()
+    }
+
+    /// Number of d2f(x) evaluations so far.
+    pub fn d2f_evals(&self) -> usize {
+        self.d2f_evals.get()
+    }
+}
+
+impl<'a, F> RealFnEval for CountingFn<'a, F>
+where
+    F: 'a + RealFnEval,
+{
+    fn eval_f(&self, x: f64) -> f64 {
+        self.f_evals.set(self.f_evals.get() + 1);
+        self.f.eval_f(x)
+    }
+
+    fn trapped(&self) -> Option<f64> {
+        self.f.trapped()
+    }
+}
+
+impl<'a, F> RealDfEval for CountingFn<'a, F>
+where
+    F: 'a + RealFnEval + RealDfEval,
+{
+    fn eval_df(&self, x: f64) -> f64 {
+        self.df_evals.set(self.df_evals.get() + 1);
+        self.f.eval_df(x)
+    }
+}
+
+impl<'a, F> RealD2fEval for CountingFn<'a, F>
+where
+    F: 'a + RealFnEval + RealD2fEval,
+{
+    fn eval_d2f(&self, x: f64) -> f64 {
+        self.d2f_evals.set(self.d2f_evals.get() + 1);
+        self.f.eval_d2f(x)
+    }
+}
+
+/// Wraps a RealFnEval implementor and memoizes recent evaluations of f(x).
+///
+/// Values are keyed on the bit pattern of x, so only exactly identical inputs
+/// hit the cache.  Nearby points, or `0.0` versus `-0.0`, are evaluated anew.
+/// The cache is cleared whenever it fills up, which keeps memory bounded for
+/// long solves.  Requires the `std` feature.
+#[cfg(feature = "std")]
+pub struct CachingFn<'a, F>
+where
+    F: 'a + RealFnEval,
+{
+    pub f: &'a F,
+    capacity: usize,
+    cache: RefCell<HashMap<u64, f64>>,
+}
+
+#[cfg(feature = "std")]
+impl<'a, F> CachingFn<'a, F>
+where
+    F: 'a + RealFnEval,
+{
+    pub fn new(f: &'a F) -> CachingFn<'a, F> {
+        CachingFn::with_capacity(f, 64)
+    }
+
+    /// Cache holding at most `capacity` evaluations.
+    pub fn with_capacity(f: &'a F, capacity: usize) -> CachingFn<'a, F> {
+        assert!(capacity > 0, "capacity must be positive");
+        CachingFn {
+            f,
+            capacity,
+            cache: RefCell::new(HashMap::with_capacity(capacity)),
+        }
+    }
+
+    /// Forget all cached evaluations.
+    pub fn clear(&self) {
+        self.cache.borrow_mut().clear();
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, F> RealFnEval for CachingFn<'a, F>
+where
+    F: 'a + RealFnEval,
+{
+    fn eval_f(&self, x: f64) -> f64 {
+        let key = x.to_bits();
+        if let Some(&fx) = self.cache.borrow().get(&key) {
+            return fx;
+        }
+
+        let fx = self.f.eval_f(x);
+        let mut cache = self.cache.borrow_mut();
+        if cache.len() >= self.capacity {
+            cache.clear();
+        }
+        cache.insert(key, fx);
+        fx
+    }
+
+    fn trapped(&self) -> Option<f64> {
+        self.f.trapped()
+    }
+}
+
+/// Wraps an existing function to trap the first non-finite evaluation.
+///
+/// Solvers usually notice a NaN or infinite f(x) only indirectly, once it has
+/// produced a bad step (`RootError::IteratedToNaN`), by which time the input
+/// that caused it is lost.  This wrapper remembers the first x where f, df,
+/// or d2f came out non-finite and reports it through `RealFnEval::trapped`.
+///
+/// The iterative solvers (`newton_raphson`, `halley_method`, `householder3`,
+/// `schroder`, `custom_iterative` and their variants) check the trap after
+/// every evaluation and return `RootError::NonFiniteEvaluation { x }` the
+/// moment it fires.  Other solvers see the values passed through unchanged and
+/// carry on as they would without the wrapper; pass their result through
+/// `check()` to get the same error after the fact.
+///
+/// Use a fresh instance for each solve (or call `reset()` in between).
+pub struct TrapNaNFn<'a, F>
+where
+    F: 'a + RealFnEval,
+{
+    pub f: &'a F,
+    trapped: Cell<Option<f64>>,
+}
+
+impl<'a, F> TrapNaNFn<'a, F>
+where
+    F: 'a + RealFnEval,
+{
+    pub fn new(f: &'a F) -> TrapNaNFn<'a, F> {
+        TrapNaNFn {
+            f,
+            trapped: Cell::new(None),
+        }
+    }
+
+    /// Forget any trapped evaluation.
+    pub fn reset(&self) {
+        self.trapped.set(None);
+    }
+
+    /// Replace a solver result with `RootError::NonFiniteEvaluation` if a
+    /// non-finite evaluation was trapped along the way.
+    pub fn check<T>(&self, result: Result<T, RootError>) -> Result<T, RootError> {
+        match self.trapped.get() {
+            Some(x) => Err(RootError::NonFiniteEvaluation { x }),
+            None => result,
+        }
+    }
+
+    fn trap(&self, x: f64, value: f64) -> f64 {
+        if !value.is_finite() && self.trapped.get().is_none() {
+            self.trapped.set(Some(x));
+        }
+        value
+    }
+}
+
+impl<'a, F> RealFnEval for TrapNaNFn<'a, F>
+where
+    F: 'a + RealFnEval,
+{
+    fn eval_f(&self, x: f64) -> f64 {
+        self.trap(x, self.f.eval_f(x))
+    }
+
+    fn trapped(&self) -> Option<f64> {
+        self.trapped.get()
+    }
+}
+
+impl<'a, F> RealDfEval for TrapNaNFn<'a, F>
+where
+    F: 'a + RealFnEval + RealDfEval,
+{
+    fn eval_df(&self, x: f64) -> f64 {
+        self.trap(x, self.f.eval_df(x))
+    }
+}
+
+impl<'a, F> RealD2fEval for TrapNaNFn<'a, F>
+where
+    F: 'a + RealFnEval + RealD2fEval,
+{
+    fn eval_d2f(&self, x: f64) -> f64 {
+        self.trap(x, self.f.eval_d2f(x))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bracket::Bounds;
+    use convergence::{DeltaX, MinIterations};
+    use solver::{bisection, halley_method, newton_raphson};
+    use std::f64;
+
+    #[test]
+    fn test_kahan_fn() {
+        // root at x=0.5 hidden between two huge cancelling terms
+        let terms = |x: f64| [1e16, x, -1e16, -0.5];
+        let naive = |x: f64| terms(x).iter().sum::<f64>();
+        let f = KahanFn::new(&terms);
+
+        for &dx in [1e-3, -1e-3, 0.25].iter() {
+            let x = 0.5 + dx;
+            assert!((f.eval_f(x) - dx).abs() < 1e-15);
+
+            // naive summation loses x entirely, even getting the sign wrong
+            assert!((naive(x) - dx).abs() > 0.1);
+        }
+        assert_eq!(0.0, f.eval_f(0.5));
+
+        // expanded Costabile06 Example Five matches its factored form
+        let terms = |x: f64| [17.0 * x * x, -1.0, 10.0 * x, -25.0 * x * x];
+        let f = KahanFn::new(&terms);
+        let x = 0.4;
+        let exact = 17.0 * x * x - (1.0 - 5.0 * x) * (1.0 - 5.0 * x);
+        assert!((f.eval_f(x) - exact).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_numeric_from_halley() {
+        let in_f = |x: f64| x.cos() - x * x * x;
+        let f = RealFnAndFirstSecond::numeric_from(&in_f);
+
+        let x = 0.7;
+        assert!((f.eval_df(x) - (-x.sin() - 3.0 * x * x)).abs() < 1e-8);
+        assert!((f.eval_d2f(x) - (-x.cos() - 6.0 * x)).abs() < 1e-6);
+
+        let conv = DeltaX::new(1e-9);
+        let root = halley_method(&f, 1.0, &conv, 100).expect("root");
+        assert!((root - 0.865474033101614).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_trap_nan_fn() {
+        // newton lands exactly on 0.5 after one step, where f is broken
+        let in_f = |x: f64| if x == 0.5 { f64::NAN } else { x - 0.5 };
+        let in_df = |_: f64| 1.0;
+        let inner = RealFnAndFirst::new(&in_f, &in_df);
+        let conv = DeltaX::new(1e-9);
+
+        match newton_raphson(&inner, 3.0, &conv, 100) {
+            Err(RootError::IteratedToNaN { .. }) => {}
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+
+        // the solve stops at the NaN itself, without check()
+        let counted = CountingFn::new(&inner);
+        let f = TrapNaNFn::new(&counted);
+        match newton_raphson(&f, 3.0, &conv, 100) {
+            Err(RootError::NonFiniteEvaluation { x }) => assert_eq!(x, 0.5),
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+        assert_eq!(counted.f_evals(), 2);
+
+        // bisection carries on past the trap, so check() reports it after
+        let in_h = |x: f64| if x == 0.5 { f64::INFINITY } else { x - 0.75 };
+        let inner_h = RealFn::new(&in_h);
+        let h = TrapNaNFn::new(&inner_h);
+        let result = bisection(&h, &Bounds::new(0.0, 1.0), &conv, 100);
+        assert!(result.is_ok());
+        match h.check(result) {
+            Err(RootError::NonFiniteEvaluation { x }) => assert_eq!(x, 0.5),
+            _ => {
+                panic!("incorrect error type");
+            }
+        }
+
+        f.reset();
+        assert_eq!(f.trapped(), None);
+
+        // nothing trapped on a clean solve
+        let in_g = |x: f64| x * x - 2.0;
+        let in_dg = |x: f64| 2.0 * x;
+        let inner = RealFnAndFirst::new(&in_g, &in_dg);
+        let g = TrapNaNFn::new(&inner);
+        assert!(g.check(newton_raphson(&g, 1.0, &conv, 100)).is_ok());
+        assert_eq!(g.trapped(), None);
+    }
+
+    #[test]
+    fn test_counting_fn() {
+        let in_f = |x: f64| x * x;
+        let inner = RealFn::new(&in_f);
+        let f = CountingFn::new(&inner);
+        assert_eq!(f.f_evals(), 0);
+
+        assert_eq!(f.eval_f(3.0), 9.0);
+        assert_eq!(f.eval_f(3.0), 9.0);
+        assert_eq!(f.f_evals(), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_caching_fn() {
+        let in_f = |x: f64| x.exp() - 2.0;
+        let inner = RealFn::new(&in_f);
+        let counted = CountingFn::new(&inner);
+        let f = CachingFn::new(&counted);
+
+        assert_eq!(f.eval_f(0.5), 0.5f64.exp() - 2.0);
+        assert_eq!(f.eval_f(0.5), 0.5f64.exp() - 2.0);
+        assert_eq!(counted.f_evals(), 1);
+
+        // only identical bit patterns hit
+        f.eval_f(0.5 + f64::EPSILON);
+        f.eval_f(0.0);
+        f.eval_f(-0.0);
+        assert_eq!(counted.f_evals(), 4);
+
+        f.clear();
+        f.eval_f(0.5);
+        assert_eq!(counted.f_evals(), 5);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_caching_fn_capacity() {
+        let in_f = |x: f64| x;
+        let inner = RealFn::new(&in_f);
+        let counted = CountingFn::new(&inner);
+        let f = CachingFn::with_capacity(&counted, 2);
+
+        f.eval_f(1.0);
+        f.eval_f(2.0);
+        f.eval_f(1.0);
+        assert_eq!(counted.f_evals(), 2);
+
+        // third distinct value flushes the cache
+        f.eval_f(3.0);
+        f.eval_f(1.0);
+        assert_eq!(counted.f_evals(), 4);
+    }
+
+    #[test]
+    fn test_counting_fn_newton() {
+        let in_f = |x: f64| x * x - 2.0;
+        let in_df = |x: f64| 2.0 * x;
+        let in_d2f = |_: f64| 2.0;
+        let inner = RealFnAndFirstSecond::new(&in_f, &in_df, &in_d2f);
+
+        let f = CountingFn::new(&inner);
+        let conv = DeltaX::new(1e-12);
+        let finish = MinIterations::new(&conv, 0);
+        newton_raphson(&f, 1.0, &finish, 100).expect("newton root");
+        assert_eq!(f.f_evals(), finish.calls() + 1);
+        assert_eq!(f.df_evals(), finish.calls());
+        assert_eq!(f.d2f_evals(), 0);
+
+        // halley additionally pays for d2f every step
+        let g = CountingFn::new(&inner);
+        halley_method(&g, 1.0, &conv, 100).expect("halley root");
+        assert!(g.d2f_evals() > 0);
+        assert_eq!(g.d2f_evals(), g.df_evals());
+    }
+
+    #[test]
+    fn test_numerical_second_from_first() {
+        let in_f = |x: f64| x.sin();
+        let in_df = |x: f64| x.cos();
+        let f = RealFnAndFirstNumericalSecond::new(&in_f, &in_df);
+
+        for x in [-2.0, 0.0, 0.5, 3.0, 100.0] {
+            assert_eq!(f.eval_f(x), x.sin());
+            assert_eq!(f.eval_df(x), x.cos());
+            assert!((f.eval_d2f(x) + x.sin()).abs() < 1e-9 * x.abs().max(1.0));
+        }
+
+        // a coarse fixed step is noticeably worse
+        let coarse = RealFnAndFirstNumericalSecond::new(&in_f, &in_df).with_step(0.1);
+        let err_coarse = (coarse.eval_d2f(1.0) + 1f64.sin()).abs();
+        assert!(err_coarse > 1e-4);
+        assert!(err_coarse < 1e-2);
+    }
+
+    #[test]
+    fn test_complex_step_df() {
+        // f(x) = e^x sin(x) / (x^2 + 1), with its real counterparts
+        let cf = |z: Complex<f64>| z.exp() * z.sin() / (z * z + 1.0);
+        let rf = |x: f64| x.exp() * x.sin() / (x * x + 1.0);
+        let rdf = |x: f64| {
+            let d = x * x + 1.0;
+            x.exp() * ((x.sin() + x.cos()) * d - 2.0 * x * x.sin()) / (d * d)
+        };
+
+        let f = ComplexStepDf::new(&cf);
+        let numerical = RealFnNumericalDiff::new(&rf);
+
+        for x in [-3.0, -1.0, -0.1, 0.0, 0.5, 2.0, 7.5] {
+            let exact = rdf(x);
+            let scale = exact.abs().max(1.0);
+            let err_cstep = (f.eval_df(x) - exact).abs() / scale;
+            let err_central = (numerical.eval_df(x) - exact).abs() / scale;
+
+            assert_eq!(f.eval_f(x), rf(x));
+            assert!(
+                err_cstep <= 4.0 * f64::EPSILON,
+                "complex step error {} at x={}",
+                err_cstep,
+                x
+            );
+            assert!(
+                err_cstep * 1000.0 < err_central,
+                "complex step {} vs central {} at x={}",
+                err_cstep,
+                err_central,
+                x
+            );
+        }
+    }
+
+    #[test]
+    fn test_complex_step_tiny_slope() {
+        // slope of 1e-20 is far below anything finite differences can resolve
+        let cf = |z: Complex<f64>| 1e-20 * z + 1.0;
+        let f = ComplexStepDf::new(&cf);
+
+        assert_eq!(f.eval_df(3.0), 1e-20);
+        assert_eq!(f.eval_f(0.0), 1.0);
     }
 }
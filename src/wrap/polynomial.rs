@@ -0,0 +1,632 @@
+//! Polynomials in one real variable.
+//!
+//! Coefficients are stored in ascending power order, so `[c0, c1, c2]`
+//! represents c0 + c1*x + c2*x^2.
+//!
+//! `Polynomial` implements RealFnEval, RealDfEval, and RealD2fEval, so it can
+//! be passed straight to the solvers without hand-writing derivatives.
+//!
+//! # Examples
+//! ```
+//! use rootfind::wrap::Polynomial;
+//!
+//! // (x^2 + 2x + 5)(x^2 - 2x + 10)
+//! let p = Polynomial::new(vec![50.0, 10.0, 11.0, 0.0, 1.0]);
+//!
+//! // two complex conjugate pairs, both extracted as real quadratics
+//! let factors = p.bairstow_factors(100, 1e-12).expect("factors");
+//! assert_eq!(factors.len(), 2);
+//!
+//! // or all four complex roots at once
+//! let roots = p.aberth(100, 1e-12).expect("roots");
+//! assert_eq!(roots.len(), 4);
+//! ```
+use alloc::vec::Vec;
+use core::f64;
+
+use num_complex::Complex;
+
+use bracket::{isolate_roots, Bounds};
+use convergence::DeltaX;
+use solver::{bisection, RootError};
+use wrap::{RealD2fEval, RealDfEval, RealFnEval};
+
+/// Polynomial with real coefficients.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Polynomial {
+    coeffs: Vec<f64>,
+}
+
+impl Polynomial {
+    /// Create polynomial from coefficients in ascending power order.
+    ///
+    /// Zero coefficients on the highest powers are dropped.  This will panic if
+    /// no coefficients are supplied or any are not finite.
+    pub fn new(mut coeffs: Vec<f64>) -> Polynomial {
+        assert!(!coeffs.is_empty());
+        assert!(coeffs.iter().all(|c| c.is_finite()));
+        while coeffs.len() > 1 && coeffs[coeffs.len() - 1] == 0.0 {
+            coeffs.pop();
+        }
+        Polynomial { coeffs }
+    }
+
+    /// Coefficients in ascending power order.
+    pub fn coeffs(&self) -> &[f64] {
+        &self.coeffs
+    }
+
+    /// Degree of the polynomial.  The zero polynomial has degree zero.
+    pub fn degree(&self) -> usize {
+        self.coeffs.len() - 1
+    }
+
+    /// Evaluate p(x) via Horner's method.
+    pub fn eval(&self, x: f64) -> f64 {
+        self.coeffs.iter().rev().fold(0.0, |p, c| p * x + c)
+    }
+
+    /// Evaluate p(x), p'(x), and p''(x) in a single Horner pass.
+    pub fn eval_with_derivatives(&self, x: f64) -> (f64, f64, f64) {
+        let mut p = 0.0;
+        let mut dp = 0.0;
+        let mut d2p = 0.0;
+        for c in self.coeffs.iter().rev() {
+            d2p = d2p * x + 2.0 * dp;
+            dp = dp * x + p;
+            p = p * x + c;
+        }
+        (p, dp, d2p)
+    }
+
+    /// Cauchy bound on the magnitude of the roots, 1 + max|a_i/a_n|.
+    ///
+    /// Every root, real or complex, satisfies |x| < bound, so
+    /// `Bounds::new(-bound, bound)` brackets all the real roots.  The bound is
+    /// often loose when coefficients vary wildly in size.
+    pub fn real_root_bound(&self) -> f64 {
+        let n = self.degree();
+        let lead = self.coeffs[n];
+        1.0 + self.coeffs[..n]
+            .iter()
+            .map(|c| (c / lead).abs())
+            .fold(0.0, f64::max)
+    }
+
+    /// Find the real roots inside `bounds`, in increasing order.
+    ///
+    /// Passing None searches within `real_root_bound()`, i.e. finds all real
+    /// roots.  Repeated roots are reduced with `squarefree_part` first so they
+    /// show up as sign changes, then isolated with `isolate_roots` and refined
+    /// by bisection.  Each root is reported once regardless of multiplicity.
+    pub fn real_roots_in(
+        &self,
+        bounds: Option<Bounds>,
+        max_iter: usize,
+    ) -> Result<Vec<f64>, RootError> {
+        if self.degree() == 0 {
+            return Ok(Vec::new());
+        }
+        let bounds = bounds.unwrap_or_else(|| {
+            let bound = self.real_root_bound();
+            Bounds::new(-bound, bound)
+        });
+
+        let sf = self.squarefree_part();
+        let finish = DeltaX::new(1e-9);
+        isolate_roots(&sf, &bounds, 30)
+            .iter()
+            .map(|bracket| bisection(&sf, bracket, &finish, max_iter))
+            .collect()
+    }
+
+    /// Derivative of the polynomial.
+    pub fn derivative(&self) -> Polynomial {
+        if self.degree() == 0 {
+            return Polynomial::new(vec![0.0]);
+        }
+        Polynomial::new(
+            self.coeffs[1..]
+                .iter()
+                .enumerate()
+                .map(|(i, c)| c * (i + 1) as f64)
+                .collect(),
+        )
+    }
+
+    /// Polynomial with the same roots but each of multiplicity one.
+    ///
+    /// Computed as p / gcd(p, p'), since a root of multiplicity m in p has
+    /// multiplicity m-1 in p'.  Root finders then only see simple roots, which
+    /// restores Newton's quadratic convergence.  The result is determined only
+    /// up to a constant factor.
+    ///
+    /// The GCD uses the Euclidean algorithm in floating point, treating
+    /// remainder coefficients below a relative 1e-9 as zero.  Roots that are
+    /// nearly but not exactly repeated may get merged.
+    pub fn squarefree_part(&self) -> Polynomial {
+        if self.degree() == 0 {
+            return self.clone();
+        }
+        let g = gcd(&self.coeffs, &self.derivative().coeffs);
+        let (quotient, _) = div_rem(&self.coeffs, &g);
+        Polynomial::new(quotient)
+    }
+
+    /// Extract a real quadratic factor x^2 + u*x + v using Bairstow's method.
+    ///
+    /// The `u0` and `v0` are the initial guesses for the factor.  Iteration
+    /// stops once both corrections drop below `tol`.  Complex conjugate root
+    /// pairs are found using only real arithmetic.
+    ///
+    /// Bairstow's update solves a 2x2 linear system.  If that system is nearly
+    /// singular the current guess is nudged rather than taking a wild step.
+    ///
+    /// The polynomial must have degree two or more.  On hitting the iteration
+    /// limit, `last_x` holds the latest `u`.
+    pub fn bairstow(
+        &self,
+        u0: f64,
+        v0: f64,
+        max_iter: usize,
+        tol: f64,
+    ) -> Result<(f64, f64), RootError> {
+        assert!(self.degree() >= 2);
+        assert!(u0.is_finite() && v0.is_finite());
+        assert!(tol > 0.0);
+
+        // classical form of the factor is x^2 - r*x - s
+        let mut r = -u0;
+        let mut s = -v0;
+
+        for _ in 0..max_iter {
+            let b = divide_quadratic(&self.coeffs, r, s);
+            let c = divide_quadratic(&b, r, s);
+            let c3 = if c.len() > 3 { c[3] } else { 0.0 };
+
+            // nearly singular jacobian, nudge guess
+            let det = c[2] * c[2] - c[1] * c3;
+            if det.abs() <= f64::EPSILON * (c[2] * c[2]).abs().max((c[1] * c3).abs()) {
+                r += 0.5 * (1.0 + r.abs());
+                s -= 0.25 * (1.0 + s.abs());
+                continue;
+            }
+
+            let dr = (-b[1] * c[2] + b[0] * c3) / det;
+            let ds = (-b[0] * c[2] + b[1] * c[1]) / det;
+            r += dr;
+            s += ds;
+            if !r.is_finite() || !s.is_finite() {
+                return Err(RootError::IteratedToNaN { x_new: -r });
+            }
+
+            if dr.abs() < tol && ds.abs() < tol {
+                return Ok((-r, -s));
+            }
+        }
+        Err(RootError::IterationLimit { last_x: -r })
+    }
+
+    /// Factor into real monic linear and quadratic polynomials.
+    ///
+    /// Repeatedly applies Bairstow's method, deflating the polynomial after each
+    /// quadratic factor is found.  The leading coefficient is not included in
+    /// the returned factors.
+    pub fn bairstow_factors(
+        &self,
+        max_iter: usize,
+        tol: f64,
+    ) -> Result<Vec<Polynomial>, RootError> {
+        let mut factors = Vec::new();
+        let mut rest = self.clone();
+
+        while rest.degree() > 2 {
+            let n = rest.degree();
+            let a = &rest.coeffs;
+            let (u, v) = rest.bairstow(a[n - 1] / a[n], a[n - 2] / a[n], max_iter, tol)?;
+            factors.push(Polynomial::new(vec![v, u, 1.0]));
+
+            let b = divide_quadratic(a, -u, -v);
+            rest = Polynomial::new(b[2..].to_vec());
+        }
+
+        let n = rest.degree();
+        if n > 0 {
+            let lead = rest.coeffs[n];
+            factors.push(Polynomial::new(
+                rest.coeffs.iter().map(|c| c / lead).collect(),
+            ));
+        }
+        Ok(factors)
+    }
+
+    /// Find all roots simultaneously using the Aberth-Ehrlich method.
+    ///
+    /// Each estimate z_k is corrected by
+    ///
+    /// w_k = (p/p') / (1 - (p/p') * sum_{j!=k} 1/(z_k - z_j))
+    ///
+    /// which combines a Newton step with repulsion from the other estimates.
+    /// This converges cubically for simple roots, faster than Durand-Kerner.
+    /// Initial estimates are spread on a circle whose radius is the Cauchy
+    /// bound on the root magnitudes.
+    ///
+    /// Both real and complex roots are returned; real roots come back with a
+    /// negligible imaginary part.  Iteration stops once every correction has
+    /// magnitude below `tol`.
+    ///
+    /// *Aberth, O. (1973). Iteration methods for finding all zeros of a
+    /// polynomial simultaneously. Mathematics of Computation, 27(122),
+    /// 339-344.*
+    ///
+    pub fn aberth(&self, max_iter: usize, tol: f64) -> Result<Vec<Complex<f64>>, RootError> {
+        assert!(tol > 0.0);
+
+        // a constant has no roots, however few iterations are allowed
+        let n = self.degree();
+        if n == 0 {
+            return Ok(Vec::new());
+        }
+        let radius = self.real_root_bound();
+
+        // offset angle avoids symmetric starts landing on symmetric roots
+        let mut z: Vec<Complex<f64>> = (0..n)
+            .map(|k| {
+                let theta = 2.0 * f64::consts::PI * (k as f64) / (n as f64) + 0.4;
+                Complex::from_polar(radius, theta)
+            })
+            .collect();
+
+        for _ in 0..max_iter {
+            let mut max_step: f64 = 0.0;
+            for k in 0..n {
+                let (p, dp) = self.eval_complex(z[k]);
+                if p == Complex::new(0.0, 0.0) {
+                    continue;
+                }
+
+                let ratio = p / dp;
+                let repulsion: Complex<f64> = (0..n)
+                    .filter(|&j| j != k)
+                    .map(|j| (z[k] - z[j]).inv())
+                    .sum();
+                let w = ratio / (1.0 - ratio * repulsion);
+                if !w.re.is_finite() || !w.im.is_finite() {
+                    return Err(RootError::IteratedToNaN { x_new: z[k].re });
+                }
+
+                z[k] -= w;
+                max_step = max_step.max(w.norm());
+            }
+
+            if max_step < tol {
+                return Ok(z);
+            }
+        }
+        Err(RootError::IterationLimit { last_x: z[0].re })
+    }
+
+    /// Evaluate p(z) and p'(z) at a complex argument via Horner's method.
+    fn eval_complex(&self, z: Complex<f64>) -> (Complex<f64>, Complex<f64>) {
+        let mut p = Complex::new(0.0, 0.0);
+        let mut dp = Complex::new(0.0, 0.0);
+        for c in self.coeffs.iter().rev() {
+            dp = dp * z + p;
+            p = p * z + c;
+        }
+        (p, dp)
+    }
+}
+
+/// Polynomial long division returning (quotient, remainder).
+///
+/// The divisor must have a non-zero leading coefficient.
+fn div_rem(num: &[f64], den: &[f64]) -> (Vec<f64>, Vec<f64>) {
+    let m = den.len() - 1;
+    let lead = den[m];
+    assert!(lead != 0.0);
+    if num.len() <= m {
+        return (vec![0.0], num.to_vec());
+    }
+
+    let mut rem = num.to_vec();
+    let mut quot = vec![0.0; num.len() - m];
+    for i in (0..quot.len()).rev() {
+        let q = rem[i + m] / lead;
+        quot[i] = q;
+        for (r, d) in rem[i..].iter_mut().zip(den.iter()) {
+            *r -= q * d;
+        }
+    }
+    rem.truncate(m.max(1));
+    (quot, rem)
+}
+
+/// Monic greatest common divisor by the Euclidean algorithm.
+///
+/// Remainder coefficients small relative to the dividend count as zero.
+fn gcd(a: &[f64], b: &[f64]) -> Vec<f64> {
+    let mut a = a.to_vec();
+    let mut b = b.to_vec();
+    loop {
+        let scale = a.iter().fold(0.0, |m: f64, c| m.max(c.abs()));
+        while b.len() > 1 && b[b.len() - 1].abs() <= 1e-9 * scale {
+            b.pop();
+        }
+        if b.len() == 1 && b[0].abs() <= 1e-9 * scale {
+            let lead = a[a.len() - 1];
+            return a.iter().map(|c| c / lead).collect();
+        }
+        let (_, rem) = div_rem(&a, &b);
+        a = b;
+        b = rem;
+    }
+}
+
+/// Synthetic division by x^2 - r*x - s.
+///
+/// Returns the `b` coefficients of the classical Bairstow recurrence.  The
+/// quotient is `b[2..]` and the remainder is b[1]*(x - r) + b[0].
+fn divide_quadratic(a: &[f64], r: f64, s: f64) -> Vec<f64> {
+    let n = a.len();
+    let mut b = vec![0.0; n];
+    for i in (0..n).rev() {
+        let b1 = if i + 1 < n { b[i + 1] } else { 0.0 };
+        let b2 = if i + 2 < n { b[i + 2] } else { 0.0 };
+        b[i] = a[i] + r * b1 + s * b2;
+    }
+    b
+}
+
+impl RealFnEval for Polynomial {
+    fn eval_f(&self, x: f64) -> f64 {
+        self.eval(x)
+    }
+}
+
+impl RealDfEval for Polynomial {
+    fn eval_df(&self, x: f64) -> f64 {
+        self.eval_with_derivatives(x).1
+    }
+}
+
+impl RealD2fEval for Polynomial {
+    fn eval_d2f(&self, x: f64) -> f64 {
+        self.eval_with_derivatives(x).2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use convergence::FnResidual;
+    use solver::{halley_method, newton_raphson};
+
+    fn assert_has_factor(factors: &[Polynomial], expected: &[f64]) {
+        assert!(
+            factors.iter().any(|p| p.degree() + 1 == expected.len()
+                && p.coeffs()
+                    .iter()
+                    .zip(expected.iter())
+                    .all(|(c, e)| (c - e).abs() < 1e-9)),
+            "missing factor {:?} in {:?}",
+            expected,
+            factors
+        );
+    }
+
+    #[test]
+    fn test_new_trims_leading_zeros() {
+        let p = Polynomial::new(vec![1.0, 2.0, 0.0, 0.0]);
+        assert_eq!(p.degree(), 1);
+        assert_eq!(p.coeffs(), &[1.0, 2.0]);
+
+        let p = Polynomial::new(vec![0.0, 0.0]);
+        assert_eq!(p.degree(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_empty() {
+        let _ = Polynomial::new(Vec::new());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_nan() {
+        let _ = Polynomial::new(vec![1.0, f64::NAN]);
+    }
+
+    #[test]
+    fn test_bairstow_single_factor() {
+        // (x^2 + 2x + 5)(x - 3)
+        let p = Polynomial::new(vec![-15.0, -1.0, -1.0, 1.0]);
+        let (u, v) = p.bairstow(1.5, 4.0, 100, 1e-12).expect("factor");
+        assert!((u - 2.0).abs() < 1e-9);
+        assert!((v - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_bairstow_factors_complex_pairs() {
+        // (x^2 + 2x + 5)(x^2 - 2x + 10), roots -1±2i and 1±3i
+        let p = Polynomial::new(vec![50.0, 10.0, 11.0, 0.0, 1.0]);
+        let factors = p.bairstow_factors(100, 1e-12).expect("factors");
+
+        assert_eq!(factors.len(), 2);
+        assert_has_factor(&factors, &[5.0, 2.0, 1.0]);
+        assert_has_factor(&factors, &[10.0, -2.0, 1.0]);
+    }
+
+    #[test]
+    fn test_bairstow_factors_odd_degree() {
+        // 2(x^2 + 1)(x^2 - 3x + 2)(x + 4), real roots 1, 2, -4
+        // deflated cubic starts from a singular guess and must be nudged
+        let p = Polynomial::new(vec![16.0, -20.0, 18.0, -18.0, 2.0, 2.0]);
+        let factors = p.bairstow_factors(100, 1e-12).expect("factors");
+
+        assert_eq!(factors.iter().map(|p| p.degree()).sum::<usize>(), 5);
+        assert_has_factor(&factors, &[1.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_aberth_roots_of_unity() {
+        // x^5 - 1
+        let p = Polynomial::new(vec![-1.0, 0.0, 0.0, 0.0, 0.0, 1.0]);
+        let roots = p.aberth(100, 1e-14).expect("roots");
+        assert_eq!(roots.len(), 5);
+
+        for k in 0..5 {
+            let theta = 2.0 * f64::consts::PI * (k as f64) / 5.0;
+            let expected = Complex::from_polar(1.0, theta);
+            assert!(
+                roots.iter().any(|z| (z - expected).norm() < 1e-12),
+                "missing root {} in {:?}",
+                expected,
+                roots
+            );
+        }
+    }
+
+    #[test]
+    fn test_aberth_real_roots() {
+        // (x - 1)(x - 2)(x + 3)
+        let p = Polynomial::new(vec![6.0, -7.0, 0.0, 1.0]);
+        let roots = p.aberth(100, 1e-14).expect("roots");
+
+        let mut real: Vec<f64> = roots
+            .iter()
+            .map(|z| {
+                assert!(z.im.abs() < 1e-12);
+                z.re
+            })
+            .collect();
+        real.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        for (r, e) in real.iter().zip([-3.0, 1.0, 2.0].iter()) {
+            assert!((r - e).abs() < 1e-12, "root wanted={}, got={}", e, r);
+        }
+    }
+
+    #[test]
+    fn test_aberth_constant() {
+        let p = Polynomial::new(vec![3.0]);
+        assert!(p.aberth(100, 1e-12).expect("roots").is_empty());
+        assert!(p.aberth(0, 1e-12).expect("roots").is_empty());
+    }
+
+    #[test]
+    fn test_real_root_bound() {
+        // (x - 1)(x + 2)(x - 3)(x^2 + 1)
+        let p = Polynomial::new(vec![6.0, -5.0, 4.0, -4.0, -2.0, 1.0]);
+        let bound = p.real_root_bound();
+        assert_eq!(bound, 7.0);
+
+        for z in p.aberth(100, 1e-12).expect("roots") {
+            assert!(z.norm() < bound);
+        }
+
+        // leading coefficient is divided out
+        let p = Polynomial::new(vec![-8.0, 0.0, 2.0]);
+        assert_eq!(p.real_root_bound(), 5.0);
+    }
+
+    #[test]
+    fn test_real_roots_in() {
+        // (x - 1)(x + 2)(x - 3)(x^2 + 1)
+        let p = Polynomial::new(vec![6.0, -5.0, 4.0, -4.0, -2.0, 1.0]);
+
+        let roots = p.real_roots_in(None, 100).expect("roots");
+        assert_eq!(roots.len(), 3);
+        for (r, e) in roots.iter().zip([-2.0, 1.0, 3.0].iter()) {
+            assert!((r - e).abs() < 1e-8, "root wanted={}, got={}", e, r);
+        }
+
+        let roots = p
+            .real_roots_in(Some(Bounds::new(0.0, 2.0)), 100)
+            .expect("roots");
+        assert_eq!(roots.len(), 1);
+        assert!((roots[0] - 1.0).abs() < 1e-8);
+
+        // triple root at 1 still found once
+        let p = Polynomial::new(vec![2.0, -7.0, 9.0, -5.0, 1.0]);
+        let roots = p.real_roots_in(None, 100).expect("roots");
+        assert_eq!(roots.len(), 2);
+    }
+
+    #[test]
+    fn test_eval_traits_ford95_seven() {
+        // x^20 - 1 against the hand-coded Ford95 Example Seven derivatives
+        let mut coeffs = vec![0.0; 21];
+        coeffs[0] = -1.0;
+        coeffs[20] = 1.0;
+        let p = Polynomial::new(coeffs);
+
+        let f = |x: f64| x.powi(20) - 1.;
+        let df = |x: f64| 20. * x.powi(19);
+        let d2f = |x: f64| 380. * x.powi(18);
+
+        for &x in &[-1.3, -1.0, -0.5, 0.0, 0.3, 1.0, 1.2, 2.0] {
+            let tol = 1e-13 * d2f(x).abs().max(1.0);
+            assert!((p.eval_f(x) - f(x)).abs() < tol, "f at {}", x);
+            assert!((p.eval_df(x) - df(x)).abs() < tol, "df at {}", x);
+            assert!((p.eval_d2f(x) - d2f(x)).abs() < tol, "d2f at {}", x);
+        }
+    }
+
+    #[test]
+    fn test_eval_with_derivatives() {
+        // 2 - 3x + x^3
+        let p = Polynomial::new(vec![2.0, -3.0, 0.0, 1.0]);
+        assert_eq!(p.eval_with_derivatives(2.0), (4.0, 9.0, 12.0));
+        assert_eq!(p.eval_with_derivatives(0.0), (2.0, -3.0, 0.0));
+
+        let dp = p.derivative();
+        assert_eq!(p.eval_df(-1.5), dp.eval(-1.5));
+        assert_eq!(p.eval_d2f(-1.5), dp.derivative().eval(-1.5));
+
+        // constants have vanishing derivatives
+        let p = Polynomial::new(vec![7.0]);
+        assert_eq!(p.eval_with_derivatives(3.0), (7.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_solvers_on_polynomial() {
+        // x^3 - 2x - 5, Wallis's example
+        let p = Polynomial::new(vec![-5.0, -2.0, 0.0, 1.0]);
+        let root = 2.0945514815423265;
+        let conv = FnResidual::new(1e-12);
+
+        let x = newton_raphson(&p, 2.0, &conv, 100).expect("root");
+        assert!((x - root).abs() < 1e-12);
+        let x = halley_method(&p, 2.0, &conv, 100).expect("root");
+        assert!((x - root).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_squarefree_part() {
+        // (x-1)^3 (x-2)
+        let p = Polynomial::new(vec![2.0, -7.0, 9.0, -5.0, 1.0]);
+        let sf = p.squarefree_part();
+        assert_eq!(sf.degree(), 2);
+
+        // (x-1)(x-2) up to scaling
+        let lead = sf.coeffs()[2];
+        for (c, e) in sf.coeffs().iter().zip([2.0, -3.0, 1.0].iter()) {
+            assert!((c / lead - e).abs() < 1e-9, "got {:?}", sf);
+        }
+    }
+
+    #[test]
+    fn test_squarefree_part_simple_roots() {
+        // (x-1)(x+2)(x-3) is already squarefree
+        let p = Polynomial::new(vec![6.0, -5.0, -2.0, 1.0]);
+        assert_eq!(p.squarefree_part().degree(), 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bairstow_degree_too_low() {
+        let p = Polynomial::new(vec![1.0, 1.0]);
+        let _ = p.bairstow(0.0, 0.0, 100, 1e-12);
+    }
+}
@@ -0,0 +1,62 @@
+extern crate rootfind;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rootfind::bracket::Bounds;
+use rootfind::convergence::DeltaX;
+use rootfind::solver::{bisection, newton_raphson, newton_raphson_trace_scratch, SolverScratch};
+use rootfind::wrap::{RealFn, RealFnAndFirst};
+
+/// Forwards to the system allocator, counting allocations made by threads
+/// which enabled `COUNTING`.
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    // the test harness allocates from its own threads, so only count ours
+    static COUNTING: Cell<bool> = const { Cell::new(false) };
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if COUNTING.with(|c| c.get()) {
+            ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        }
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+#[test]
+fn test_solvers_allocation_free() {
+    let in_f = |x: f64| -x * x + 2.0 * x + 1.0;
+    let in_df = |x: f64| -2.0 * x + 2.0;
+    let f = RealFnAndFirst::new(&in_f, &in_df);
+    let g = RealFn::new(&in_f);
+    let conv = DeltaX::new(1e-9);
+    let b = Bounds::new(2.0, 3.0);
+
+    // warm up scratch so it has capacity
+    let mut scratch = SolverScratch::new();
+    newton_raphson_trace_scratch(&f, 3.0, &conv, 100, &mut scratch).expect("root");
+
+    COUNTING.with(|c| c.set(true));
+    let before = ALLOCATIONS.load(Ordering::SeqCst);
+    for _ in 0..1000 {
+        newton_raphson(&f, 3.0, &conv, 100).expect("root");
+        bisection(&g, &b, &DeltaX::new(1e-9), 100).expect("root");
+        newton_raphson_trace_scratch(&f, 3.0, &conv, 100, &mut scratch).expect("root");
+    }
+    let after = ALLOCATIONS.load(Ordering::SeqCst);
+    COUNTING.with(|c| c.set(false));
+    assert_eq!(before, after);
+}
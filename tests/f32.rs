@@ -0,0 +1,78 @@
+extern crate rootfind;
+
+use rootfind::bracket::Bounds;
+use rootfind::convergence::{DeltaX, DualCriteria, FnResidual};
+use rootfind::solver::{
+    bisection, halley_method, newton_raphson, newton_raphson_verbose, RootError,
+};
+use rootfind::wrap::{RealFn, RealFnAndFirst, RealFnAndFirstSecond};
+
+// f32 carries about 7 significant digits, so tolerances here are far looser
+// than in the f64 tests.
+
+#[test]
+fn test_newton_f32() {
+    let in_f = |x: f32| x * x - 2.0;
+    let in_df = |x: f32| 2.0 * x;
+    let f = RealFnAndFirst::new(&in_f, &in_df);
+
+    let finish = DeltaX::new(1e-5f32);
+    let root: f32 = newton_raphson(&f, 1.0, &finish, 100).expect("found root");
+    assert!((root - 2f32.sqrt()).abs() < 1e-6, "got={}", root);
+
+    let r = newton_raphson_verbose(&f, 1.0, &finish, 100).expect("found root");
+    assert_eq!(r.root, root);
+    assert!(r.residual.abs() < 1e-6);
+}
+
+#[test]
+fn test_halley_f32() {
+    let in_f = |x: f32| x.sin();
+    let in_df = |x: f32| x.cos();
+    let in_d2f = |x: f32| -x.sin();
+    let f = RealFnAndFirstSecond::new(&in_f, &in_df, &in_d2f);
+
+    let c1 = DeltaX::new(1e-5f32);
+    let c2 = FnResidual::new(1e-6f32);
+    let finish = DualCriteria::new(&c1, &c2);
+    let root = halley_method(&f, 3.0f32, &finish, 100).expect("found root");
+    assert!((root - std::f32::consts::PI).abs() < 1e-6, "got={}", root);
+}
+
+#[test]
+fn test_bisection_f32() {
+    let in_f = |x: f32| x * x * x - x - 1.0;
+    let f = RealFn::new(&in_f);
+
+    let bounds = Bounds::new(1.0f32, 2.0);
+    let finish = DeltaX::new(1e-6f32);
+    let root = bisection(&f, &bounds, &finish, 100).expect("found root");
+    assert!((root - 1.324_718).abs() < 1e-5, "got={}", root);
+
+    // agrees with the f64 solve to f32 precision
+    let in_f64 = |x: f64| x * x * x - x - 1.0;
+    let f64_root = bisection(
+        &RealFn::new(&in_f64),
+        &Bounds::new(1.0, 2.0),
+        &DeltaX::new(1e-12),
+        100,
+    )
+    .expect("found root");
+    assert!((f64::from(root) - f64_root).abs() < 1e-5);
+}
+
+#[test]
+fn test_newton_f32_errors() {
+    // flat at the start, error still reports the f32 position
+    let in_f = |x: f32| x * x + 1.0;
+    let in_df = |x: f32| 2.0 * x;
+    let f = RealFnAndFirst::new(&in_f, &in_df);
+
+    let finish = DeltaX::new(1e-5f32);
+    match newton_raphson(&f, 0.0f32, &finish, 100) {
+        Err(RootError::ZeroDerivative { x_cur }) => assert_eq!(x_cur, 0.0),
+        _ => {
+            panic!("incorrect error type");
+        }
+    }
+}
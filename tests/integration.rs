@@ -1,6 +1,7 @@
 extern crate rootfind;
 
 use rootfind::bracket::{Bounds, BracketGenerator};
+use rootfind::convergence::DeltaX;
 use rootfind::solver::bisection;
 use rootfind::wrap::RealFn;
 
@@ -17,13 +18,13 @@ fn test_end_to_end() {
     let window_size = 0.1;
     let bounds = Bounds::new(-0.1, 6.3);
 
-    for (i, b) in BracketGenerator::new(&f, bounds, window_size)
-        .into_iter()
-        .enumerate()
-    {
+    // stop once successive midpoints agree to within 1e-9
+    let finish = DeltaX::new(1e-9);
+
+    for (i, b) in BracketGenerator::new(&f, bounds, window_size).enumerate() {
         // find root using bisection method
         let max_iterations = 100;
-        let computed_root = bisection(&f, &b, max_iterations).expect("found root");
+        let computed_root = bisection(&f, &b, &finish, max_iterations).expect("found root");
 
         // demonstrate that we found root
         let pi = std::f64::consts::PI;
@@ -31,7 +32,9 @@ fn test_end_to_end() {
 
         assert!(
             (computed_root - expected_root).abs() < 1e-9,
-            format!("got={}, wanted={}", computed_root, expected_root)
+            "got={}, wanted={}",
+            computed_root,
+            expected_root
         );
     }
 }
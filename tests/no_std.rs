@@ -0,0 +1,32 @@
+//! Smoke test for the `no_std` build.
+//!
+//! Run with `cargo test --no-default-features` to exercise the library
+//! compiled without the standard library.  Only core and libm provide the
+//! floating point math then.
+extern crate rootfind;
+
+use rootfind::bracket::Bounds;
+use rootfind::convergence::{DeltaX, FnResidual};
+use rootfind::solver::{bisection, newton_raphson};
+use rootfind::wrap::{RealFn, RealFnAndFirst};
+
+#[test]
+fn test_newton_no_std() {
+    let in_f = |x: f64| x.exp() - 2.0;
+    let in_df = |x: f64| x.exp();
+    let f = RealFnAndFirst::new(&in_f, &in_df);
+
+    let finish = FnResidual::new(1e-12);
+    let root = newton_raphson(&f, 1.0, &finish, 100).expect("found root");
+    assert!((root - 2f64.ln()).abs() < 1e-12);
+}
+
+#[test]
+fn test_bisection_no_std() {
+    let in_f = |x: f64| x.sin();
+    let f = RealFn::new(&in_f);
+
+    let finish = DeltaX::new(1e-9);
+    let root = bisection(&f, &Bounds::new(3.0, 4.0), &finish, 100).expect("found root");
+    assert!((root - std::f64::consts::PI).abs() < 1e-8);
+}